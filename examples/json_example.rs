@@ -196,6 +196,8 @@ fn robots_example() -> Result<(), Box<dyn std::error::Error>> {
 
     let txt_data = TxtData {
         permalink: "https://example.com".to_string(),
+        disallow: vec!["/drafts/".to_string()],
+        ..TxtData::default()
     };
 
     let output = txt(&txt_data);
@@ -1,5 +1,8 @@
 // Copyright © 2025 Static Data Gen. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+/// The `renderer` module contains the pluggable Markdown renderers.
+pub mod renderer;
+
 /// The `service` module contains the compiler service.
 pub mod service;
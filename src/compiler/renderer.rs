@@ -0,0 +1,166 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Pluggable Markdown rendering
+//!
+//! The compiler renders page bodies through the [`MarkdownRenderer`]
+//! trait, so the Markdown engine can be swapped without touching the rest
+//! of the pipeline. [`HtmlGeneratorRenderer`] wraps `html_generator` and is
+//! the default; [`PulldownCmarkRenderer`] is provided as a lightweight
+//! CommonMark alternative.
+
+use crate::Error;
+use html_generator::{generate_html, HtmlConfig};
+use pulldown_cmark::{html::push_html, Options, Parser};
+
+/// Renderer-independent options for converting Markdown to HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderConfig {
+    /// Whether code blocks should be syntax highlighted.
+    pub enable_syntax_highlighting: bool,
+    /// Whether a table of contents should be generated.
+    pub generate_toc: bool,
+    /// The language of the content (e.g. `en`).
+    pub language: String,
+    /// The syntax highlighting theme, if any.
+    pub syntax_theme: Option<String>,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            enable_syntax_highlighting: true,
+            generate_toc: false,
+            language: "en".to_string(),
+            syntax_theme: None,
+        }
+    }
+}
+
+/// Converts a Markdown body into HTML.
+///
+/// # Example
+///
+/// ```
+/// use staticdatagen::compiler::renderer::{MarkdownRenderer, RenderConfig};
+/// use staticdatagen::Error;
+///
+/// #[derive(Debug)]
+/// struct Verbatim;
+///
+/// impl MarkdownRenderer for Verbatim {
+///     fn render(&self, body: &str, _config: &RenderConfig) -> Result<String, Error> {
+///         Ok(format!("<pre>{}</pre>", body))
+///     }
+/// }
+///
+/// let html = Verbatim.render("# Title", &RenderConfig::default()).unwrap();
+/// assert_eq!(html, "<pre># Title</pre>");
+/// ```
+pub trait MarkdownRenderer {
+    /// Renders `body` to HTML using `config`.
+    fn render(
+        &self,
+        body: &str,
+        config: &RenderConfig,
+    ) -> Result<String, Error>;
+}
+
+/// The default renderer, backed by `html_generator`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HtmlGeneratorRenderer;
+
+impl MarkdownRenderer for HtmlGeneratorRenderer {
+    fn render(
+        &self,
+        body: &str,
+        config: &RenderConfig,
+    ) -> Result<String, Error> {
+        let html_config = HtmlConfig {
+            enable_syntax_highlighting: config
+                .enable_syntax_highlighting,
+            minify_output: false,
+            add_aria_attributes: true,
+            generate_structured_data: true,
+            generate_toc: config.generate_toc,
+            language: config.language.clone(),
+            max_input_size: usize::MAX,
+            syntax_theme: config.syntax_theme.clone(),
+        };
+
+        generate_html(body, &html_config).map_err(|e| {
+            Error::content_processing(
+                "Failed to generate HTML content",
+                Some(Box::new(e)),
+            )
+        })
+    }
+}
+
+/// A CommonMark renderer backed by `pulldown-cmark`, with tables,
+/// footnotes, strikethrough and task lists enabled.
+///
+/// Syntax highlighting and tables of contents are not supported and the
+/// corresponding [`RenderConfig`] options are ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PulldownCmarkRenderer;
+
+impl MarkdownRenderer for PulldownCmarkRenderer {
+    fn render(
+        &self,
+        body: &str,
+        _config: &RenderConfig,
+    ) -> Result<String, Error> {
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_FOOTNOTES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS;
+
+        let mut html = String::with_capacity(body.len() * 3 / 2);
+        push_html(&mut html, Parser::new_ext(body, options));
+        Ok(html)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_generator_renderer() {
+        let html = HtmlGeneratorRenderer
+            .render("# Title\n\nBody.", &RenderConfig::default())
+            .expect("Rendering should succeed");
+
+        assert!(html.contains("Title"));
+        assert!(html.contains("<p>Body.</p>"));
+    }
+
+    #[test]
+    fn test_pulldown_cmark_renderer() {
+        let html = PulldownCmarkRenderer
+            .render(
+                "| a |\n|---|\n| b |\n\n~~gone~~",
+                &RenderConfig::default(),
+            )
+            .expect("Rendering should succeed");
+
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<del>gone</del>"));
+    }
+
+    #[test]
+    fn test_renderer_as_trait_object() {
+        let renderers: Vec<Box<dyn MarkdownRenderer>> = vec![
+            Box::new(HtmlGeneratorRenderer),
+            Box::new(PulldownCmarkRenderer),
+        ];
+
+        for renderer in &renderers {
+            let html = renderer
+                .render("Hello *world*", &RenderConfig::default())
+                .expect("Rendering should succeed");
+            assert!(html.contains("<em>world</em>"));
+        }
+    }
+}
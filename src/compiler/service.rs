@@ -8,8 +8,8 @@
 //! sitemaps, and various metadata files.
 
 use anyhow::{Context, Result};
-use html_generator::{generate_html, HtmlConfig};
-use metadata_gen::extract_and_prepare_metadata;
+use metadata_gen::{extract_and_prepare_metadata, MetaTagGroups};
+use quick_xml::escape::escape;
 use rlg::{log_format::LogFormat, log_level::LogLevel};
 use rss_gen::{
     data::{RssData, RssItem},
@@ -17,28 +17,81 @@
 };
 use sitemap_gen::create_site_map_data;
 use staticweaver::{Context as TemplateContext, Engine, PageOptions};
-use std::{collections::HashMap, fs, path::Path, time::Duration};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use time::OffsetDateTime;
 
 use crate::{
+    compiler::renderer::{
+        HtmlGeneratorRenderer, MarkdownRenderer, RenderConfig,
+    },
     generators::{
         cname::{CnameConfig, CnameGenerator},
         humans::{HumansConfig, HumansGenerator},
         manifest::{ManifestConfig, ManifestGenerator},
         news_sitemap::{NewsSiteMapConfig, NewsSiteMapGenerator},
         tags::*,
+        verification::VerificationConfig,
     },
+    locales::direction,
     macro_cleanup_directories, macro_create_directories,
-    macro_log_info, macro_metadata_option,
-    models::data::{FileData, PageData},
+    macro_log_info,
+    models::data::{
+        AlternateData, DataError, FileData, PageData, PageMetadata,
+        TxtData,
+    },
     modules::{
-        json::{security, sitemap, txt},
+        build::{
+            content_build_id, generate_build_json, BUILD_JSON_FILE,
+        },
+        excerpt::{
+            excerpt_text, extract_excerpt, DEFAULT_EXCERPT_SEPARATOR,
+        },
+        json::{
+            same_url_set, security, sitemap_with_options, txt,
+            SitemapOptions,
+        },
         navigation::NavigationGenerator,
-        robots::create_txt_data,
+        plaintext::word_count,
+        postprocessor::{
+            find_executable, first_image, inject_snippets,
+        },
+        preprocessor::{interpolate, interpolate_front_matter},
+        robots::{create_txt_data, MetaRobots},
+        sections::{
+            generate_section_indexes_with_options, SectionIndexOptions,
+        },
         security::create_security_data,
+        template::validate_layout,
+        urls::{
+            generate_llms_txt, generate_url_list, LLMS_TXT_FILE,
+            URL_LIST_FILE,
+        },
+    },
+    utilities::{
+        datetime::to_rfc2822,
+        file::{add, add_all, ContentOverride},
+        frontmatter::insert_entries,
+        metadata::{
+            meta_date, meta_str, meta_url, with_raw_scalars,
+            MetadataError,
+        },
+        permalink::{split_date_prefix, UrlOptions},
+        schema::{validate_metadata, MetadataSchema},
+        url::{to_absolute, UrlMode},
+        write::{
+            output_paths, page_html_path, page_url_path,
+            write_files_to_build_directory_with_options, WriteOptions,
+        },
     },
-    utilities::{file::add, write::write_files_to_build_directory},
 };
 
+pub use crate::utilities::frontmatter::split_frontmatter_and_body;
+
 /// Compiles source files in a specified directory into static site content.
 /// Generates HTML pages, RSS feeds, sitemaps, and other essential metadata files.
 ///
@@ -59,40 +112,857 @@ pub fn compile(
     site_path: &Path,
     template_path: &Path,
 ) -> Result<()> {
-    // Create necessary directories with error context.
-    macro_create_directories!(build_dir_path, site_path)
-        .context("Failed to create build and site directories")?;
+    compile_with_renderer(
+        build_dir_path,
+        content_path,
+        site_path,
+        template_path,
+        None,
+    )
+}
+
+/// A last-mile pass over a page's rendered HTML, given the page's source
+/// file (see [`CompileHooks::transform`]).
+pub type HtmlTransform = dyn Fn(&FileData, String) -> Result<String>;
+
+/// Options controlling how source files are compiled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// Treat a leading `YYYY-MM-DD-` in a file name as the publication
+    /// date (Jekyll convention): `2024-01-15-my-post.md` publishes at
+    /// `/my-post/`, and the date fills `pub_date`, `item_pub_date` and
+    /// `date` when the front matter omits them. Two files that publish
+    /// under the same name once their dates are removed fail the build.
+    pub date_in_filename: bool,
+    /// Values for `${NAME}` references in front matter (see
+    /// [`interpolate`]). The process environment is never consulted.
+    pub variables: HashMap<String, String>,
+    /// Fail on a `${NAME}` reference missing from `variables` instead of
+    /// expanding it to an empty string.
+    pub strict_variables: bool,
+    /// Expand `${NAME}` references in page bodies as well as front matter.
+    pub interpolate_body: bool,
+    /// The front matter keys and delimiter used to read page tags.
+    pub tags: TagOptions,
+    /// Write a `page.json` sidecar next to each page's `index.html`,
+    /// holding its front matter, permalink, tags, word count and
+    /// excerpt (see [`PageMetadata`]).
+    pub emit_json_metadata: bool,
+    /// When a page sets no `image_url`, fill a missing `og:image` and
+    /// `twitter:image` with the first `<img src>` of its rendered HTML,
+    /// resolved against `base_url` (or, failing that, `permalink`).
+    pub first_image_fallback: bool,
+    /// What to do with pages whose body is empty or whitespace-only,
+    /// such as a Markdown file holding nothing but front matter.
+    pub empty_body: EmptyBodyPolicy,
+    /// The per-page generators to run. Disabled generators leave their
+    /// [`FileData`] field empty, so their file is not written.
+    pub generators: GeneratorFlags,
+    /// The number of pages listed in each page's `related` template
+    /// variable, ranked by shared tags (see [`compute_related`]). `0`
+    /// leaves the variable empty.
+    pub related_pages: usize,
+    /// Write `urls.txt`, listing the URL of every page that is neither
+    /// `noindex` nor a draft, written with [`CompileOptions::url`] (see
+    /// [`generate_url_list`]).
+    pub emit_url_list: bool,
+    /// Write an `llms.txt` index of the same pages, titled with the home
+    /// page's `name` and `description` (see [`generate_llms_txt`]).
+    pub emit_llms_txt: bool,
+    /// The absolute site URL relative permalinks are resolved against in
+    /// `urls.txt` and `llms.txt`. Defaults to the home page's
+    /// `permalink`.
+    pub base_url: String,
+    /// Add the full page HTML to each RSS item as `content:encoded`, and
+    /// the page's `image_url` as `media:thumbnail`, for readers that
+    /// display whole articles.
+    pub rich_rss: bool,
+    /// The build id exposed to templates as `build_id`. When `None`, it
+    /// is derived from the source files (see [`content_build_id`]), so
+    /// identical inputs yield identical ids.
+    pub build_id: Option<String>,
+    /// Write a `build.json` holding the build id, the build time and the
+    /// crate version (see [`generate_build_json`]).
+    pub emit_build_json: bool,
+    /// Keep the partially built directory when compilation fails, and
+    /// name it in the returned error, so the failed build can be
+    /// inspected. By default a build directory created by the failed
+    /// call is removed; one that existed beforehand is always left alone.
+    pub keep_build_on_error: bool,
+    /// The site language tag (e.g. `en-GB`), used to render pages that
+    /// set no `lang` key. Defaults to `en`.
+    pub language: String,
+    /// Raw HTML added to every page before `</head>`, such as analytics
+    /// scripts or verification meta tags. Templates may place it
+    /// themselves with `{{extra_head}}`.
+    pub head_snippet: Option<String>,
+    /// Raw HTML added to every page before `</body>`, or wherever the
+    /// template places `{{extra_footer}}`.
+    pub footer_snippet: Option<String>,
+    /// Reject snippets holding scripts, event handlers or other
+    /// executable content (see [`find_executable`]).
+    pub safe_snippets: bool,
+    /// Fail on a page whose `news_publication_date` is missing or cannot
+    /// be parsed, instead of dating its news sitemap entry with the
+    /// current time.
+    pub strict_news_dates: bool,
+    /// How sitemap `<loc>` URLs and RSS item links are written. `None`
+    /// keeps them absolute under each page's front matter URLs;
+    /// [`UrlMode::Relative`] writes root-relative links for builds whose
+    /// site URL is not known yet. The RSS channel link stays absolute, as
+    /// RSS requires.
+    pub url_mode: Option<UrlMode>,
+    /// Check each page's layout against its variables before rendering,
+    /// and fail with the full list of undefined ones (see
+    /// [`validate_layout`]) rather than the engine's first error.
+    pub strict_templates: bool,
+    /// Generate an index page for each section without one, listing its
+    /// pages (see [`generate_section_indexes_with_options`]). An existing
+    /// file at the index path is never replaced.
+    pub section_indexes: Option<SectionIndexOptions>,
+    /// Front matter schemas keyed by content type: a page's `type` key,
+    /// else its `layout`. Pages of other types are not checked.
+    pub schemas: HashMap<String, MetadataSchema>,
+    /// Fail on a page that breaks its schema instead of logging each
+    /// issue as a warning.
+    pub strict_metadata: bool,
+    /// Search console ownership tokens. Their verification files are
+    /// written at the site root and their meta tags added to every
+    /// page's `<head>`.
+    pub verification: VerificationConfig,
+    /// The time scheduled pages are published against. A page whose
+    /// `publish_at` is later is left out of the build, and so of the
+    /// navigation, feeds and sitemaps, until a build after that time.
+    /// Defaults to the system clock.
+    pub now: Option<OffsetDateTime>,
+    /// Build scheduled pages anyway, to preview them before their
+    /// `publish_at`.
+    pub preview: bool,
+    /// How pages map to output files and how their URLs are written.
+    /// The writer, the navigation and the sitemap all follow it, so links
+    /// always point at written files (see [`UrlOptions`]).
+    pub url: UrlOptions,
+    /// The line endings and trailing newline of the written text files
+    /// (see [`WriteOptions`]).
+    pub write: WriteOptions,
+}
+
+/// Switches for the files generated alongside each page.
+///
+/// Every generator is enabled by default. Turning one off skips its work
+/// for every page and leaves its output file unwritten, which avoids
+/// spurious warnings for sites that do not need it.
+///
+/// Whether a generator is enabled or not, a generated file is written
+/// only when it has content: an enabled generator with nothing to emit
+/// for a page, such as `humans.txt` for a page without a `humans` key,
+/// leaves no empty file behind. The page's `index.html` is always
+/// written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GeneratorFlags {
+    /// `CNAME`, from the `cname` key.
+    pub cname: bool,
+    /// `humans.txt`, from the `humans` key.
+    pub humans: bool,
+    /// `manifest.json`.
+    pub manifest: bool,
+    /// `news-sitemap.xml`.
+    pub news_sitemap: bool,
+    /// `rss.xml`.
+    pub rss: bool,
+    /// `robots.txt`.
+    pub robots: bool,
+    /// `security.txt`.
+    pub security: bool,
+    /// `sitemap.xml`.
+    pub sitemap: bool,
+}
+
+impl GeneratorFlags {
+    /// Returns flags with every generator enabled.
+    pub fn all() -> Self {
+        Self::with_all(true)
+    }
+
+    /// Returns flags with every generator disabled, so only the HTML
+    /// pages are written.
+    pub fn none() -> Self {
+        Self::with_all(false)
+    }
+
+    /// Returns flags with every generator set to `enabled`.
+    fn with_all(enabled: bool) -> Self {
+        Self {
+            cname: enabled,
+            humans: enabled,
+            manifest: enabled,
+            news_sitemap: enabled,
+            rss: enabled,
+            robots: enabled,
+            security: enabled,
+            sitemap: enabled,
+        }
+    }
+}
+
+impl Default for GeneratorFlags {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Handling of pages whose Markdown body is empty or whitespace-only.
+///
+/// Such pages are always rendered; the policy decides whether they also
+/// publish an RSS item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyBodyPolicy {
+    /// Publish the feed item as usual and log a warning (the default).
+    Warn,
+    /// Render the page but publish no feed item for it.
+    SkipFeedItem,
+}
+
+impl Default for EmptyBodyPolicy {
+    fn default() -> Self {
+        EmptyBodyPolicy::Warn
+    }
+}
 
+/// Settings shared by every page of a build.
+#[derive(Debug, Default)]
+struct PageSettings {
+    /// Site-wide options for the generated sitemap.
+    sitemap: SitemapOptions,
+    /// How page tags are read from the front matter.
+    tags: TagOptions,
+    /// Whether each page gets a `page.json` sidecar.
+    emit_json_metadata: bool,
+    /// Whether pages without `image_url` use their first image.
+    first_image_fallback: bool,
+    /// How pages with an empty body are handled.
+    empty_body: EmptyBodyPolicy,
+    /// The per-page generators to run.
+    generators: GeneratorFlags,
+    /// The related pages of each page, keyed by file name.
+    related: HashMap<String, Vec<PageData>>,
+    /// Whether RSS items carry the full page HTML and thumbnail.
+    rich_rss: bool,
+    /// The build id exposed to templates.
+    build_id: String,
+    /// The site language tag.
+    language: String,
+    /// The HTML added to each page's `<head>`.
+    head_snippet: String,
+    /// The HTML added to the end of each page's `<body>`.
+    footer_snippet: String,
+    /// Whether bad news publication dates are errors.
+    strict_news_dates: bool,
+    /// How RSS item links are written, if not as given.
+    url_mode: Option<UrlMode>,
+    /// Whether layouts are checked for undefined variables first.
+    strict_templates: bool,
+    /// The front matter schemas, keyed by content type.
+    schemas: HashMap<String, MetadataSchema>,
+    /// Whether schema issues are errors.
+    strict_metadata: bool,
+    /// How pages map to output files.
+    urls: UrlOptions,
+}
+
+/// Compiles source files like [`compile`], rendering Markdown bodies with
+/// the given [`MarkdownRenderer`].
+///
+/// # Arguments
+///
+/// * `build_dir_path` - The path to the temporary build directory.
+/// * `content_path` - The path to the content directory with source files.
+/// * `site_path` - The path to the output site directory.
+/// * `template_path` - The path to the template directory for HTML templates.
+/// * `renderer` - The Markdown renderer to use, or `None` for the default
+///   [`HtmlGeneratorRenderer`].
+///
+/// # Returns
+///
+/// Returns `Ok(())` if compilation succeeds. If an error occurs, a detailed
+/// `anyhow::Error` is returned.
+pub fn compile_with_renderer(
+    build_dir_path: &Path,
+    content_path: &Path,
+    site_path: &Path,
+    template_path: &Path,
+    renderer: Option<Box<dyn MarkdownRenderer>>,
+) -> Result<()> {
+    compile_with_options(
+        build_dir_path,
+        content_path,
+        site_path,
+        template_path,
+        renderer,
+        CompileOptions::default(),
+    )
+}
+
+/// Compiles source files like [`compile_with_renderer`], applying the
+/// given [`CompileOptions`].
+///
+/// # Arguments
+///
+/// * `build_dir_path` - The path to the temporary build directory.
+/// * `content_path` - The path to the content directory with source files.
+/// * `site_path` - The path to the output site directory.
+/// * `template_path` - The path to the template directory for HTML templates.
+/// * `renderer` - The Markdown renderer to use, or `None` for the default
+///   [`HtmlGeneratorRenderer`].
+/// * `options` - The [`CompileOptions`] to apply.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if compilation succeeds. If an error occurs, a detailed
+/// `anyhow::Error` is returned.
+pub fn compile_with_options(
+    build_dir_path: &Path,
+    content_path: &Path,
+    site_path: &Path,
+    template_path: &Path,
+    renderer: Option<Box<dyn MarkdownRenderer>>,
+    options: CompileOptions,
+) -> Result<()> {
     // Load source files for compilation.
     let source_files = add(content_path).context(
         "Failed to load source files from content directory",
     )?;
 
+    compile_files(
+        build_dir_path,
+        source_files,
+        site_path,
+        template_path,
+        options,
+        CompileHooks {
+            renderer,
+            ..CompileHooks::default()
+        },
+    )
+}
+
+/// Caller-supplied parts of a build, for [`compile_with_hooks`].
+///
+/// Every hook is optional, and any of them can be combined; the default
+/// builds like [`compile_with_options`].
+#[derive(Default)]
+pub struct CompileHooks<'a> {
+    /// A caller-owned template engine to render pages with. Its render
+    /// cache outlives the build, so repeated builds (in a watch loop, for
+    /// instance) reuse the pages it has already rendered; call
+    /// [`Engine::clear_cache`] after templates change on disk. `None`
+    /// creates an engine for the build from the template directory.
+    pub engine: Option<&'a mut Engine>,
+    /// The Markdown renderer, or `None` for the default
+    /// [`HtmlGeneratorRenderer`].
+    pub renderer: Option<Box<dyn MarkdownRenderer>>,
+    /// A pass over each page's rendered HTML, and that of its
+    /// alternates, just before it is written. It suits last-mile edits
+    /// such as adding a cookie banner or moving asset URLs onto a CDN; an
+    /// error fails the build.
+    pub transform: Option<&'a HtmlTransform>,
+}
+
+impl std::fmt::Debug for CompileHooks<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompileHooks")
+            .field("engine", &self.engine.is_some())
+            .field("renderer", &self.renderer.is_some())
+            .field("transform", &self.transform.is_some())
+            .finish()
+    }
+}
+
+/// Compiles several content directories merged into a single tree, like
+/// [`compile_content_paths`], with the given [`CompileHooks`].
+///
+/// # Arguments
+///
+/// * `build_dir_path` - The path to the temporary build directory.
+/// * `content_paths` - The content directories, from lowest to highest
+///   priority.
+/// * `site_path` - The path to the output site directory.
+/// * `template_path` - The path to the template directory for auxiliary
+///   files; a [`CompileHooks::engine`] should load its templates from the
+///   same directory.
+/// * `options` - The [`CompileOptions`] to apply.
+/// * `hooks` - The [`CompileHooks`] to build with.
+///
+/// # Returns
+///
+/// Returns the files overridden by a later directory if compilation
+/// succeeds. If an error occurs, a detailed `anyhow::Error` is returned.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use std::time::Duration;
+/// use staticdatagen::compiler::service::{compile_with_hooks, CompileHooks, CompileOptions};
+/// use staticdatagen::Engine;
+///
+/// let mut engine = Engine::new("templates", Duration::from_secs(3600));
+/// for _ in 0..2 {
+///     let _ = compile_with_hooks(
+///         Path::new("build"),
+///         &[Path::new("shared"), Path::new("content")],
+///         Path::new("public"),
+///         Path::new("templates"),
+///         CompileOptions::default(),
+///         CompileHooks {
+///             engine: Some(&mut engine),
+///             transform: Some(&|_file, html| {
+///                 Ok(html.replace("src=\"/assets/", "src=\"https://cdn.example.com/assets/"))
+///             }),
+///             ..CompileHooks::default()
+///         },
+///     )?;
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn compile_with_hooks(
+    build_dir_path: &Path,
+    content_paths: &[&Path],
+    site_path: &Path,
+    template_path: &Path,
+    options: CompileOptions,
+    hooks: CompileHooks<'_>,
+) -> Result<Vec<ContentOverride>> {
+    let merged = add_all(content_paths).context(
+        "Failed to load source files from content directories",
+    )?;
+
+    for o in &merged.overrides {
+        log::info!(
+            "{} from {} overrides {}",
+            o.name,
+            o.by.display(),
+            o.overridden.display()
+        );
+    }
+
+    compile_files(
+        build_dir_path,
+        merged.files,
+        site_path,
+        template_path,
+        options,
+        hooks,
+    )?;
+
+    Ok(merged.overrides)
+}
+
+/// Compiles several content directories merged into a single tree, as
+/// read by [`add_all`]. Later directories override files with the same
+/// name in earlier ones; navigation, tags and sitemaps see the merged set.
+///
+/// # Arguments
+///
+/// * `build_dir_path` - The path to the temporary build directory.
+/// * `content_paths` - The content directories, from lowest to highest
+///   priority.
+/// * `site_path` - The path to the output site directory.
+/// * `template_path` - The path to the template directory for HTML templates.
+/// * `renderer` - The Markdown renderer to use, or `None` for the default
+///   [`HtmlGeneratorRenderer`].
+/// * `options` - The [`CompileOptions`] to apply.
+///
+/// # Returns
+///
+/// Returns the files overridden by a later directory if compilation
+/// succeeds. If an error occurs, a detailed `anyhow::Error` is returned.
+pub fn compile_content_paths(
+    build_dir_path: &Path,
+    content_paths: &[&Path],
+    site_path: &Path,
+    template_path: &Path,
+    renderer: Option<Box<dyn MarkdownRenderer>>,
+    options: CompileOptions,
+) -> Result<Vec<ContentOverride>> {
+    compile_with_hooks(
+        build_dir_path,
+        content_paths,
+        site_path,
+        template_path,
+        options,
+        CompileHooks {
+            renderer,
+            ..CompileHooks::default()
+        },
+    )
+}
+
+/// Lists the files a compile of `content_path` with `options` would
+/// write, without rendering or writing anything.
+///
+/// Source files are loaded and prepared as for a build (variables, dated
+/// file names and scheduled pages), and each page's output paths are
+/// resolved by the same rules as the writer. Whether a page's metadata
+/// files are written is decided from its front matter and
+/// [`CompileOptions::generators`]; the site-wide listings, verification
+/// files and section indexes are included as enabled.
+/// Pages' `alternate_templates` are listed without checking that the
+/// templates exist, and the auxiliary scripts copied from the template
+/// directory are not listed.
+///
+/// # Arguments
+///
+/// * `content_path` - The path to the content directory with source files.
+/// * `options` - The [`CompileOptions`] the build would use.
+///
+/// # Returns
+///
+/// The distinct output paths, relative to the site directory and sorted,
+/// or an error for input the build would also reject.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use staticdatagen::compiler::service::{planned_outputs, CompileOptions};
+///
+/// for path in planned_outputs(Path::new("content"), &CompileOptions::default())? {
+///     println!("{}", path.display());
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn planned_outputs(
+    content_path: &Path,
+    options: &CompileOptions,
+) -> Result<Vec<PathBuf>> {
+    let source_files = add(content_path).context(
+        "Failed to load source files from content directory",
+    )?;
+    let source_files = prepare_files(source_files, options)?;
+
+    let mut paths = Vec::new();
+    for file in &source_files {
+        let page = planned_page(file, options).with_context(|| {
+            format!("Failed to plan the outputs of '{}'", file.name)
+        })?;
+        paths.extend(output_paths(&page)?);
+    }
+
+    for (enabled, file_name) in [
+        (options.emit_url_list, URL_LIST_FILE),
+        (options.emit_llms_txt, LLMS_TXT_FILE),
+        (options.emit_build_json, BUILD_JSON_FILE),
+    ] {
+        if enabled {
+            paths.push(PathBuf::from(file_name));
+        }
+    }
+    for (file_name, _) in options
+        .verification
+        .files()
+        .context("Invalid site verification")?
+    {
+        paths.push(PathBuf::from(file_name));
+    }
+
+    // A page already written in place of a section index wins.
+    if let Some(section_options) = &options.section_indexes {
+        for (file_name, _) in generate_section_indexes_with_options(
+            &source_files,
+            section_options,
+            &options.url,
+        ) {
+            let path = PathBuf::from(file_name);
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Returns a stand-in for the compiled `file` whose fields are non-empty
+/// exactly when the build would write the matching output file.
+fn planned_page(
+    file: &FileData,
+    options: &CompileOptions,
+) -> Result<FileData> {
+    let (metadata, _, _) = extract_and_prepare_metadata(&file.content)
+        .context("Failed to extract and prepare metadata")?;
+    let generators = &options.generators;
+    let planned = |written: bool| {
+        if written {
+            file.name.clone()
+        } else {
+            String::new()
+        }
+    };
+
+    let manifest = generators.manifest
+        && ManifestConfig::from_metadata(&metadata)
+            .and_then(|config| {
+                ManifestGenerator::new(config).generate()
+            })
+            .is_ok();
+    let news_sitemap = generators.news_sitemap
+        && !NewsSiteMapGenerator::new(
+            NewsSiteMapConfig::new(metadata.clone())
+                .strict(options.strict_news_dates),
+        )
+        .try_generate_xml()
+        .context("Invalid news_publication_date")?
+        .is_empty();
+    let cname = generators.cname
+        && metadata.get("cname").map_or(false, |domain| {
+            CnameConfig::new(domain, None, None).is_ok()
+        });
+    let security = generators.security
+        && !security(&create_security_data(&metadata)).is_empty();
+
+    Ok(FileData {
+        name: file.name.clone(),
+        content: planned(true),
+        cname: planned(cname),
+        human: planned(
+            generators.humans && metadata.contains_key("humans"),
+        ),
+        manifest: planned(manifest),
+        rss: planned(generators.rss),
+        security: planned(security),
+        sitemap: planned(generators.sitemap),
+        sitemap_news: planned(news_sitemap),
+        txt: planned(generators.robots),
+        json: planned(options.emit_json_metadata),
+        alternates: alternate_templates(&metadata)?
+            .into_iter()
+            .map(|template| (template, file.name.clone()))
+            .collect(),
+        output_path: page_html_path(
+            &file.name,
+            meta_str(&metadata, "permalink"),
+            &options.url,
+        ),
+        ..Default::default()
+    })
+}
+
+/// Compiles already loaded source files into the site directory.
+///
+/// On failure, a build directory this call created is removed unless
+/// [`CompileOptions::keep_build_on_error`] is set, in which case its path
+/// is added to the error. A directory that existed beforehand is never
+/// removed.
+fn compile_files(
+    build_dir_path: &Path,
+    source_files: Vec<FileData>,
+    site_path: &Path,
+    template_path: &Path,
+    options: CompileOptions,
+    hooks: CompileHooks<'_>,
+) -> Result<()> {
+    let keep_build_on_error = options.keep_build_on_error;
+    let existed = build_dir_path.exists();
+    let result = build_files(
+        build_dir_path,
+        source_files,
+        site_path,
+        template_path,
+        options,
+        hooks,
+    );
+
+    match result {
+        Err(e) if keep_build_on_error && build_dir_path.exists() => {
+            Err(e.context(format!(
+                "Build directory kept at {}",
+                build_dir_path.display()
+            )))
+        }
+        Err(e) => {
+            if !existed && build_dir_path.exists() {
+                if let Err(cleanup) = fs::remove_dir_all(build_dir_path)
+                {
+                    log::warn!(
+                        "Failed to remove build directory {}: {}",
+                        build_dir_path.display(),
+                        cleanup
+                    );
+                }
+            }
+            Err(e)
+        }
+        Ok(()) => Ok(()),
+    }
+}
+
+/// Builds the site from `source_files` in `build_dir_path`, then moves it
+/// to `site_path`.
+fn build_files(
+    build_dir_path: &Path,
+    source_files: Vec<FileData>,
+    site_path: &Path,
+    template_path: &Path,
+    options: CompileOptions,
+    hooks: CompileHooks<'_>,
+) -> Result<()> {
+    let CompileHooks {
+        engine,
+        renderer,
+        transform,
+    } = hooks;
+    let renderer =
+        renderer.unwrap_or_else(|| Box::new(HtmlGeneratorRenderer));
+    let source_files = prepare_files(source_files, &options)?;
+
+    // Create necessary directories with error context.
+    macro_create_directories!(build_dir_path, site_path)
+        .context("Failed to create build and site directories")?;
+
     // Generate the navigation structure.
     let navigation =
-        NavigationGenerator::generate_navigation(&source_files);
+        NavigationGenerator::generate_navigation_with_options(
+            &source_files,
+            &options.url,
+        );
+
+    let head_snippet = options.head_snippet.clone().unwrap_or_default();
+    let footer_snippet =
+        options.footer_snippet.clone().unwrap_or_default();
+    if options.safe_snippets {
+        for (name, snippet) in
+            [("head", &head_snippet), ("footer", &footer_snippet)]
+        {
+            if let Some(found) = find_executable(snippet) {
+                anyhow::bail!(
+                    "The {} snippet holds executable content: {}",
+                    name,
+                    found
+                );
+            }
+        }
+    }
+
+    let mut listings = site_listings(&source_files, &options);
+
+    // Verification meta tags are generated, so they skip the safety check.
+    let verification_files = options
+        .verification
+        .files()
+        .context("Invalid site verification")?;
+    let head_snippet = format!(
+        "{}{}",
+        options
+            .verification
+            .meta_tags()
+            .context("Invalid site verification")?,
+        head_snippet
+    );
+
+    let build_id = options
+        .build_id
+        .clone()
+        .unwrap_or_else(|| content_build_id(&source_files));
+    if options.emit_build_json {
+        listings.push((
+            BUILD_JSON_FILE,
+            generate_build_json(&build_id, &OffsetDateTime::now_utc())
+                .context("Failed to generate build.json")?,
+        ));
+    }
+
+    let section_indexes = match &options.section_indexes {
+        Some(section_options) => generate_section_indexes_with_options(
+            &source_files,
+            section_options,
+            &options.url,
+        ),
+        None => Vec::new(),
+    };
+
+    let related = if options.related_pages > 0 {
+        compute_related_with_options(
+            &source_files,
+            options.related_pages,
+            &options.tags,
+        )
+    } else {
+        HashMap::new()
+    };
+
+    // Collect the mobile and AMP variants and the `noindex` pages.
+    let settings = PageSettings {
+        sitemap: SitemapOptions {
+            urls: options.url.clone(),
+            url_mode: options.url_mode.clone(),
+            ..collect_sitemap_options(&source_files)?
+        },
+        tags: options.tags,
+        emit_json_metadata: options.emit_json_metadata,
+        first_image_fallback: options.first_image_fallback,
+        rich_rss: options.rich_rss,
+        build_id,
+        language: options.language,
+        head_snippet,
+        footer_snippet,
+        strict_news_dates: options.strict_news_dates,
+        url_mode: options.url_mode,
+        strict_templates: options.strict_templates,
+        schemas: options.schemas,
+        strict_metadata: options.strict_metadata,
+        empty_body: options.empty_body,
+        generators: options.generators,
+        related,
+        urls: options.url,
+    };
 
     let mut global_tags_data: HashMap<String, Vec<PageData>> =
         HashMap::new();
 
-    // Initialize the templating engine with caching.
-    let mut engine = Engine::new(
-        template_path.to_str().unwrap(),
-        Duration::from_secs(60),
-    );
+    // Initialize the templating engine with caching, unless the caller
+    // keeps one across builds.
+    let mut build_engine;
+    let engine = match engine {
+        Some(engine) => engine,
+        None => {
+            build_engine = Engine::new(
+                template_path.to_str().unwrap(),
+                Duration::from_secs(60),
+            );
+            &mut build_engine
+        }
+    };
 
     // Compile source files into `compiled_files`, collecting results as `FileData`.
     let compiled_files: Result<Vec<FileData>> = source_files
         .into_iter()
         .map(|file| {
-            process_file(
+            let mut page = process_file(
                 &file,
-                &mut engine,
-                template_path,
+                engine,
+                renderer.as_ref(),
                 &navigation,
                 &mut global_tags_data,
                 site_path,
-            )
+                &settings,
+            )?;
+            if let Some(transform) = transform {
+                let pages = std::iter::once(&mut page.content).chain(
+                    page.alternates.iter_mut().map(|(_, html)| html),
+                );
+                for html in pages {
+                    *html = transform(&file, std::mem::take(html))
+                        .with_context(|| {
+                            format!(
+                                "Failed to transform '{}'",
+                                file.name
+                            )
+                        })?;
+                }
+            }
+            Ok(page)
         })
         .collect();
 
@@ -110,17 +980,81 @@ pub fn compile(
     );
 
     // Write each compiled file to the output directory.
-    for file in &compiled_files? {
-        write_files_to_build_directory(
+    let compiled_files = compiled_files?;
+    for file in &compiled_files {
+        write_files_to_build_directory_with_options(
             build_dir_path,
             file,
             template_path,
+            &options.write,
         )?;
     }
 
-    // Generate and write global tags HTML.
+    // Like the page text files, the text listings follow the write
+    // options; verification files are written as issued.
+    for (file_name, content) in listings
+        .iter()
+        .map(|(file_name, content)| {
+            let content = if file_name.ends_with(".txt") {
+                options.write.apply(content)
+            } else {
+                content.clone()
+            };
+            (file_name.to_string(), content)
+        })
+        .chain(verification_files)
+    {
+        let path = build_dir_path.join(file_name);
+        fs::write(&path, content).with_context(|| {
+            format!("Failed to write {}", path.display())
+        })?;
+    }
+
+    for (file_name, content) in &section_indexes {
+        let path = build_dir_path.join(file_name);
+        if path.exists() {
+            log::warn!(
+                "Skipping the generated section index {}: the file exists",
+                path.display()
+            );
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create {}", parent.display())
+            })?;
+        }
+        fs::write(&path, content).with_context(|| {
+            format!("Failed to write {}", path.display())
+        })?;
+    }
+
+    // Generate and write global tags HTML into the tags page, wherever
+    // the URL options put it.
     let tags_html_content = generate_tags_html(&global_tags_data);
-    write_tags_html_to_file(&tags_html_content, build_dir_path)?;
+    let tags_page = compiled_files
+        .iter()
+        .find(|file| page_url_path(&file.name) == "/tags/")
+        .map(|file| file.output_path.as_str())
+        .filter(|path| !path.is_empty())
+        .unwrap_or("tags/index.html");
+    write_tags_html_to_page(
+        &tags_html_content,
+        &build_dir_path.join(tags_page),
+    )?;
+
+    // A sitemap listing the same URLs as the deployed one is replaced by
+    // a hard link to it, so it keeps its modification time. The site
+    // directory itself is left untouched until the final rename.
+    for file in compiled_files
+        .iter()
+        .filter(|file| !file.sitemap.is_empty())
+    {
+        keep_unchanged_sitemap(
+            &site_path.join(sitemap_path(&file.name)),
+            &build_dir_path.join(sitemap_path(&file.name)),
+        );
+    }
 
     // Clean up and finalize site structure.
     macro_cleanup_directories!(site_path)
@@ -131,56 +1065,160 @@ pub fn compile(
     Ok(())
 }
 
-/// Splits a Markdown content string into frontmatter and body parts.
-///
-/// The function uses the `---` separator to divide the content into two parts:
-/// the frontmatter (metadata) and the body (main content).
-///
-/// # Parameters
-///
-/// * `content` - A reference to a string containing the Markdown content.
-///
-/// # Returns
-///
-/// A tuple containing two strings:
-/// - The first string represents the frontmatter part of the content.
-/// - The second string represents the body part of the content.
-///
-/// If the `---` separator is not found in the content, both strings will be empty.
-pub fn split_frontmatter_and_body(content: &str) -> (String, String) {
-    let mut lines = content.lines();
-    let mut frontmatter = String::new();
-    let mut body = String::new();
-    let mut in_frontmatter = false;
-
-    for line in &mut lines {
-        if line.trim() == "---" {
-            if in_frontmatter {
-                // Ending the frontmatter
-                break;
-            } else {
-                // Starting the frontmatter
-                in_frontmatter = true;
-                continue;
+/// Applies the source-level `options` to loaded files: expands variables,
+/// moves dates out of file names and holds back scheduled pages.
+fn prepare_files(
+    source_files: Vec<FileData>,
+    options: &CompileOptions,
+) -> Result<Vec<FileData>> {
+    // Expand caller-supplied variables before metadata is read.
+    let source_files = if options.variables.is_empty() {
+        source_files
+    } else {
+        source_files
+            .into_iter()
+            .map(|file| interpolate_file(file, options))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    // Move dates out of file names before anything derives URLs from them.
+    let source_files: Vec<FileData> = if options.date_in_filename {
+        let mut sources: HashMap<String, String> = HashMap::new();
+        let mut files = Vec::with_capacity(source_files.len());
+        for file in source_files {
+            let source = file.name.clone();
+            let file = apply_filename_date(file);
+            if let Some(other) =
+                sources.insert(file.name.clone(), source.clone())
+            {
+                anyhow::bail!(
+                    "'{}' and '{}' both publish as '{}' once their dates are removed",
+                    other,
+                    source,
+                    file.name
+                );
             }
+            files.push(file);
         }
+        files
+    } else {
+        source_files
+    };
 
-        if in_frontmatter {
-            frontmatter.push_str(line);
-            frontmatter.push('\n');
-        } else {
-            body.push_str(line);
-            body.push('\n');
-        }
+    // Hold back scheduled pages before any listing can link to them.
+    if options.preview {
+        Ok(source_files)
+    } else {
+        hold_scheduled(
+            source_files,
+            options.now.unwrap_or_else(OffsetDateTime::now_utc),
+        )
+    }
+}
+
+/// Expands `${NAME}` references in a file's front matter, and in its body
+/// when [`CompileOptions::interpolate_body`] is set.
+fn interpolate_file(
+    file: FileData,
+    options: &CompileOptions,
+) -> Result<FileData> {
+    let content = if options.interpolate_body {
+        interpolate(
+            &file.content,
+            &options.variables,
+            options.strict_variables,
+        )
+    } else {
+        interpolate_front_matter(
+            &file.content,
+            &options.variables,
+            options.strict_variables,
+        )
     }
+    .with_context(|| {
+        format!("Failed to interpolate '{}'", file.name)
+    })?;
+
+    Ok(FileData { content, ..file })
+}
 
-    // Append the rest of the lines to the body
-    for line in lines {
-        body.push_str(line);
-        body.push('\n');
+/// Removes the files whose `publish_at` is later than `now`.
+///
+/// # Errors
+///
+/// Returns an error if a `publish_at` cannot be parsed, rather than
+/// publishing the page early.
+fn hold_scheduled(
+    files: Vec<FileData>,
+    now: OffsetDateTime,
+) -> Result<Vec<FileData>> {
+    let mut published = Vec::with_capacity(files.len());
+    for file in files {
+        let publish_at =
+            match extract_and_prepare_metadata(&file.content) {
+                Ok((metadata, _, _)) => meta_date(
+                    &metadata,
+                    "publish_at",
+                )
+                .with_context(|| {
+                    format!("Invalid publish date in '{}'", file.name)
+                })?,
+                Err(_) => None,
+            };
+        match publish_at {
+            Some(publish_at) if publish_at > now => log::info!(
+                "Skipping '{}', scheduled for {}",
+                file.name,
+                publish_at
+            ),
+            _ => published.push(file),
+        }
     }
+    Ok(published)
+}
+
+/// Strips a leading `YYYY-MM-DD-` date from a file's name and records the
+/// date in its front matter, unless the front matter already sets
+/// `pub_date`, `item_pub_date` or `date`.
+fn apply_filename_date(file: FileData) -> FileData {
+    let (date, name) = match split_date_prefix(&file.name) {
+        Some((date, name)) => (date, name.to_string()),
+        None => return file,
+    };
+
+    // Keys are read up to a YAML or JSON `:` or a TOML `=`.
+    let (frontmatter, _) = split_frontmatter_and_body(&file.content);
+    let declared: Vec<&str> = frontmatter
+        .lines()
+        .filter_map(|line| line.split([':', '=']).next())
+        .map(|key| key.trim().trim_matches('"'))
+        .collect();
 
-    (frontmatter.trim().to_string(), body.trim().to_string())
+    // RSS dates use the RFC 2822 layout, e.g. `Mon, 15 Jan 2024 00:00:00 GMT`.
+    let rfc2822 = format!(
+        "{}, {:02} {} {} 00:00:00 GMT",
+        &date.weekday().to_string()[..3],
+        date.day(),
+        &date.month().to_string()[..3],
+        date.year()
+    );
+    let date = date.to_string();
+    let missing: Vec<(&str, &str)> = [
+        ("pub_date", rfc2822.as_str()),
+        ("item_pub_date", rfc2822.as_str()),
+        ("date", date.as_str()),
+    ]
+    .into_iter()
+    .filter(|(key, _)| !declared.contains(key))
+    .collect();
+
+    let content = insert_entries(&file.content, &missing);
+
+    FileData {
+        name,
+        content,
+        ..file
+    }
 }
 
 /// Processes a single file, generating necessary content and metadata.
@@ -189,10 +1227,11 @@ pub fn split_frontmatter_and_body(content: &str) -> (String, String) {
 ///
 /// * `file` - A reference to `FileData` representing the source file.
 /// * `engine` - A mutable reference to the templating `Engine`.
-/// * `_template_path` - The path to the template directory (optional).
+/// * `renderer` - The Markdown renderer used for the page body.
 /// * `navigation` - HTML navigation content.
 /// * `global_tags_data` - Mutable reference to global tags data for aggregation.
 /// * `site_path` - The path to the output site directory.
+/// * `settings` - Settings shared by every page of the build.
 ///
 /// # Returns
 ///
@@ -200,10 +1239,11 @@ pub fn split_frontmatter_and_body(content: &str) -> (String, String) {
 fn process_file(
     file: &FileData,
     engine: &mut Engine,
-    _template_path: &Path,
+    renderer: &dyn MarkdownRenderer,
     navigation: &str,
     global_tags_data: &mut HashMap<String, Vec<PageData>>,
     site_path: &Path,
+    settings: &PageSettings,
 ) -> Result<FileData> {
     // Preprocess to separate frontmatter and body
     let (_frontmatter, body) =
@@ -214,35 +1254,93 @@ fn process_file(
     let (metadata, keywords, all_meta_tags) =
         extract_and_prepare_metadata(&file.content)
             .context("Failed to extract and prepare metadata")?;
+    check_metadata_schema(file, &metadata, settings)?;
 
     let _security_options = create_security_data(&metadata);
-    let config = HtmlConfig {
-        enable_syntax_highlighting: true,
-        minify_output: false,
-        add_aria_attributes: true,
-        generate_structured_data: true,
-        generate_toc: false,
-        language: "en".to_string(),
-        max_input_size: usize::MAX,
-        syntax_theme: None,
-    };
-
-    let html_content = generate_html(&body, &config)
+    let language = page_language(&metadata, &settings.language)?;
+    let html_content = renderer
+        .render(
+            &body,
+            &RenderConfig {
+                language: language.clone(),
+                ..RenderConfig::default()
+            },
+        )
         .context("Failed to generate HTML content")?;
 
-    // println!("HTML Content: {}", html_content);
+    // Split the rendered HTML at the excerpt separator, if any.
+    let separator = metadata
+        .get("excerpt_separator")
+        .map_or(DEFAULT_EXCERPT_SEPARATOR, String::as_str);
+    let excerpt = extract_excerpt(&html_content, separator);
+
+    let all_meta_tags = if settings.first_image_fallback {
+        with_first_image(all_meta_tags, &metadata, &html_content)
+    } else {
+        all_meta_tags
+    };
 
     let mut page_options = PageOptions::new();
     for (key, value) in metadata.iter() {
         page_options.set(key.to_string(), value.to_string());
     }
 
+    let alternates = alternate_data(&file.content, &metadata)
+        .context("Invalid alternate versions in metadata")?;
+    if !alternates.amp_url.is_empty() {
+        page_options.set(
+            "amphtml".to_string(),
+            format!(
+                "<link rel=\"amphtml\" href=\"{}\">",
+                alternates.amp_url
+            ),
+        );
+    }
+
+    let robots = meta_robots(&file.content, &metadata);
+    if !robots.is_empty() {
+        page_options
+            .set("meta_robots".to_string(), robots.to_meta_tag());
+    }
+
     page_options.set("apple".to_string(), all_meta_tags.apple.clone());
-    page_options.set("content".to_string(), html_content);
+    page_options.set("build_id".to_string(), settings.build_id.clone());
+    // A page in another language than the site is marked as such, so
+    // its text is read and laid out in the right language and direction.
+    let content = if meta_str(&metadata, "lang").is_some() {
+        format!(
+            r#"<div lang="{}" dir="{}">{}</div>"#,
+            language,
+            direction(&language),
+            excerpt.body
+        )
+    } else {
+        excerpt.body.clone()
+    };
+    page_options.set("content".to_string(), content);
+    page_options
+        .set("dir".to_string(), direction(&language).to_string());
+    page_options.set("lang".to_string(), language);
+    page_options.set("excerpt".to_string(), excerpt.excerpt.clone());
+    page_options.set(
+        "extra_footer".to_string(),
+        settings.footer_snippet.clone(),
+    );
+    page_options
+        .set("extra_head".to_string(), settings.head_snippet.clone());
     page_options.set("microsoft".to_string(), all_meta_tags.ms.clone());
     page_options.set("navigation".to_string(), navigation.to_owned());
     page_options.set("opengraph".to_string(), all_meta_tags.og);
     page_options.set("primary".to_string(), all_meta_tags.primary);
+    page_options.set(
+        "related".to_string(),
+        settings
+            .related
+            .get(&file.name)
+            .map_or_else(String::new, |pages| {
+                generate_related_html(pages)
+            }),
+    );
     page_options.set("twitter".to_string(), all_meta_tags.twitter);
 
     let mut context = TemplateContext::new();
@@ -250,194 +1348,2067 @@ fn process_file(
         context.set(key.to_string(), value.to_string());
     }
 
-    let content = engine.render_page(
-        &context,
-        metadata.get("layout").cloned().unwrap_or_default().as_str(),
-    )?;
+    let layout = meta_str(&metadata, "layout").unwrap_or_default();
+    if settings.strict_templates {
+        let missing = validate_layout(engine, layout, &context)
+            .with_context(|| {
+                format!("Failed to read template '{}'", layout)
+            })?;
+        if !missing.is_empty() {
+            let names: Vec<String> =
+                missing.iter().map(ToString::to_string).collect();
+            anyhow::bail!(
+                "Template '{}' uses undefined variables: {}",
+                layout,
+                names.join(", ")
+            );
+        }
+    }
+
+    // Render the page again through each of its alternate templates, and
+    // link the alternates from the page's head.
+    let page_url = page_url_path(&file.name);
+    let mut alternate_links = String::new();
+    let mut alternate_pages = Vec::new();
+    for template in alternate_templates(&metadata)? {
+        let path = Path::new(&engine.template_path)
+            .join(format!("{}.html", template));
+        if !path.is_file() {
+            anyhow::bail!(
+                "Alternate template '{}' of '{}' not found at {}",
+                template,
+                file.name,
+                path.display()
+            );
+        }
+        alternate_links.push_str(&alternate_link(
+            &template,
+            &format!("{}{}/", page_url, template),
+        ));
+        let html = inject_snippets(
+            &engine.render_page(&context, &template)?,
+            &settings.head_snippet,
+            &settings.footer_snippet,
+        );
+        alternate_pages.push((template, html));
+    }
+
+    // The alternate links go in separately, so a head snippet the
+    // template already renders with `{{extra_head}}` is still found.
+    let content = inject_snippets(
+        &inject_snippets(
+            &engine.render_page(&context, layout)?,
+            &alternate_links,
+            "",
+        ),
+        &settings.head_snippet,
+        &settings.footer_snippet,
+    );
+
+    let rss = if settings.generators.rss {
+        validate_feed_metadata(
+            &metadata,
+            settings.url_mode.is_some()
+                || site_url(&metadata).is_some(),
+        )
+        .context("Invalid feed metadata")?;
+        // `noindex` pages keep their feed channel but publish no item,
+        // and so may pages without a body.
+        let publish_item =
+            publishes_feed_item(&file.name, &body, settings.empty_body)
+                && !robots.is_noindex();
+        let rss = page_rss(
+            &metadata,
+            &excerpt.excerpt,
+            excerpt.explicit,
+            publish_item,
+            settings.url_mode.as_ref(),
+        )?;
+        if settings.rich_rss {
+            with_rich_content(
+                &rss,
+                &excerpt.body,
+                meta_str(&metadata, "image_url").unwrap_or_default(),
+            )
+        } else {
+            rss
+        }
+    } else {
+        String::new()
+    };
+
+    let generators = &settings.generators;
+
+    let manifest_content = if generators.manifest {
+        ManifestConfig::from_metadata(&metadata)
+            .and_then(|config| {
+                ManifestGenerator::new(config).generate()
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("Error generating manifest: {}", e);
+                String::new()
+            })
+    } else {
+        String::new()
+    };
+
+    let news_sitemap_content = if generators.news_sitemap {
+        let news_sitemap_config =
+            NewsSiteMapConfig::new(metadata.clone())
+                .strict(settings.strict_news_dates);
+        let news_sitemap_generator =
+            NewsSiteMapGenerator::new(news_sitemap_config);
+
+        match news_sitemap_generator
+            .try_generate_xml()
+            .context("Invalid news_publication_date")?
+        {
+            xml if !xml.is_empty() => xml, // Use the generated XML string
+            _ => {
+                eprintln!("Error generating news sitemap XML.");
+                String::new() // Default to an empty string if XML generation fails
+            }
+        }
+    } else {
+        String::new()
+    };
+
+    let cname_content = metadata
+        .get("cname")
+        .filter(|_| generators.cname)
+        .and_then(|domain| CnameConfig::new(domain, None, None).ok())
+        .map(|config| CnameGenerator::new(config).generate())
+        .unwrap_or_default();
+
+    let humans_content = metadata
+        .get("humans")
+        .filter(|_| generators.humans)
+        .map(|humans| {
+            // Try parsing the "humans" string into a HashMap
+            let humans: HashMap<String, String> =
+                serde_json::from_str(humans)
+                    .context("Failed to parse humans metadata")
+                    .unwrap_or_else(|err| {
+                        eprintln!(
+                            "Error parsing humans metadata: {}",
+                            err
+                        );
+                        HashMap::new() // Default to an empty HashMap if parsing fails
+                    });
+
+            // Generate humans.txt content
+            match HumansConfig::from_metadata(&humans) {
+                Ok(humans_config) => {
+                    HumansGenerator::new(humans_config).generate()
+                }
+                Err(err) => {
+                    eprintln!("Error creating HumansConfig: {}", err);
+                    String::new() // Default to an empty string if creation fails
+                }
+            }
+        })
+        .unwrap_or_default();
+
+    let tags_data =
+        generate_tags_with_options(file, &metadata, &settings.tags);
+
+    update_global_tags_data(global_tags_data, &tags_data);
+
+    let security_data = if generators.security {
+        security(&create_security_data(&metadata))
+    } else {
+        String::new()
+    };
+    let sitemap_data = if generators.sitemap {
+        sitemap_with_options(
+            create_site_map_data(&metadata)?,
+            site_path,
+            &settings.sitemap,
+        )?
+    } else {
+        String::new()
+    };
+    let txt_data = if generators.robots {
+        let mut data = create_txt_data(&with_raw_scalars(
+            &file.content,
+            &metadata,
+            &["robots_crawl_delay"],
+        ));
+        data.sitemaps = sitemap_urls(
+            &data.permalink,
+            &[
+                ("sitemap.xml", &sitemap_data),
+                ("news-sitemap.xml", &news_sitemap_content),
+            ],
+        );
+        txt(&data)
+    } else {
+        String::new()
+    };
+
+    let json = if settings.emit_json_metadata {
+        page_json(&body, &metadata, &excerpt.excerpt, &settings.tags)
+            .context("Failed to generate page metadata")?
+    } else {
+        String::new()
+    };
+
+    Ok(FileData {
+        cname: cname_content,
+        content,
+        keyword: keywords.join(", "),
+        human: humans_content,
+        manifest: manifest_content,
+        name: file.name.clone(),
+        rss,
+        security: security_data,
+        sitemap: sitemap_data,
+        sitemap_news: news_sitemap_content,
+        txt: txt_data,
+        json,
+        alternates: alternate_pages,
+        output_path: page_html_path(
+            &file.name,
+            meta_str(&metadata, "permalink"),
+            &settings.urls,
+        ),
+    })
+}
+
+/// Replaces the sitemap `written` by a hard link to the `deployed` one
+/// when both list the same URLs (see [`same_url_set`]).
+///
+/// This is best effort: when the files cannot be linked, for instance
+/// across file systems, the written sitemap is kept.
+fn keep_unchanged_sitemap(deployed: &Path, written: &Path) {
+    let unchanged = match (
+        fs::read_to_string(deployed),
+        fs::read_to_string(written),
+    ) {
+        (Ok(deployed), Ok(written)) => {
+            same_url_set(&deployed, &written)
+        }
+        _ => false,
+    };
+    if !unchanged {
+        return;
+    }
+
+    let mut linked = written.as_os_str().to_owned();
+    linked.push(".previous");
+    let linked = PathBuf::from(linked);
+    let result = fs::hard_link(deployed, &linked)
+        .and_then(|()| fs::rename(&linked, written));
+    if let Err(err) = result {
+        let _ = fs::remove_file(&linked);
+        log::debug!(
+            "Keeping the rewritten sitemap {}: {}",
+            written.display(),
+            err
+        );
+    }
+}
+
+/// Returns the path, relative to the site directory, of the sitemap of
+/// the page loaded from `file_name`.
+fn sitemap_path(file_name: &str) -> PathBuf {
+    Path::new(page_url_path(file_name).trim_start_matches('/'))
+        .join("sitemap.xml")
+}
+
+/// Returns the URLs under `permalink` of the `(file name, content)`
+/// sitemaps that were generated, skipping empty ones, for robots.txt to
+/// advertise. None are returned unless `permalink` is an absolute URL
+/// (see [`to_absolute`]).
+fn sitemap_urls(
+    permalink: &str,
+    sitemaps: &[(&str, &str)],
+) -> Vec<String> {
+    sitemaps
+        .iter()
+        .filter(|(_, content)| !content.is_empty())
+        .filter_map(|(file_name, _)| {
+            to_absolute(permalink, file_name).ok()
+        })
+        .collect()
+}
+
+/// Generates the RSS feed of a page.
+///
+/// # Arguments
+///
+/// * `metadata` - The front matter of the page.
+/// * `excerpt` - The rendered HTML excerpt of the page.
+/// * `explicit_excerpt` - Whether the excerpt was marked by a separator.
+/// * `publish_item` - Whether the feed holds an item for the page.
+/// * `url_mode` - How the item link is written, or `None` to resolve a
+///   relative one against the site `url` with [`to_absolute`].
+fn page_rss(
+    metadata: &HashMap<String, String>,
+    excerpt: &str,
+    explicit_excerpt: bool,
+    publish_item: bool,
+    url_mode: Option<&UrlMode>,
+) -> Result<String> {
+    let text = |key: &str| {
+        meta_str(metadata, key).unwrap_or_default().to_string()
+    };
+    // RSS dates are written as RFC 2822 whatever their front matter
+    // layout; `validate_feed_metadata` has already rejected bad ones.
+    let feed_date = |key: &str| match meta_date(metadata, key) {
+        Ok(Some(date)) => to_rfc2822(&date),
+        _ => text(key),
+    };
 
     let mut rss_data = RssData::new(None);
 
     macro_set_rss_data_fields!(
         rss_data,
-        AtomLink = macro_metadata_option!(metadata, "atom_link"),
-        Author = macro_metadata_option!(metadata, "author"),
-        Category = macro_metadata_option!(metadata, "category"),
-        Copyright = macro_metadata_option!(metadata, "copyright"),
-        Description = macro_metadata_option!(metadata, "description"),
-        Docs = macro_metadata_option!(metadata, "docs"),
-        Generator = macro_metadata_option!(metadata, "generator"),
-        ImageTitle = macro_metadata_option!(metadata, "image_title"),
-        ImageUrl = macro_metadata_option!(metadata, "image_url"),
-        Language = macro_metadata_option!(metadata, "language"),
-        LastBuildDate =
-            macro_metadata_option!(metadata, "last_build_date"),
-        Link = macro_metadata_option!(metadata, "permalink"),
-        ManagingEditor =
-            macro_metadata_option!(metadata, "managing_editor"),
-        PubDate = macro_metadata_option!(metadata, "pub_date"),
-        Title = macro_metadata_option!(metadata, "title"),
-        Ttl = macro_metadata_option!(metadata, "ttl"),
-        Webmaster = macro_metadata_option!(metadata, "webmaster")
+        AtomLink = text("atom_link"),
+        Author = text("author"),
+        Category = text("category"),
+        Copyright = text("copyright"),
+        Description = text("description"),
+        Docs = text("docs"),
+        Generator = text("generator"),
+        ImageTitle = text("image_title"),
+        ImageUrl = text("image_url"),
+        Language = text("language"),
+        LastBuildDate = feed_date("last_build_date"),
+        Link = text("permalink"),
+        ManagingEditor = text("managing_editor"),
+        PubDate = feed_date("pub_date"),
+        Title = text("title"),
+        Ttl = text("ttl"),
+        Webmaster = text("webmaster")
     );
 
-    let item = RssItem::new()
-        .guid(macro_metadata_option!(metadata, "item_guid"))
-        .description(macro_metadata_option!(
-            metadata,
-            "item_description"
-        ))
-        .link(macro_metadata_option!(metadata, "item_link"))
-        .pub_date(macro_metadata_option!(metadata, "item_pub_date"))
-        .title(macro_metadata_option!(metadata, "item_title"));
-    rss_data.add_item(item);
-
-    let rss = generate_rss(&rss_data)?;
-
-    let manifest_content = ManifestConfig::from_metadata(&metadata)
-        .and_then(|config| ManifestGenerator::new(config).generate())
-        .unwrap_or_else(|e| {
-            eprintln!("Error generating manifest: {}", e);
-            String::new()
+    // An explicit excerpt wins over the front matter description, and
+    // the first paragraph is only used when neither is available.
+    let item_description = match text("item_description") {
+        description if !description.is_empty() && !explicit_excerpt => {
+            description
+        }
+        description => match excerpt_text(excerpt) {
+            text if text.is_empty() => description,
+            text => text,
+        },
+    };
+
+    if publish_item {
+        // Without a URL mode, a relative item link is resolved against
+        // the site `url`.
+        let item_link = match (url_mode, text("item_link")) {
+            (_, link) if link.is_empty() => link,
+            (Some(mode), link) => mode
+                .resolve(&link)
+                .context("Invalid feed metadata: item_link")?,
+            (None, link) => match site_url(metadata) {
+                Some(base) => to_absolute(base, &link)
+                    .context("Invalid feed metadata: item_link")?,
+                None => link,
+            },
+        };
+        let item = RssItem::new()
+            .guid(text("item_guid"))
+            .description(item_description)
+            .link(item_link)
+            .pub_date(feed_date("item_pub_date"))
+            .title(text("item_title"));
+        rss_data.add_item(item);
+    }
+
+    Ok(generate_rss(&rss_data)?)
+}
+
+/// Returns the non-empty site `url` of the front matter.
+fn site_url(metadata: &HashMap<String, String>) -> Option<&str> {
+    meta_str(metadata, "url")
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+}
+
+/// Declares the `content` and `media` namespaces on an RSS 2.0 feed and
+/// adds the page `html` to its item as `content:encoded`, along with a
+/// `media:thumbnail` for a non-empty `thumbnail` URL.
+///
+/// A feed without an item only gains the namespace declarations.
+fn with_rich_content(rss: &str, html: &str, thumbnail: &str) -> String {
+    const ATOM_NAMESPACE: &str =
+        r#"xmlns:atom="http://www.w3.org/2005/Atom""#;
+
+    let mut item = format!(
+        "<content:encoded><![CDATA[{}]]></content:encoded>",
+        // `]]>` would end the CDATA section early, so split it across two.
+        html.replace("]]>", "]]]]><![CDATA[>")
+    );
+    if !thumbnail.trim().is_empty() {
+        item.push_str(&format!(
+            r#"<media:thumbnail url="{}"/>"#,
+            escape(thumbnail.trim())
+        ));
+    }
+    item.push_str("</item>");
+
+    rss.replacen(
+        ATOM_NAMESPACE,
+        &format!(
+            r#"{} xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:media="http://search.yahoo.com/mrss/""#,
+            ATOM_NAMESPACE
+        ),
+        1,
+    )
+    .replacen("</item>", &item, 1)
+}
+
+/// Generates the site-wide `urls.txt` and `llms.txt` listings enabled in
+/// `options`, as `(file name, content)` pairs.
+fn site_listings(
+    files: &[FileData],
+    options: &CompileOptions,
+) -> Vec<(&'static str, String)> {
+    let mut listings = Vec::new();
+    if !options.emit_url_list && !options.emit_llms_txt {
+        return listings;
+    }
+
+    let home = files
+        .iter()
+        .find(|file| {
+            Path::new(&file.name).file_stem() == Some("index".as_ref())
+        })
+        .and_then(|file| {
+            extract_and_prepare_metadata(&file.content).ok()
+        })
+        .map(|(metadata, _, _)| metadata)
+        .unwrap_or_default();
+    let home_text =
+        |key: &str| meta_str(&home, key).unwrap_or_default().trim();
+    let base_url = match options.base_url.trim() {
+        "" => home_text("permalink"),
+        base_url => base_url,
+    };
+
+    if options.emit_url_list {
+        listings.push((
+            URL_LIST_FILE,
+            generate_url_list(files, base_url, &options.url),
+        ));
+    }
+    if options.emit_llms_txt {
+        let name = match home_text("name") {
+            "" => home_text("title"),
+            name => name,
+        };
+        listings.push((
+            LLMS_TXT_FILE,
+            generate_llms_txt(
+                files,
+                base_url,
+                &options.url,
+                name,
+                home_text("description"),
+            ),
+        ));
+    }
+    listings
+}
+
+/// Returns whether a page with this `body` publishes an RSS item under
+/// `policy`, logging why when the body is empty.
+fn publishes_feed_item(
+    name: &str,
+    body: &str,
+    policy: EmptyBodyPolicy,
+) -> bool {
+    if !body.trim().is_empty() {
+        return true;
+    }
+    match policy {
+        EmptyBodyPolicy::Warn => {
+            log::warn!("{} has an empty body", name);
+            true
+        }
+        EmptyBodyPolicy::SkipFeedItem => {
+            log::info!(
+                "{} has an empty body; skipping its feed item",
+                name
+            );
+            false
+        }
+    }
+}
+
+/// Fills a missing `og:image` and `twitter:image` with the first image in
+/// `html`, unless the front matter sets `image_url`.
+///
+/// Relative image sources are resolved against `base_url`, or the page
+/// `permalink` when `base_url` is absent; unresolvable or unsafe URLs
+/// leave the tags unchanged.
+fn with_first_image(
+    mut tags: MetaTagGroups,
+    metadata: &HashMap<String, String>,
+    html: &str,
+) -> MetaTagGroups {
+    let has_image = meta_str(metadata, "image_url")
+        .map_or(false, |url| !url.trim().is_empty());
+    if has_image {
+        return tags;
+    }
+
+    let base_url = meta_str(metadata, "base_url")
+        .filter(|url| !url.trim().is_empty())
+        .or_else(|| meta_str(metadata, "permalink"))
+        .unwrap_or_default();
+    let image = match first_image(html, base_url) {
+        Some(image) => image,
+        None => return tags,
+    };
+
+    let mut metadata = metadata.clone();
+    for key in ["og:image", "twitter:image"] {
+        let value = metadata.entry(key.to_string()).or_default();
+        if value.trim().is_empty() {
+            *value = image.clone();
+        }
+    }
+    tags.generate_og_meta_tags(&metadata);
+    tags.generate_twitter_meta_tags(&metadata);
+    tags
+}
+
+/// Serializes the [`PageMetadata`] of a page for its `page.json` sidecar.
+///
+/// # Arguments
+///
+/// * `body` - The Markdown body of the page, without front matter.
+/// * `metadata` - The front matter of the page.
+/// * `excerpt` - The rendered HTML excerpt of the page.
+/// * `tags` - How page tags are read from the front matter.
+fn page_json(
+    body: &str,
+    metadata: &HashMap<String, String>,
+    excerpt: &str,
+    tags: &TagOptions,
+) -> Result<String> {
+    let text = |key: &str| {
+        meta_str(metadata, key).unwrap_or_default().to_string()
+    };
+    let page = PageMetadata {
+        page: PageData {
+            title: text("title"),
+            description: text("description"),
+            date: text("date"),
+            permalink: text("permalink"),
+        },
+        tags: parse_tags(metadata, tags),
+        word_count: word_count(body)?,
+        excerpt: excerpt_text(excerpt),
+        metadata: metadata
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    };
+
+    Ok(serde_json::to_string_pretty(&page)?)
+}
+
+/// Collects the sitemap options declared in each file's front matter: the
+/// mobile and AMP variants, and the pages excluded by a `noindex` robots
+/// directive, both keyed by the page's output directory (`""` for the
+/// home page), and the `robots_disallow` paths of every file.
+///
+/// Files whose metadata cannot be extracted are skipped here and reported
+/// when they are processed; an invalid `amp_url` is an error.
+fn collect_sitemap_options(
+    files: &[FileData],
+) -> Result<SitemapOptions> {
+    let mut options = SitemapOptions::default();
+
+    for file in files {
+        let metadata = match extract_and_prepare_metadata(&file.content)
+        {
+            Ok((metadata, _, _)) => metadata,
+            Err(_) => continue,
+        };
+
+        let stem = Path::new(&file.name).with_extension("");
+        let key = match stem.to_string_lossy().as_ref() {
+            "index" => String::new(),
+            other => other.to_string(),
+        };
+
+        let robots = meta_robots(&file.content, &metadata);
+        if robots.is_noindex() {
+            let _ = options.excluded.insert(key.clone());
+        }
+
+        // Invalid rules are already reported when robots.txt is built.
+        if let Ok(txt_data) = TxtData::from_metadata(&metadata) {
+            for path in txt_data.disallow {
+                if !options.disallowed.contains(&path) {
+                    options.disallowed.push(path);
+                }
+            }
+        }
+
+        let data = alternate_data(&file.content, &metadata)
+            .with_context(|| {
+                format!("Invalid alternate versions in '{}'", file.name)
+            })?;
+        if !data.is_empty() {
+            let _ = options.alternates.insert(key, data);
+        }
+    }
+
+    Ok(options)
+}
+
+/// Front matter keys holding RSS dates.
+const FEED_DATE_KEYS: [&str; 3] =
+    ["pub_date", "last_build_date", "item_pub_date"];
+
+/// Front matter keys holding RSS links other than the permalink.
+const FEED_URL_KEYS: [&str; 3] =
+    ["atom_link", "image_url", "item_link"];
+
+/// Checks that the RSS dates and links in `metadata` parse, so a typo is
+/// reported against its key rather than producing an invalid feed.
+///
+/// Empty values are treated as absent, as `macro_metadata_option!` did.
+/// With `resolve_item_link`, `item_link` may be relative, as it is
+/// resolved against a [`UrlMode`] or the site `url` later.
+fn validate_feed_metadata(
+    metadata: &HashMap<String, String>,
+    resolve_item_link: bool,
+) -> Result<(), MetadataError> {
+    let present = |result: Result<(), MetadataError>| match result {
+        Err(e) if e.is_empty() => Ok(()),
+        result => result,
+    };
+    for key in FEED_DATE_KEYS {
+        present(meta_date(metadata, key).map(drop))?;
+    }
+    for key in FEED_URL_KEYS {
+        if resolve_item_link && key == "item_link" {
+            continue;
+        }
+        present(meta_url(metadata, key).map(drop))?;
+    }
+    Ok(())
+}
+
+/// Checks the front matter of `file` against the schema of its content
+/// type, named by its `type` key or else its `layout`.
+///
+/// Issues are logged as warnings, or returned as one error listing them
+/// all when [`CompileOptions::strict_metadata`] is set.
+fn check_metadata_schema(
+    file: &FileData,
+    metadata: &HashMap<String, String>,
+    settings: &PageSettings,
+) -> Result<()> {
+    let content_type = ["type", "layout"].iter().find_map(|key| {
+        meta_str(metadata, key)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    });
+    let (content_type, schema) = match content_type.and_then(|name| {
+        settings.schemas.get(name).map(|schema| (name, schema))
+    }) {
+        Some(found) => found,
+        None => return Ok(()),
+    };
+
+    // Unquoted booleans and numbers arrive empty from the parser.
+    let keys: Vec<&str> =
+        schema.fields.keys().map(String::as_str).collect();
+    let metadata = with_raw_scalars(&file.content, metadata, &keys);
+    let issues: Vec<String> = validate_metadata(&metadata, schema)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    if issues.is_empty() {
+        Ok(())
+    } else if settings.strict_metadata {
+        anyhow::bail!(
+            "'{}' does not match the '{}' schema: {}",
+            file.name,
+            content_type,
+            issues.join("; ")
+        )
+    } else {
+        for issue in issues {
+            log::warn!("{}: {}", file.name, issue);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the language tag of a page: its `lang` key, else the site
+/// `default` language, else `en`.
+///
+/// # Errors
+///
+/// Returns an error if `lang` is not a well-formed language tag, such as
+/// `fr` or `zh-Hant-TW`.
+fn page_language(
+    metadata: &HashMap<String, String>,
+    default: &str,
+) -> Result<String> {
+    let lang = match meta_str(metadata, "lang").map(str::trim) {
+        Some(lang) if !lang.is_empty() => lang,
+        _ => match default.trim() {
+            "" => "en",
+            default => default,
+        },
+    };
+
+    let mut subtags = lang.split('-');
+    let well_formed = subtags.next().map_or(false, |language| {
+        (2..=8).contains(&language.len())
+            && language.chars().all(|c| c.is_ascii_alphabetic())
+    }) && subtags.all(|subtag| {
+        (1..=8).contains(&subtag.len())
+            && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    if !well_formed {
+        anyhow::bail!(
+            "Invalid lang '{}': expected a language tag",
+            lang
+        );
+    }
+    Ok(lang.to_string())
+}
+
+/// Reads the `<meta name="robots">` directives of a page, restoring an
+/// inline list such as `[noindex, nofollow]` from the raw front matter.
+fn meta_robots(
+    content: &str,
+    metadata: &HashMap<String, String>,
+) -> MetaRobots {
+    MetaRobots::from_metadata(&with_raw_scalars(
+        content,
+        metadata,
+        &["meta_robots", "robots"],
+    ))
+}
+
+/// Reads the mobile and AMP variants of a page from its metadata.
+///
+/// The metadata extractor only keeps string values, so an unquoted
+/// `mobile: true` arrives empty; in that case the raw front matter value
+/// is used instead.
+fn alternate_data(
+    content: &str,
+    metadata: &HashMap<String, String>,
+) -> Result<AlternateData, DataError> {
+    AlternateData::from_metadata(&with_raw_scalars(
+        content,
+        metadata,
+        &["mobile"],
+    ))
+}
+
+/// Reads the `alternate_templates` of a page, a single name or a
+/// `[print, amp]` list, dropping duplicates.
+///
+/// Each name is both a template in the template directory and the
+/// directory the alternate is written to, so only ASCII letters, digits,
+/// `-` and `_` are allowed.
+fn alternate_templates(
+    metadata: &HashMap<String, String>,
+) -> Result<Vec<String>> {
+    let value = meta_str(metadata, "alternate_templates")
+        .unwrap_or_default()
+        .trim();
+    let value = value
+        .strip_prefix('[')
+        .and_then(|list| list.strip_suffix(']'))
+        .unwrap_or(value);
+
+    let mut templates: Vec<String> = Vec::new();
+    for name in value.split(',') {
+        let name = name.trim().trim_matches(|c| c == '"' || c == '\'');
+        if name.is_empty() || templates.iter().any(|t| t == name) {
+            continue;
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            anyhow::bail!("Invalid alternate template name '{}'", name);
+        }
+        templates.push(name.to_string());
+    }
+    Ok(templates)
+}
+
+/// Returns the `<link>` tag pointing a page at its `template` alternate
+/// at `url`: `amphtml` for `amp`, a `media="print"` alternate for
+/// `print`, and an alternate titled `template` otherwise.
+fn alternate_link(template: &str, url: &str) -> String {
+    match template {
+        "amp" => format!(r#"<link rel="amphtml" href="{}">"#, url),
+        "print" => format!(
+            r#"<link rel="alternate" media="print" href="{}">"#,
+            url
+        ),
+        _ => format!(
+            r#"<link rel="alternate" title="{}" href="{}">"#,
+            template, url
+        ),
+    }
+}
+
+/// Updates the global tags data with new tag information.
+///
+/// # Arguments
+///
+/// * `global_tags_data` - Mutable reference to global tags data hashmap.
+/// * `tags_data` - Reference to the tags data hashmap to be merged.
+fn update_global_tags_data(
+    global_tags_data: &mut HashMap<String, Vec<PageData>>,
+    tags_data: &HashMap<String, Vec<HashMap<String, String>>>,
+) {
+    for (tag, pages_data) in tags_data {
+        let page_info: Vec<PageData> = pages_data
+            .iter()
+            .map(|page_data| PageData {
+                title: page_data
+                    .get("title")
+                    .cloned()
+                    .unwrap_or_default(),
+                description: page_data
+                    .get("description")
+                    .cloned()
+                    .unwrap_or_default(),
+                permalink: page_data
+                    .get("permalink")
+                    .cloned()
+                    .unwrap_or_default(),
+                date: page_data
+                    .get("date")
+                    .cloned()
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        global_tags_data
+            .entry(tag.clone())
+            .or_default()
+            .extend(page_info);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macro_metadata_option;
+    use crate::utilities::permalink::TrailingSlash;
+    use crate::utilities::schema::FieldType;
+    use crate::utilities::write::LineEnding;
+    use html_generator::{generate_html, HtmlConfig};
+    use metadata_gen::generate_metatags;
+    use rss_gen::data::RssDataField;
+
+    #[test]
+    fn test_compile_missing_directories() {
+        let build_dir_path = Path::new("/nonexistent/build");
+        let content_path = Path::new("/nonexistent/content");
+        let site_path = Path::new("/nonexistent/site");
+        let template_path = Path::new("/nonexistent/templates");
+
+        let result = compile(
+            build_dir_path,
+            content_path,
+            site_path,
+            template_path,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_compile_content_paths_missing_directory() {
+        let result = compile_content_paths(
+            Path::new("/nonexistent/build"),
+            &[
+                Path::new("/nonexistent/shared"),
+                Path::new("/nonexistent/content"),
+            ],
+            Path::new("/nonexistent/site"),
+            Path::new("/nonexistent/templates"),
+            None,
+            CompileOptions::default(),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_compile_with_hooks_engine_keeps_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine =
+            Engine::new("examples/templates", Duration::from_secs(60));
+
+        for _ in 0..2 {
+            let _ = compile_with_hooks(
+                &dir.path().join("build"),
+                &[Path::new("examples/content")],
+                &dir.path().join("site"),
+                Path::new("examples/templates"),
+                CompileOptions::default(),
+                CompileHooks {
+                    engine: Some(&mut engine),
+                    ..CompileHooks::default()
+                },
+            )
+            .unwrap();
+            assert!(!engine.render_cache.is_empty());
+        }
+        assert!(dir.path().join("site/index.html").exists());
+    }
+
+    #[test]
+    fn test_compile_with_hooks_combined() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared");
+        fs::create_dir(&shared).unwrap();
+        let _ = fs::copy(
+            "examples/content/terms.md",
+            shared.join("terms.md"),
+        )
+        .unwrap();
+        let mut engine =
+            Engine::new("examples/templates", Duration::from_secs(60));
+
+        let overrides = compile_with_hooks(
+            &dir.path().join("build"),
+            &[&shared, Path::new("examples/content")],
+            &dir.path().join("site"),
+            Path::new("examples/templates"),
+            CompileOptions::default(),
+            CompileHooks {
+                engine: Some(&mut engine),
+                renderer: Some(Box::new(HtmlGeneratorRenderer)),
+                transform: Some(&|_, html| {
+                    Ok(html.replace("</body>", "<p>hooked</p></body>"))
+                }),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].name, "terms.md");
+        assert!(!engine.render_cache.is_empty());
+        let terms = fs::read_to_string(
+            dir.path().join("site/terms/index.html"),
+        )
+        .unwrap();
+        assert!(terms.contains("<p>hooked</p>"));
+    }
+
+    #[test]
+    fn test_compile_writes_build_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site");
+        let compile = |build_id: Option<&str>| {
+            compile_with_options(
+                &dir.path().join("build"),
+                Path::new("examples/content"),
+                &site,
+                Path::new("examples/templates"),
+                None,
+                CompileOptions {
+                    build_id: build_id.map(str::to_string),
+                    emit_build_json: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let json =
+                fs::read_to_string(site.join("build.json")).unwrap();
+            let info: serde_json::Value =
+                serde_json::from_str(&json).unwrap();
+            info["build_id"].as_str().unwrap().to_string()
+        };
+
+        assert_eq!(compile(Some("release-42")), "release-42");
+        let derived = compile(None);
+        assert_eq!(derived.len(), 16);
+        assert_eq!(compile(None), derived);
+    }
+
+    #[test]
+    fn test_compile_with_hooks_transform() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site");
+        let compile = |transform: &HtmlTransform| {
+            compile_with_hooks(
+                &dir.path().join("build"),
+                &[Path::new("examples/content")],
+                &site,
+                Path::new("examples/templates"),
+                CompileOptions::default(),
+                CompileHooks {
+                    transform: Some(transform),
+                    ..CompileHooks::default()
+                },
+            )
+        };
+
+        let _ = compile(&|file, html| {
+            Ok(html.replace(
+                "</body>",
+                &format!("<p>banner-{}</p></body>", file.name),
+            ))
+        })
+        .unwrap();
+        let index =
+            fs::read_to_string(site.join("index.html")).unwrap();
+        assert!(index.contains("banner-index.md"), "{}", index);
+
+        let err = compile(&|file, html| {
+            if file.name == "index.md" {
+                anyhow::bail!("no banner")
+            }
+            Ok(html)
+        })
+        .unwrap_err();
+        assert!(format!("{:#}", err)
+            .contains("Failed to transform 'index.md': no banner"));
+    }
+
+    #[test]
+    fn test_planned_outputs_match_compile() {
+        fn walk(dir: &Path, root: &Path, paths: &mut Vec<PathBuf>) {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    walk(&path, root, paths);
+                } else {
+                    paths.push(
+                        path.strip_prefix(root).unwrap().to_path_buf(),
+                    );
+                }
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site");
+        let options = CompileOptions {
+            emit_json_metadata: true,
+            emit_url_list: true,
+            generators: GeneratorFlags {
+                rss: false,
+                ..GeneratorFlags::all()
+            },
+            ..Default::default()
+        };
+        let planned =
+            planned_outputs(Path::new("examples/content"), &options)
+                .unwrap();
+        assert!(!site.exists());
+
+        compile_with_options(
+            &dir.path().join("build"),
+            Path::new("examples/content"),
+            &site,
+            Path::new("examples/templates"),
+            None,
+            options,
+        )
+        .unwrap();
+        let mut written = Vec::new();
+        walk(&site, &site, &mut written);
+        written.retain(|path| {
+            !["main.js", "sw.js"].contains(&path.to_str().unwrap())
         });
+        written.sort();
+
+        assert_eq!(planned, written);
+        assert!(planned.contains(&PathBuf::from("index.html")));
+        assert!(planned.contains(&PathBuf::from(URL_LIST_FILE)));
+        assert!(!planned.iter().any(|path| path.ends_with("rss.xml")));
+    }
+
+    #[test]
+    fn test_compile_write_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site");
+        compile_with_options(
+            &dir.path().join("build"),
+            Path::new("examples/content"),
+            &site,
+            Path::new("examples/templates"),
+            None,
+            CompileOptions {
+                emit_url_list: true,
+                write: WriteOptions {
+                    line_ending: LineEnding::CrLf,
+                    trailing_newline: true,
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for file in ["robots.txt", URL_LIST_FILE] {
+            let content = fs::read_to_string(site.join(file)).unwrap();
+            assert!(content.ends_with("\r\n"), "{}", file);
+            assert!(!content.replace("\r\n", "").contains('\n'));
+        }
+    }
+
+    #[test]
+    fn test_compile_skips_empty_enabled_generators() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site");
+        let options = CompileOptions::default();
+        assert!(options.generators.humans);
+        compile_with_options(
+            &dir.path().join("build"),
+            Path::new("examples/content"),
+            &site,
+            Path::new("examples/templates"),
+            None,
+            options,
+        )
+        .unwrap();
+
+        // No example page has a `humans` key.
+        assert!(site.join("index.html").is_file());
+        assert!(site.join("CNAME").is_file());
+        assert!(!site.join("humans.txt").exists());
+        assert!(!site.join("contact/humans.txt").exists());
+    }
+
+    #[test]
+    fn test_compile_keeps_unchanged_sitemap() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site");
+        let compile = || {
+            compile_with_options(
+                &dir.path().join("build"),
+                Path::new("examples/content"),
+                &site,
+                Path::new("examples/templates"),
+                None,
+                CompileOptions::default(),
+            )
+            .unwrap()
+        };
+        let modified = |path: &Path| {
+            fs::metadata(site.join(path)).unwrap().modified().unwrap()
+        };
+
+        compile();
+        compile();
+        let sitemap = sitemap_path("index.md");
+        let first = modified(&sitemap);
+
+        std::thread::sleep(Duration::from_millis(20));
+        compile();
+        assert_eq!(modified(&sitemap), first);
+        assert!(!site.join("sitemap.xml.hash").exists());
+        assert!(!site.join("sitemap.xml.previous").exists());
+        assert!(fs::read_to_string(site.join(&sitemap))
+            .unwrap()
+            .contains("<urlset"));
+    }
+
+    #[test]
+    fn test_compile_keep_build_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let build = dir.path().join("build");
+        let compile = |keep_build_on_error| {
+            compile_with_options(
+                &build,
+                Path::new("examples/content"),
+                &dir.path().join("site"),
+                Path::new("/nonexistent/templates"),
+                None,
+                CompileOptions {
+                    keep_build_on_error,
+                    ..Default::default()
+                },
+            )
+            .unwrap_err()
+        };
+
+        let error = compile(false);
+        assert!(!build.exists());
+        assert!(
+            !format!("{:#}", error).contains("Build directory kept")
+        );
+
+        let error = compile(true);
+        assert!(build.exists());
+        assert!(format!("{:#}", error).starts_with(&format!(
+            "Build directory kept at {}",
+            build.display()
+        )));
+
+        // A directory the caller created survives without the flag.
+        fs::remove_dir_all(&build).unwrap();
+        fs::create_dir(&build).unwrap();
+        fs::write(build.join("keep.txt"), "mine").unwrap();
+        let _ = compile(false);
+        assert!(build.join("keep.txt").exists());
+    }
+
+    #[test]
+    fn test_compile_url_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site");
+        let options = CompileOptions {
+            url: UrlOptions {
+                policy: TrailingSlash::Never,
+                ..UrlOptions::default()
+            },
+            ..Default::default()
+        };
+        let planned =
+            planned_outputs(Path::new("examples/content"), &options)
+                .unwrap();
+
+        compile_with_options(
+            &dir.path().join("build"),
+            Path::new("examples/content"),
+            &site,
+            Path::new("examples/templates"),
+            None,
+            options,
+        )
+        .unwrap();
+
+        assert!(planned.contains(&PathBuf::from("contact.html")));
+        assert!(site.join("contact.html").is_file());
+        assert!(!site.join("contact/index.html").exists());
+        assert!(site.join("index.html").is_file());
+        let contact =
+            fs::read_to_string(site.join("contact.html")).unwrap();
+        assert!(contact.contains(r#"href="/features.html""#));
+    }
+
+    #[test]
+    fn test_compile_strict_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let templates = dir.path().join("templates");
+        fs::create_dir(&templates).unwrap();
+        for entry in fs::read_dir("examples/templates").unwrap() {
+            let path = entry.unwrap().path();
+            let mut content = fs::read_to_string(&path).unwrap();
+            if path.extension().map_or(false, |ext| ext == "html") {
+                content.push_str("\n{{tittle}} {{ title }}");
+            }
+            fs::write(
+                templates.join(path.file_name().unwrap()),
+                content,
+            )
+            .unwrap();
+        }
+        let compile = |templates: &Path| {
+            compile_with_options(
+                &dir.path().join("build"),
+                Path::new("examples/content"),
+                &dir.path().join("site"),
+                templates,
+                None,
+                CompileOptions {
+                    strict_templates: true,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert!(compile(Path::new("examples/templates")).is_ok());
+        let error = format!("{:#}", compile(&templates).unwrap_err());
+        assert!(
+            error.contains("uses undefined variables: 'tittle' (line"),
+            "{}",
+            error
+        );
+        assert!(error.contains("' title ' (line"), "{}", error);
+    }
+
+    #[test]
+    fn test_compile_section_indexes() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir(&content).unwrap();
+        for entry in fs::read_dir("examples/content").unwrap() {
+            let path = entry.unwrap().path();
+            let _ = fs::copy(
+                &path,
+                content.join(path.file_name().unwrap()),
+            )
+            .unwrap();
+        }
+        let contact =
+            fs::read_to_string(content.join("contact.md")).unwrap();
+        fs::write(
+            content.join("contact.md"),
+            contact.replace(
+                "https://kaishi.one/contact\"",
+                "https://kaishi.one/team/contact/\"",
+            ),
+        )
+        .unwrap();
+
+        let site = dir.path().join("site");
+        compile_with_options(
+            &dir.path().join("build"),
+            &content,
+            &site,
+            Path::new("examples/templates"),
+            None,
+            CompileOptions {
+                section_indexes: Some(SectionIndexOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let index =
+            fs::read_to_string(site.join("team/index.html")).unwrap();
+        assert!(
+            index.contains(r#"<a href="/team/contact/index.html">"#),
+            "{}",
+            index
+        );
+    }
+
+    #[test]
+    fn test_compile_alternate_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = dir.path().join("content");
+        let templates = dir.path().join("templates");
+        for (from, to) in [
+            ("examples/content", &content),
+            ("examples/templates", &templates),
+        ] {
+            fs::create_dir(to).unwrap();
+            for entry in fs::read_dir(from).unwrap() {
+                let path = entry.unwrap().path();
+                let _ =
+                    fs::copy(&path, to.join(path.file_name().unwrap()))
+                        .unwrap();
+            }
+        }
+        let contact =
+            fs::read_to_string(content.join("contact.md")).unwrap();
+        fs::write(
+            content.join("contact.md"),
+            contact.replacen(
+                "layout:",
+                "alternate_templates: [print, amp]\nlayout:",
+                1,
+            ),
+        )
+        .unwrap();
+        let page =
+            fs::read_to_string(templates.join("page.html")).unwrap();
+        fs::write(
+            templates.join("print.html"),
+            page.replace("<body", "<body data-view=\"print\""),
+        )
+        .unwrap();
+        let layout =
+            fs::read_to_string(templates.join("contact.html")).unwrap();
+        fs::write(
+            templates.join("contact.html"),
+            layout.replace("</head>", "{{extra_head}}</head>"),
+        )
+        .unwrap();
+
+        let site = dir.path().join("site");
+        let compile = || {
+            compile_with_options(
+                &dir.path().join("build"),
+                &content,
+                &site,
+                &templates,
+                None,
+                CompileOptions {
+                    head_snippet: Some(
+                        r#"<meta name="x-head" content="1">"#
+                            .to_string(),
+                    ),
+                    ..Default::default()
+                },
+            )
+        };
+
+        let error = format!("{:#}", compile().unwrap_err());
+        assert!(
+            error.contains(
+                "Alternate template 'amp' of 'contact.md' not found"
+            ),
+            "{}",
+            error
+        );
+
+        fs::write(templates.join("amp.html"), &page).unwrap();
+        compile().unwrap();
+        let print =
+            fs::read_to_string(site.join("contact/print/index.html"))
+                .unwrap();
+        assert!(print.contains("data-view=\"print\""));
+        assert!(site.join("contact/amp/index.html").exists());
+        let canonical =
+            fs::read_to_string(site.join("contact/index.html"))
+                .unwrap();
+        assert!(canonical.contains(
+            r#"<link rel="alternate" media="print" href="/contact/print/">"#
+        ));
+        assert!(canonical
+            .contains(r#"<link rel="amphtml" href="/contact/amp/">"#));
+        assert_eq!(canonical.matches(r#"name="x-head""#).count(), 1);
+
+        let planned =
+            planned_outputs(&content, &CompileOptions::default())
+                .unwrap();
+        assert!(planned
+            .contains(&PathBuf::from("contact/print/index.html")));
+    }
+
+    #[test]
+    fn test_alternate_templates() {
+        let metadata = |value: &str| {
+            let mut metadata = HashMap::new();
+            let _ = metadata.insert(
+                "alternate_templates".to_string(),
+                value.to_string(),
+            );
+            metadata
+        };
+
+        assert_eq!(
+            alternate_templates(&metadata("[print, 'amp', print]"))
+                .unwrap(),
+            ["print", "amp"]
+        );
+        assert_eq!(
+            alternate_templates(&metadata("print")).unwrap(),
+            ["print"]
+        );
+        assert!(alternate_templates(&HashMap::new())
+            .unwrap()
+            .is_empty());
+        assert!(alternate_templates(&metadata("[../index]")).is_err());
+    }
+
+    #[test]
+    fn test_check_metadata_schema() {
+        let file = FileData::new(
+            "post.md".to_string(),
+            "---\ntype: post\ntitle: Post\ndraft: true\n---\nBody."
+                .to_string(),
+        );
+        let (metadata, _, _) =
+            extract_and_prepare_metadata(&file.content).unwrap();
+        let mut settings = PageSettings {
+            strict_metadata: true,
+            ..Default::default()
+        };
+        let _ = settings.schemas.insert(
+            "post".to_string(),
+            MetadataSchema::new()
+                .required("title", FieldType::Text)
+                .required("draft", FieldType::Bool),
+        );
+        assert!(
+            check_metadata_schema(&file, &metadata, &settings).is_ok()
+        );
+
+        let _ = settings.schemas.insert(
+            "post".to_string(),
+            MetadataSchema::new()
+                .required("date", FieldType::Date)
+                .required("author", FieldType::Text),
+        );
+        let error = check_metadata_schema(&file, &metadata, &settings)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "'post.md' does not match the 'post' schema: Required metadata key 'author' is missing; Required metadata key 'date' is missing"
+        );
+
+        settings.strict_metadata = false;
+        assert!(
+            check_metadata_schema(&file, &metadata, &settings).is_ok()
+        );
+
+        // Pages of other types are not checked.
+        settings.strict_metadata = true;
+        let (page, _, _) =
+            extract_and_prepare_metadata("---\ntitle: Page\n---\n")
+                .unwrap();
+        assert!(check_metadata_schema(&file, &page, &settings).is_ok());
+    }
+
+    #[test]
+    fn test_compile_site_verification() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site");
+        let compile = |google: &str| {
+            compile_with_options(
+                &dir.path().join("build"),
+                Path::new("examples/content"),
+                &site,
+                Path::new("examples/templates"),
+                None,
+                CompileOptions {
+                    verification: VerificationConfig {
+                        google: Some(google.to_string()),
+                        bing: Some(
+                            "0123456789ABCDEF0123456789ABCDEF"
+                                .to_string(),
+                        ),
+                    },
+                    ..Default::default()
+                },
+            )
+        };
+
+        compile("google0123456789abcdef").unwrap();
+        assert_eq!(
+            fs::read_to_string(
+                site.join("google0123456789abcdef.html")
+            )
+            .unwrap(),
+            "google-site-verification: google0123456789abcdef.html"
+        );
+        assert!(site.join("BingSiteAuth.xml").exists());
+        let index =
+            fs::read_to_string(site.join("index.html")).unwrap();
+        assert!(index.contains("msvalidate.01"), "{}", index);
+
+        let error = compile("not a token").unwrap_err();
+        assert!(format!("{:#}", error)
+            .starts_with("Invalid site verification"));
+    }
+
+    #[test]
+    fn test_page_language() {
+        let metadata = |lang: &str| {
+            let mut metadata = HashMap::new();
+            let _ =
+                metadata.insert("lang".to_string(), lang.to_string());
+            metadata
+        };
+
+        assert_eq!(page_language(&HashMap::new(), "").unwrap(), "en");
+        assert_eq!(page_language(&HashMap::new(), "fr").unwrap(), "fr");
+        assert_eq!(page_language(&metadata(""), "fr").unwrap(), "fr");
+        assert_eq!(
+            page_language(&metadata(" zh-Hant-TW "), "fr").unwrap(),
+            "zh-Hant-TW"
+        );
+        for lang in ["e", "en_GB", "en-", "en\" onclick=\"x"] {
+            assert!(
+                page_language(&metadata(lang), "").is_err(),
+                "{}",
+                lang
+            );
+        }
+    }
+
+    #[test]
+    fn test_process_file_lang() {
+        let mut engine =
+            Engine::new("examples/templates", Duration::from_secs(60));
+        let index =
+            fs::read_to_string("examples/content/index.md").unwrap();
+        let mut compile = |content: String| {
+            process_file(
+                &FileData::new("index.md".to_string(), content),
+                &mut engine,
+                &HtmlGeneratorRenderer,
+                "Navigation",
+                &mut HashMap::new(),
+                Path::new("examples/site"),
+                &PageSettings::default(),
+            )
+            .unwrap()
+            .content
+        };
+
+        let default = compile(index.clone());
+        assert!(!default.contains(r#"dir="rtl""#));
+
+        let arabic =
+            compile(index.replacen("---\n", "---\nlang: ar\n", 1));
+        assert!(arabic.contains(r#"<div lang="ar" dir="rtl">"#));
+    }
+
+    #[test]
+    fn test_process_file_snippets() {
+        let file = FileData::new(
+            "index.md".to_string(),
+            fs::read_to_string("examples/content/index.md").unwrap(),
+        );
+        let mut engine =
+            Engine::new("examples/templates", Duration::from_secs(60));
+        let settings = PageSettings {
+            head_snippet: r#"<meta name="verify" content="abc">"#
+                .to_string(),
+            footer_snippet: r#"<p class="extra">Footer</p>"#
+                .to_string(),
+            ..Default::default()
+        };
+
+        let content = process_file(
+            &file,
+            &mut engine,
+            &HtmlGeneratorRenderer,
+            "Navigation",
+            &mut HashMap::new(),
+            Path::new("examples/site"),
+            &settings,
+        )
+        .unwrap()
+        .content;
+
+        assert!(content
+            .contains(r#"<meta name="verify" content="abc"></head>"#));
+        assert!(
+            content.contains(r#"<p class="extra">Footer</p></body>"#)
+        );
+    }
+
+    #[test]
+    fn test_compile_rejects_unsafe_snippets() {
+        let dir = tempfile::tempdir().unwrap();
+        let compile = |safe_snippets| {
+            compile_with_options(
+                &dir.path().join("build"),
+                Path::new("examples/content"),
+                &dir.path().join("site"),
+                Path::new("examples/templates"),
+                None,
+                CompileOptions {
+                    head_snippet: Some(
+                        r#"<script src="https://example.com/a.js"></script>"#
+                            .to_string(),
+                    ),
+                    safe_snippets,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let error = compile(true).unwrap_err();
+        assert!(error.to_string().contains("head snippet"));
+        assert!(!dir.path().join("build").exists());
+
+        compile(false).unwrap();
+        let index =
+            fs::read_to_string(dir.path().join("site/index.html"))
+                .unwrap();
+        assert!(index.contains("https://example.com/a.js"));
+    }
+
+    #[test]
+    fn test_page_json() {
+        let mut metadata = HashMap::new();
+        for (key, value) in [
+            ("title", "Post"),
+            ("permalink", "https://example.com/post/"),
+            ("tags", "rust, web"),
+        ] {
+            let _ = metadata.insert(key.to_string(), value.to_string());
+        }
+
+        let json = page_json(
+            "Hello **big** world.",
+            &metadata,
+            "<p>Hello big world.</p>",
+            &TagOptions::default(),
+        )
+        .unwrap();
+        let page: PageMetadata = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(page.page.title, "Post");
+        assert_eq!(page.page.permalink, "https://example.com/post/");
+        assert_eq!(page.tags, vec!["rust", "web"]);
+        assert_eq!(page.word_count, 3);
+        assert_eq!(page.excerpt, "Hello big world.");
+        assert_eq!(page.metadata["tags"], "rust, web");
+    }
+
+    #[test]
+    fn test_with_rich_content() {
+        let mut metadata = HashMap::new();
+        for (key, value) in [
+            ("title", "Post"),
+            ("description", "A post"),
+            ("permalink", "https://example.com"),
+            ("item_title", "Post"),
+            ("item_link", "https://example.com/post/"),
+            ("item_guid", "https://example.com/post/"),
+        ] {
+            let _ = metadata.insert(key.to_string(), value.to_string());
+        }
+        let rss =
+            page_rss(&metadata, "<p>Intro</p>", false, true, None)
+                .unwrap();
+
+        let rich = with_rich_content(
+            &rss,
+            "<p>Body with ]]> inside</p>",
+            "https://example.com/a.png?w=1&h=2",
+        );
+        assert!(rich.contains(
+            r#"xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:media="http://search.yahoo.com/mrss/">"#
+        ));
+        assert!(rich.contains(
+            "<content:encoded><![CDATA[<p>Body with ]]]]><![CDATA[> inside</p>]]></content:encoded>"
+        ));
+        assert!(rich.contains(
+            r#"<media:thumbnail url="https://example.com/a.png?w=1&amp;h=2"/></item>"#
+        ));
+
+        let no_item =
+            page_rss(&metadata, "", false, false, None).unwrap();
+        let rich = with_rich_content(&no_item, "<p>Body</p>", "");
+        assert!(rich.contains("xmlns:media"));
+        assert!(!rich.contains("content:encoded"));
+    }
+
+    #[test]
+    fn test_site_listings() {
+        let files = vec![
+            FileData::new(
+                "index.md".to_string(),
+                "---\nname: Example\ntitle: Home\ndescription: A site\npermalink: https://example.com\n---\nHome".to_string(),
+            ),
+            FileData::new(
+                "about.md".to_string(),
+                "---\ntitle: About\n---\nAbout".to_string(),
+            ),
+        ];
 
-    let news_sitemap_config = NewsSiteMapConfig::new(metadata.clone());
-    let news_sitemap_generator =
-        NewsSiteMapGenerator::new(news_sitemap_config);
+        assert!(site_listings(&files, &CompileOptions::default())
+            .is_empty());
 
-    let news_sitemap_content =
-        match news_sitemap_generator.generate_xml() {
-            xml if !xml.is_empty() => xml, // Use the generated XML string
-            _ => {
-                eprintln!("Error generating news sitemap XML.");
-                String::new() // Default to an empty string if XML generation fails
-            }
+        let options = CompileOptions {
+            emit_url_list: true,
+            emit_llms_txt: true,
+            ..Default::default()
         };
+        let listings = site_listings(&files, &options);
+        assert_eq!(
+            listings[0],
+            (
+                URL_LIST_FILE,
+                "https://example.com/about/index.html\nhttps://example.com/index.html\n"
+                    .to_string()
+            )
+        );
+        assert_eq!(listings[1].0, LLMS_TXT_FILE);
+        assert!(listings[1].1.starts_with("# Example\n\n> A site\n"));
+
+        let options = CompileOptions {
+            emit_url_list: true,
+            base_url: "https://www.example.org/".to_string(),
+            url: UrlOptions {
+                pretty_urls: true,
+                ..UrlOptions::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            site_listings(&files, &options)[0].1,
+            "https://example.com/\nhttps://www.example.org/about/\n"
+        );
+    }
 
-    let cname_content = metadata
-        .get("cname")
-        .and_then(|domain| CnameConfig::new(domain, None, None).ok())
-        .map(|config| CnameGenerator::new(config).generate())
-        .unwrap_or_default();
+    #[test]
+    fn test_publishes_feed_item() {
+        assert!(publishes_feed_item(
+            "post.md",
+            "Body",
+            EmptyBodyPolicy::SkipFeedItem
+        ));
+        assert!(publishes_feed_item(
+            "empty.md",
+            " \n\t\n",
+            EmptyBodyPolicy::Warn
+        ));
+        assert!(!publishes_feed_item(
+            "empty.md",
+            " \n\t\n",
+            EmptyBodyPolicy::SkipFeedItem
+        ));
+        assert_eq!(EmptyBodyPolicy::default(), EmptyBodyPolicy::Warn);
+    }
 
-    let humans_content = metadata
-        .get("humans")
-        .map(|humans| {
-            // Try parsing the "humans" string into a HashMap
-            let humans: HashMap<String, String> =
-                serde_json::from_str(humans)
-                    .context("Failed to parse humans metadata")
-                    .unwrap_or_else(|err| {
-                        eprintln!(
-                            "Error parsing humans metadata: {}",
-                            err
-                        );
-                        HashMap::new() // Default to an empty HashMap if parsing fails
-                    });
+    #[test]
+    fn test_with_first_image() {
+        let html =
+            r#"<p><img src="/images/cover.png" alt="Cover"></p>"#;
+        let mut metadata = HashMap::new();
+        for (key, value) in [
+            ("permalink", "https://example.com/post/"),
+            ("og:title", "Post"),
+            ("twitter:card", "summary"),
+        ] {
+            let _ = metadata.insert(key.to_string(), value.to_string());
+        }
 
-            // Generate humans.txt content
-            match HumansConfig::from_metadata(&humans) {
-                Ok(humans_config) => {
-                    HumansGenerator::new(humans_config).generate()
-                }
-                Err(err) => {
-                    eprintln!("Error creating HumansConfig: {}", err);
-                    String::new() // Default to an empty string if creation fails
-                }
-            }
-        })
-        .unwrap_or_default();
+        let tags = with_first_image(
+            generate_metatags(&metadata),
+            &metadata,
+            html,
+        );
+        assert!(tags.og.contains(
+            r#"<meta name="og:image" content="https://example.com/images/cover.png">"#
+        ));
+        assert!(tags.og.contains(r#"name="og:title""#));
+        assert!(tags.twitter.contains(
+            r#"<meta name="twitter:image" content="https://example.com/images/cover.png">"#
+        ));
+
+        let _ = metadata.insert(
+            "base_url".to_string(),
+            "https://cdn.example.com/".to_string(),
+        );
+        let tags = with_first_image(
+            generate_metatags(&metadata),
+            &metadata,
+            html,
+        );
+        assert!(tags
+            .og
+            .contains("https://cdn.example.com/images/cover.png"));
 
-    // let human_options = create_human_data(&metadata);
-    let security_options = create_security_data(&metadata);
-    let sitemap_options = create_site_map_data(&metadata);
-    // let news_sitemap_options = create_news_site_map_data(&metadata);
+        let _ = metadata.insert(
+            "image_url".to_string(),
+            "https://example.com/hero.png".to_string(),
+        );
+        let tags = with_first_image(
+            generate_metatags(&metadata),
+            &metadata,
+            html,
+        );
+        assert!(!tags.og.contains("og:image"));
+    }
 
-    let tags_data = generate_tags(file, &metadata);
+    #[test]
+    fn test_interpolate_file() {
+        let mut options = CompileOptions::default();
+        let _ = options
+            .variables
+            .insert("BUILD_DATE".to_string(), "2025-01-01".to_string());
+        let file = FileData::new(
+            "index.md".to_string(),
+            "---\nlast_build_date: ${BUILD_DATE}\n---\nBuilt ${BUILD_DATE} ${SHA}"
+                .to_string(),
+        );
 
-    update_global_tags_data(global_tags_data, &tags_data);
+        let front = interpolate_file(file.clone(), &options).unwrap();
+        assert_eq!(
+            front.content,
+            "---\nlast_build_date: 2025-01-01\n---\nBuilt ${BUILD_DATE} ${SHA}"
+        );
 
-    let txt_options = create_txt_data(&metadata);
+        options.interpolate_body = true;
+        let body = interpolate_file(file.clone(), &options).unwrap();
+        assert!(body.content.ends_with("Built 2025-01-01 "));
 
-    let txt_data = txt(&txt_options);
-    // let human_data = human(&human_options);
-    let security_data = security(&security_options);
-    let sitemap_data = sitemap(sitemap_options?, site_path);
+        options.strict_variables = true;
+        assert!(interpolate_file(file, &options).is_err());
+    }
 
-    Ok(FileData {
-        cname: cname_content,
-        content,
-        keyword: keywords.join(", "),
-        human: humans_content,
-        manifest: manifest_content,
-        name: file.name.clone(),
-        rss,
-        security: security_data,
-        sitemap: sitemap_data?,
-        sitemap_news: news_sitemap_content,
-        txt: txt_data,
-    })
-}
+    #[test]
+    fn test_sitemap_urls() {
+        assert_eq!(
+            sitemap_urls(
+                "https://example.com/",
+                &[
+                    ("sitemap.xml", "<urlset/>"),
+                    ("news-sitemap.xml", "<urlset/>"),
+                ]
+            ),
+            [
+                "https://example.com/sitemap.xml",
+                "https://example.com/news-sitemap.xml"
+            ]
+        );
+        assert_eq!(
+            sitemap_urls(
+                "https://example.com",
+                &[
+                    ("sitemap.xml", "<urlset/>"),
+                    ("news-sitemap.xml", "")
+                ]
+            ),
+            ["https://example.com/sitemap.xml"]
+        );
+        assert!(sitemap_urls(
+            "example.com",
+            &[("sitemap.xml", "<urlset/>")]
+        )
+        .is_empty());
+    }
 
-/// Updates the global tags data with new tag information.
-///
-/// # Arguments
-///
-/// * `global_tags_data` - Mutable reference to global tags data hashmap.
-/// * `tags_data` - Reference to the tags data hashmap to be merged.
-fn update_global_tags_data(
-    global_tags_data: &mut HashMap<String, Vec<PageData>>,
-    tags_data: &HashMap<String, Vec<HashMap<String, String>>>,
-) {
-    for (tag, pages_data) in tags_data {
-        let page_info: Vec<PageData> = pages_data
-            .iter()
-            .map(|page_data| PageData {
-                title: page_data
-                    .get("title")
-                    .cloned()
-                    .unwrap_or_default(),
-                description: page_data
-                    .get("description")
-                    .cloned()
-                    .unwrap_or_default(),
-                permalink: page_data
-                    .get("permalink")
-                    .cloned()
-                    .unwrap_or_default(),
-                date: page_data
-                    .get("date")
-                    .cloned()
-                    .unwrap_or_default(),
-            })
+    #[test]
+    fn test_hold_scheduled() {
+        // 2024-06-01T12:00:00Z
+        let now =
+            OffsetDateTime::from_unix_timestamp(1_717_243_200).unwrap();
+        let files = vec![
+            FileData::new(
+                "due.md".to_string(),
+                "---\ntitle: Due\npublish_at: 2024-06-01T12:00:00Z\n---\nBody."
+                    .to_string(),
+            ),
+            FileData::new(
+                "later.md".to_string(),
+                "---\ntitle: Later\npublish_at: 2024-06-02\n---\nBody."
+                    .to_string(),
+            ),
+            FileData::new(
+                "plain.md".to_string(),
+                "---\ntitle: Plain\n---\nBody.".to_string(),
+            ),
+        ];
+
+        let names: Vec<String> = hold_scheduled(files.clone(), now)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.name)
             .collect();
+        assert_eq!(names, ["due.md", "plain.md"]);
+
+        let bad = vec![FileData::new(
+            "bad.md".to_string(),
+            "---\ntitle: Bad\npublish_at: soon\n---\nBody.".to_string(),
+        )];
+        let err = hold_scheduled(bad, now).unwrap_err();
+        assert!(err.to_string().contains("'bad.md'"));
+    }
 
-        global_tags_data
-            .entry(tag.clone())
-            .or_default()
-            .extend(page_info);
+    #[test]
+    fn test_apply_filename_date() {
+        let file = apply_filename_date(FileData::new(
+            "2024-01-15-my-post.md".to_string(),
+            "---\ntitle: Post\n---\nBody.".to_string(),
+        ));
+
+        assert_eq!(file.name, "my-post.md");
+        let (metadata, _, _) =
+            extract_and_prepare_metadata(&file.content).unwrap();
+        assert_eq!(
+            metadata["pub_date"],
+            "Mon, 15 Jan 2024 00:00:00 GMT"
+        );
+        assert_eq!(
+            metadata["item_pub_date"],
+            "Mon, 15 Jan 2024 00:00:00 GMT"
+        );
+        assert_eq!(metadata["date"], "2024-01-15");
+        assert_eq!(metadata["title"], "Post");
+        assert!(file.content.ends_with("\n---\nBody."));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rss_gen::data::RssDataField;
+    #[test]
+    fn test_apply_filename_date_keeps_front_matter_dates() {
+        let file = apply_filename_date(FileData::new(
+            "2024-01-15-my-post.md".to_string(),
+            "---\ntitle: Post\npub_date: \"Tue, 20 Feb 2024 15:15:15 GMT\"\n---\nBody."
+                .to_string(),
+        ));
+
+        let (metadata, _, _) =
+            extract_and_prepare_metadata(&file.content).unwrap();
+        assert_eq!(
+            metadata["pub_date"],
+            "Tue, 20 Feb 2024 15:15:15 GMT"
+        );
+        assert_eq!(metadata["date"], "2024-01-15");
+
+        let undated = apply_filename_date(FileData::new(
+            "my-post.md".to_string(),
+            "Body.".to_string(),
+        ));
+        assert_eq!(undated.name, "my-post.md");
+        assert_eq!(undated.content, "Body.");
+    }
 
     #[test]
-    fn test_compile_missing_directories() {
-        let build_dir_path = Path::new("/nonexistent/build");
-        let content_path = Path::new("/nonexistent/content");
-        let site_path = Path::new("/nonexistent/site");
-        let template_path = Path::new("/nonexistent/templates");
+    fn test_prepare_files_rejects_colliding_dated_names() {
+        let options = CompileOptions {
+            date_in_filename: true,
+            preview: true,
+            ..Default::default()
+        };
+        let file = |name: &str| {
+            FileData::new(name.to_string(), "Body.".to_string())
+        };
 
-        let result = compile(
-            build_dir_path,
-            content_path,
-            site_path,
-            template_path,
+        let err = prepare_files(
+            vec![
+                file("2023-05-01-update.md"),
+                file("2024-05-01-update.md"),
+            ],
+            &options,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'2023-05-01-update.md' and '2024-05-01-update.md' both publish as 'update.md' once their dates are removed"
         );
 
-        assert!(result.is_err());
+        let files = prepare_files(
+            vec![
+                file("2023-05-01-update.md"),
+                file("2024-05-01-news.md"),
+            ],
+            &options,
+        )
+        .unwrap();
+        assert_eq!(files[0].name, "update.md");
+        assert_eq!(files[1].name, "news.md");
+    }
+
+    #[test]
+    fn test_apply_filename_date_crlf_and_toml() {
+        for content in [
+            "---\r\ntitle: Post\r\n---\r\nBody.",
+            "+++\ntitle = \"Post\"\ndate = \"2024-02-20\"\n+++\nBody.",
+            "{\n  \"title\": \"Post\"\n}\nBody.",
+        ] {
+            let file = apply_filename_date(FileData::new(
+                "2024-01-15-my-post.md".to_string(),
+                content.to_string(),
+            ));
+
+            let (metadata, _, _) =
+                extract_and_prepare_metadata(&file.content).unwrap();
+            assert_eq!(metadata["title"], "Post", "{}", file.content);
+            assert_eq!(
+                metadata["pub_date"],
+                "Mon, 15 Jan 2024 00:00:00 GMT"
+            );
+            assert_eq!(
+                split_frontmatter_and_body(&file.content).1,
+                "Body."
+            );
+        }
+
+        let file = apply_filename_date(FileData::new(
+            "2024-01-15-my-post.md".to_string(),
+            "+++\ntitle = \"Post\"\ndate = \"2024-02-20\"\n+++\nBody."
+                .to_string(),
+        ));
+        assert!(file.content.starts_with("+++\npub_date = "));
+        assert!(!file.content.contains("date = \"2024-01-15\""));
     }
 
     #[test]
@@ -458,6 +3429,67 @@ fn test_split_frontmatter_and_body_no_separator() {
         assert_eq!(body, "This is just the body.");
     }
 
+    #[test]
+    fn test_split_frontmatter_and_body_toml_and_json() {
+        let (frontmatter, body) = split_frontmatter_and_body(
+            "+++\ntitle = \"Test\"\n+++\nThis is the body.\n",
+        );
+        assert_eq!(frontmatter, "title = \"Test\"");
+        assert_eq!(body, "This is the body.");
+
+        let (frontmatter, body) = split_frontmatter_and_body(
+            "{\n  \"title\": \"Test\",\n  \"a\": {\n  }\n}\r\nBody.",
+        );
+        assert_eq!(
+            frontmatter,
+            "\"title\": \"Test\",\n  \"a\": {\n  }"
+        );
+        assert_eq!(body, "Body.");
+
+        // An unclosed fence is not front matter.
+        let (frontmatter, body) =
+            split_frontmatter_and_body("+++\nBody.");
+        assert!(frontmatter.is_empty());
+        assert_eq!(body, "+++\nBody.");
+    }
+
+    #[test]
+    fn test_process_file_toml_frontmatter() {
+        let file = FileData::new(
+            "toml.md".to_string(),
+            "+++\ntitle = \"Toml\"\ndescription = \"A TOML page\"\nlayout = \"page\"\npermalink = \"https://example.com/toml/\"\n+++\n# Heading\n\nBody text.\n"
+                .to_string(),
+        );
+        let templates = tempfile::tempdir().unwrap();
+        fs::write(
+            templates.path().join("page.html"),
+            "<main>{{content}}</main>",
+        )
+        .unwrap();
+        let mut engine = Engine::new(
+            templates.path().to_str().unwrap(),
+            Duration::from_secs(60),
+        );
+        let settings = PageSettings {
+            generators: GeneratorFlags::none(),
+            ..Default::default()
+        };
+
+        let compiled = process_file(
+            &file,
+            &mut engine,
+            &HtmlGeneratorRenderer,
+            "Navigation",
+            &mut HashMap::new(),
+            Path::new("examples/site"),
+            &settings,
+        )
+        .unwrap();
+        assert!(compiled.content.contains("Body text."));
+        assert!(!compiled.content.contains("+++"));
+        assert!(!compiled.content.contains("title = "));
+    }
+
     #[test]
     fn test_split_frontmatter_and_body_empty_content() {
         let content = "";
@@ -496,6 +3528,51 @@ fn test_split_frontmatter_and_body_multiple_separators() {
         assert_eq!(body, "---\nThis is the body.");
     }
 
+    #[test]
+    fn test_process_file_disabled_generators() {
+        let file = FileData::new(
+            "index.md".to_string(),
+            fs::read_to_string("examples/content/index.md").unwrap(),
+        );
+        let mut engine =
+            Engine::new("examples/templates", Duration::from_secs(60));
+        let mut compile = |generators| {
+            let settings = PageSettings {
+                generators,
+                ..Default::default()
+            };
+            process_file(
+                &file,
+                &mut engine,
+                &HtmlGeneratorRenderer,
+                "Navigation",
+                &mut HashMap::new(),
+                Path::new("examples/site"),
+                &settings,
+            )
+            .unwrap()
+        };
+
+        let enabled = compile(GeneratorFlags::all());
+        assert!(!enabled.cname.is_empty());
+        assert!(!enabled.rss.is_empty());
+
+        let compiled = compile(GeneratorFlags::none());
+        assert!(!compiled.content.is_empty());
+        for output in [
+            &compiled.cname,
+            &compiled.human,
+            &compiled.manifest,
+            &compiled.rss,
+            &compiled.security,
+            &compiled.sitemap,
+            &compiled.sitemap_news,
+            &compiled.txt,
+        ] {
+            assert!(output.is_empty(), "{}", output);
+        }
+    }
+
     #[test]
     fn test_process_file_invalid_metadata() {
         let file = FileData {
@@ -513,10 +3590,11 @@ fn test_process_file_invalid_metadata() {
         let result = process_file(
             &file,
             &mut engine,
-            Path::new("/templates"),
+            &HtmlGeneratorRenderer,
             navigation,
             &mut global_tags_data,
             site_path,
+            &PageSettings::default(),
         );
 
         assert!(result.is_err());
@@ -568,10 +3646,11 @@ fn test_compile_missing_navigation() {
         let result = process_file(
             &file,
             &mut engine,
-            Path::new("/templates"),
+            &HtmlGeneratorRenderer,
             navigation,
             &mut global_tags_data,
             site_path,
+            &PageSettings::default(),
         );
 
         assert!(result.is_err());
@@ -613,6 +3692,72 @@ fn test_metadata_extraction() {
         assert!(frontmatter.contains("author: John Doe"));
     }
 
+    #[test]
+    fn test_validate_feed_metadata() {
+        let mut metadata = HashMap::new();
+        let _ = metadata.insert(
+            "item_pub_date".to_string(),
+            "Mon, 15 Jan 2024 00:00:00 GMT".to_string(),
+        );
+        let _ = metadata.insert("atom_link".to_string(), String::new());
+        assert!(validate_feed_metadata(&metadata, false).is_ok());
+
+        let _ = metadata
+            .insert("pub_date".to_string(), "yesterday".to_string());
+        assert!(matches!(
+            validate_feed_metadata(&metadata, false),
+            Err(MetadataError::Invalid { key, .. }) if key == "pub_date"
+        ));
+
+        let _ = metadata.remove("pub_date");
+        let _ = metadata
+            .insert("item_link".to_string(), "/relative".to_string());
+        assert!(validate_feed_metadata(&metadata, false).is_err());
+        assert!(validate_feed_metadata(&metadata, true).is_ok());
+    }
+
+    #[test]
+    fn test_page_rss_relative_item_link() {
+        let mut metadata = HashMap::new();
+        for (key, value) in [
+            ("title", "Post"),
+            ("description", "A post"),
+            ("permalink", "https://example.com"),
+            ("item_title", "Post"),
+            ("item_link", "https://example.com/post/"),
+        ] {
+            let _ = metadata.insert(key.to_string(), value.to_string());
+        }
+
+        let rss = page_rss(
+            &metadata,
+            "",
+            false,
+            true,
+            Some(&UrlMode::Relative),
+        )
+        .unwrap();
+        assert!(rss.contains("<link>/post/</link>"));
+
+        let base = UrlMode::Absolute("https://preview.test/".into());
+        let _ = metadata
+            .insert("item_link".to_string(), "post/".to_string());
+        let rss =
+            page_rss(&metadata, "", false, true, Some(&base)).unwrap();
+        assert!(rss.contains("<link>https://preview.test/post/</link>"));
+
+        // Without a URL mode, the site `url` is the base.
+        let _ = metadata.insert(
+            "url".to_string(),
+            "https://example.com/".to_string(),
+        );
+        let rss = page_rss(&metadata, "", false, true, None).unwrap();
+        assert!(rss.contains("<link>https://example.com/post/</link>"));
+        let _ = metadata.remove("url");
+        let rss = page_rss(&metadata, "", false, true, None).unwrap();
+        assert!(rss.contains("<link>post/</link>"));
+    }
+
     // Test RSS data generation
     #[test]
     fn test_rss_data_generation() {
@@ -662,6 +3807,110 @@ fn test_multiple_file_compilation() {
         assert!(!navigation.is_empty());
     }
 
+    #[test]
+    fn test_collect_alternates() {
+        let files = vec![
+            FileData {
+                name: "index.md".to_string(),
+                content: "---\ntitle: Home\nmobile: true\n---\nBody."
+                    .to_string(),
+                ..Default::default()
+            },
+            FileData {
+                name: "post.md".to_string(),
+                content: "---\ntitle: Post\namp_url: https://example.com/amp/post/\n---\nBody."
+                    .to_string(),
+                ..Default::default()
+            },
+            FileData {
+                name: "plain.md".to_string(),
+                content: "---\ntitle: Plain\n---\nBody.".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let alternates =
+            collect_sitemap_options(&files).unwrap().alternates;
+        assert_eq!(alternates.len(), 2);
+        assert!(alternates[""].mobile);
+        assert_eq!(
+            alternates["post"].amp_url,
+            "https://example.com/amp/post/"
+        );
+    }
+
+    #[test]
+    fn test_collect_sitemap_options_noindex() {
+        let files = vec![
+            FileData {
+                name: "thanks.md".to_string(),
+                content: "---\ntitle: Thanks\nrobots: [noindex, nofollow]\n---\nBody."
+                    .to_string(),
+                ..Default::default()
+            },
+            FileData {
+                name: "about.md".to_string(),
+                content: "---\ntitle: About\nmeta_robots: \"noarchive\"\nrobots_disallow: /drafts/, /search/\n---\nBody."
+                    .to_string(),
+                ..Default::default()
+            },
+            FileData {
+                name: "index.md".to_string(),
+                content: "---\ntitle: Home\nrobots_disallow: /drafts/\n---\nBody."
+                    .to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let options = collect_sitemap_options(&files).unwrap();
+        assert_eq!(options.excluded.len(), 1);
+        assert!(options.excluded.contains("thanks"));
+        assert_eq!(options.disallowed, vec!["/drafts/", "/search/"]);
+
+        // An unknown directive is only a warning.
+        let unknown = vec![FileData {
+            name: "bad.md".to_string(),
+            content: "---\ntitle: Bad\nrobots: \"hidden\"\n---\nBody."
+                .to_string(),
+            ..Default::default()
+        }];
+        assert!(collect_sitemap_options(&unknown)
+            .unwrap()
+            .excluded
+            .is_empty());
+    }
+
+    #[test]
+    fn test_with_raw_scalars() {
+        let content =
+            "---\nrobots_crawl_delay: 10\ntitle: Post\n---\nBody";
+        let mut metadata = HashMap::new();
+        let _ = metadata
+            .insert("robots_crawl_delay".to_string(), String::new());
+        let _ = metadata.insert("title".to_string(), String::new());
+
+        let restored = with_raw_scalars(
+            content,
+            &metadata,
+            &["robots_crawl_delay"],
+        );
+        assert_eq!(restored["robots_crawl_delay"], "10");
+        assert_eq!(restored["title"], "");
+    }
+
+    #[test]
+    fn test_collect_alternates_relative_amp_url() {
+        let files = vec![FileData {
+            name: "post.md".to_string(),
+            content:
+                "---\ntitle: Post\namp_url: /amp/post/\n---\nBody."
+                    .to_string(),
+            ..Default::default()
+        }];
+
+        assert!(collect_sitemap_options(&files).is_err());
+    }
+
     // Test error handling for invalid templates
     #[test]
     fn test_invalid_template_handling() {
@@ -691,10 +3940,11 @@ fn test_missing_required_metadata() {
         let result = process_file(
             &file,
             &mut engine,
-            Path::new("/templates"),
+            &HtmlGeneratorRenderer,
             navigation,
             &mut global_tags_data,
             site_path,
+            &PageSettings::default(),
         );
 
         assert!(result.is_err());
@@ -56,6 +56,9 @@ pub enum CnameError {
     /// The total domain length exceeds 255 characters.
     #[error("Total domain length exceeds 255 characters: {0}")]
     ExcessiveDomainLength(String),
+    /// Record generation panicked.
+    #[error("Internal error: {0}")]
+    Internal(String),
 }
 
 /// ## CNAME Configuration
@@ -212,6 +215,20 @@ fn validate_domain(domain: &str) -> Result<(), CnameError> {
         Ok(())
     }
 
+    /// Re-validates a configuration that may have been built without
+    /// [`CnameConfig::new`] (e.g. with a struct literal or `Default`).
+    fn validate(&self) -> Result<(), CnameError> {
+        if self.ttl == 0 {
+            return Err(CnameError::InvalidTtl(
+                "TTL must be greater than 0.".to_string(),
+            ));
+        }
+        if self.domain.is_empty() {
+            return Err(CnameError::EmptyDomain);
+        }
+        Self::validate_domain(&self.domain)
+    }
+
     /// Generates a formatted CNAME record using the configuration.
     ///
     /// # Returns
@@ -231,6 +248,36 @@ pub fn generate_custom(&self) -> String {
     }
 }
 
+/// ## Batch Export Options
+///
+/// Controls how [`CnameGenerator::export_batch_with_options`] handles
+/// invalid records, ordering and parallelism.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchExportOptions {
+    /// Write the valid records even if some configurations fail, and
+    /// record the failures in a `<path>.errors` sidecar file.
+    pub write_partial: bool,
+    /// Sort records by domain, ignoring ASCII case, instead of keeping
+    /// the input order. Records for the same domain are ordered by their
+    /// text, so the output does not depend on the input order.
+    pub sort: bool,
+    /// The number of threads generating records. `None` uses the global
+    /// thread pool; `Some(n)` runs the batch on a dedicated pool of `n`
+    /// threads (`Some(0)` lets the pool pick, as `None` does globally).
+    pub threads: Option<usize>,
+}
+
+/// ## Batch Export Summary
+///
+/// The outcome of a batch export.
+#[derive(Debug, Default)]
+pub struct BatchExportSummary {
+    /// The number of records written.
+    pub written: usize,
+    /// The domains that failed, with their errors, in input order.
+    pub errors: Vec<(String, CnameError)>,
+}
+
 /// ## CNAME Generator
 ///
 /// Facilitates the generation of CNAME records using the provided configuration.
@@ -284,6 +331,8 @@ pub fn export_to_file(&self, path: &str) -> std::io::Result<()> {
     /// # Returns
     ///
     /// A vector of results, where each result is either the generated CNAME record or a `CnameError`.
+    /// Each configuration is re-validated, so configurations built without
+    /// [`CnameConfig::new`] are reported as errors rather than emitted.
     ///
     /// # Example
     ///
@@ -305,13 +354,105 @@ pub fn batch_generate(
 
         configs
             .into_par_iter() // Use parallel iterator for efficiency
+            .map(|config| Self::generate_validated(&config))
+            .collect()
+    }
+
+    /// Generates multiple CNAME records in parallel like
+    /// [`CnameGenerator::batch_generate`], isolating panics.
+    ///
+    /// A panic while generating one record is caught and reported as
+    /// [`CnameError::Internal`] for that item, so one bad input cannot abort
+    /// the rest of the batch.
+    ///
+    /// # Arguments
+    ///
+    /// - `configs`: A vector of `CnameConfig` instances.
+    ///
+    /// # Returns
+    ///
+    /// A vector of results in input order, where each result is either the
+    /// generated CNAME record or a `CnameError`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use staticdatagen::generators::cname::{CnameConfig, CnameGenerator};
+    ///
+    /// let configs = vec![
+    ///     CnameConfig::new("example.com", None, None).unwrap(),
+    ///     CnameConfig::default(), // Invalid: empty domain
+    /// ];
+    ///
+    /// let records = CnameGenerator::batch_generate_resilient(configs);
+    /// assert!(records[0].is_ok());
+    /// assert!(records[1].is_err());
+    /// ```
+    pub fn batch_generate_resilient(
+        configs: Vec<CnameConfig>,
+    ) -> Vec<Result<String, CnameError>> {
+        Self::batch_generate_resilient_with(
+            configs,
+            Self::generate_validated,
+        )
+    }
+
+    /// Generates multiple CNAME records in parallel with a custom record
+    /// generator, isolating panics like
+    /// [`CnameGenerator::batch_generate_resilient`].
+    ///
+    /// # Arguments
+    ///
+    /// - `configs`: A vector of `CnameConfig` instances.
+    /// - `generate`: The function producing the record for one configuration.
+    ///
+    /// # Returns
+    ///
+    /// A vector of results in input order. A panic in `generate` becomes a
+    /// [`CnameError::Internal`] naming the domain and the panic message.
+    pub fn batch_generate_resilient_with<F>(
+        configs: Vec<CnameConfig>,
+        generate: F,
+    ) -> Vec<Result<String, CnameError>>
+    where
+        F: Fn(&CnameConfig) -> Result<String, CnameError> + Sync,
+    {
+        use rayon::prelude::*;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        configs
+            .into_par_iter()
             .map(|config| {
-                let generator = CnameGenerator::new(config);
-                Ok(generator.generate())
+                catch_unwind(AssertUnwindSafe(|| generate(&config)))
+                    .unwrap_or_else(|payload| {
+                        let message = payload
+                            .downcast_ref::<&str>()
+                            .map(|message| message.to_string())
+                            .or_else(|| {
+                                payload
+                                    .downcast_ref::<String>()
+                                    .cloned()
+                            })
+                            .unwrap_or_else(|| {
+                                "unknown panic".to_string()
+                            });
+                        Err(CnameError::Internal(format!(
+                            "Record generation panicked for '{}': {}",
+                            config.domain, message
+                        )))
+                    })
             })
             .collect()
     }
 
+    /// Validates a configuration and generates its record.
+    fn generate_validated(
+        config: &CnameConfig,
+    ) -> Result<String, CnameError> {
+        config.validate()?;
+        Ok(config.generate_custom())
+    }
+
     /// Exports multiple CNAME records to a file in batch using parallel processing.
     ///
     /// # Arguments
@@ -324,6 +465,12 @@ pub fn batch_generate(
     ///
     /// A `Result` indicating success or failure. If any CNAME generation fails, the function will return the first encountered error.
     ///
+    /// Records are written in input order, whatever order the parallel
+    /// generation finishes in, so re-exporting the same configurations
+    /// gives a byte-identical file. Use
+    /// [`CnameGenerator::export_batch_with_options`] with
+    /// [`BatchExportOptions::sort`] to order them by domain instead.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -353,28 +500,141 @@ pub fn export_batch_to_file(
         path: &str,
         delimiter: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let results = Self::batch_generate(configs);
+        let _ = Self::export_batch_with_options(
+            configs,
+            path,
+            delimiter,
+            BatchExportOptions::default(),
+        )?;
+        Ok(())
+    }
 
-        // Separate successful results from errors
-        let (successes, errors): (Vec<_>, Vec<_>) =
-            results.into_iter().partition(Result::is_ok);
+    /// Exports multiple CNAME records to a file, with control over partial
+    /// writes and ordering.
+    ///
+    /// Records are generated in parallel, on
+    /// [`BatchExportOptions::threads`] threads if set. By default, the
+    /// first invalid configuration aborts the export and nothing is
+    /// written. With [`BatchExportOptions::write_partial`], the valid
+    /// records are written and each failure is listed as
+    /// `domain<TAB>error` in a sidecar file named `<path>.errors`, which
+    /// an export without failures removes. Records and failures keep the
+    /// input order; with [`BatchExportOptions::sort`], records are ordered
+    /// by domain regardless of input order.
+    ///
+    /// # Arguments
+    ///
+    /// - `configs`: A vector of `CnameConfig` instances.
+    /// - `path`: The path to the file where the records will be saved.
+    /// - `delimiter`: A string delimiter used to separate the records in the file.
+    /// - `options`: The [`BatchExportOptions`] to apply.
+    ///
+    /// # Returns
+    ///
+    /// A [`BatchExportSummary`] with the number of records written and the
+    /// failed domains, or an error if writing fails (or, without
+    /// `write_partial`, if any configuration is invalid).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use staticdatagen::generators::cname::{BatchExportOptions, CnameConfig, CnameGenerator};
+    /// use std::fs;
+    ///
+    /// let configs = vec![
+    ///     CnameConfig::new("b.example.com", None, None).unwrap(),
+    ///     CnameConfig::default(), // Invalid: empty domain
+    ///     CnameConfig::new("a.example.com", None, None).unwrap(),
+    /// ];
+    /// let options = BatchExportOptions {
+    ///     write_partial: true,
+    ///     sort: true,
+    ///     threads: Some(2),
+    /// };
+    ///
+    /// let summary = CnameGenerator::export_batch_with_options(configs, "CNAME.batch", "\n", options).unwrap();
+    /// assert_eq!(summary.written, 2);
+    /// assert_eq!(summary.errors.len(), 1);
+    /// assert!(fs::read_to_string("CNAME.batch").unwrap().starts_with("a.example.com"));
+    ///
+    /// fs::remove_file("CNAME.batch").unwrap();
+    /// fs::remove_file("CNAME.batch.errors").unwrap();
+    /// ```
+    pub fn export_batch_with_options(
+        configs: Vec<CnameConfig>,
+        path: &str,
+        delimiter: &str,
+        options: BatchExportOptions,
+    ) -> Result<BatchExportSummary, Box<dyn std::error::Error>> {
+        let domains: Vec<String> = configs
+            .iter()
+            .map(|config| config.domain.clone())
+            .collect();
+        let results = match options.threads {
+            Some(threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()?
+                .install(|| Self::batch_generate(configs)),
+            None => Self::batch_generate(configs),
+        };
+
+        let mut records = Vec::with_capacity(results.len());
+        let mut errors = Vec::new();
+        for (domain, result) in domains.into_iter().zip(results) {
+            match result {
+                Ok(record) => records.push((domain, record)),
+                Err(err) if options.write_partial => {
+                    errors.push((domain, err))
+                }
+                Err(err) => return Err(Box::new(err)),
+            }
+        }
 
-        // If there are errors, return the first encountered error
-        if let Some(Err(err)) = errors.into_iter().next() {
-            return Err(Box::new(err));
+        if options.sort {
+            records.sort_by(
+                |(a_domain, a_record), (b_domain, b_record)| {
+                    a_domain
+                        .to_ascii_lowercase()
+                        .cmp(&b_domain.to_ascii_lowercase())
+                        .then_with(|| a_domain.cmp(b_domain))
+                        .then_with(|| a_record.cmp(b_record))
+                },
+            );
         }
 
         // Concatenate all successful records with the specified delimiter
-        let content = successes
-            .into_iter()
-            .filter_map(Result::ok)
+        let content = records
+            .iter()
+            .map(|(_, record)| record.as_str())
             .collect::<Vec<_>>()
             .join(delimiter);
 
         // Write the combined content to the specified file
         std::fs::write(path, content)?;
 
-        Ok(())
+        // A sidecar left by an earlier export no longer applies.
+        let sidecar = format!("{}.errors", path);
+        if errors.is_empty() {
+            match std::fs::remove_file(&sidecar) {
+                Err(err)
+                    if err.kind() != std::io::ErrorKind::NotFound =>
+                {
+                    return Err(Box::new(err));
+                }
+                _ => {}
+            }
+        } else {
+            let report = errors
+                .iter()
+                .map(|(domain, err)| format!("{}\t{}\n", domain, err))
+                .collect::<String>();
+            std::fs::write(sidecar, report)?;
+        }
+
+        Ok(BatchExportSummary {
+            written: records.len(),
+            errors,
+        })
     }
 
     /// Creates a CNAME record from metadata provided as a key-value map.
@@ -428,6 +688,7 @@ pub fn from_metadata(
 mod tests {
     use super::*;
     use std::error::Error;
+    use std::path::Path;
 
     #[test]
     fn test_valid_domain_names() {
@@ -1106,6 +1367,211 @@ fn test_batch_generate_error_propagation() {
         assert_eq!(results.len(), 2);
     }
 
+    #[test]
+    fn test_batch_generate_resilient_catches_panics() {
+        let configs: Vec<CnameConfig> = ["a.com", "panic.com", "b.com"]
+            .iter()
+            .map(|d| CnameConfig::new(*d, None, None).unwrap())
+            .collect();
+
+        let results = CnameGenerator::batch_generate_resilient_with(
+            configs,
+            |c| {
+                if c.domain == "panic.com" {
+                    panic!("bad record");
+                }
+                Ok(c.generate_custom())
+            },
+        );
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].as_ref().unwrap(),
+            "a.com 3600 IN CNAME www.a.com"
+        );
+        assert!(matches!(
+            &results[1],
+            Err(CnameError::Internal(message))
+                if message.contains("panic.com") && message.contains("bad record")
+        ));
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn test_batch_generate_resilient_validates() {
+        let results = CnameGenerator::batch_generate_resilient(vec![
+            CnameConfig {
+                domain: String::new(),
+                ttl: 3600,
+                format: None,
+            },
+        ]);
+        assert!(matches!(results[0], Err(CnameError::EmptyDomain)));
+    }
+
+    #[test]
+    fn test_export_batch_rejects_invalid_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CNAME");
+        let path = path.to_str().unwrap();
+
+        let result = CnameGenerator::export_batch_to_file(
+            vec![
+                CnameConfig::new("example.com", None, None).unwrap(),
+                CnameConfig::default(),
+            ],
+            path,
+            "\n",
+        );
+
+        assert!(result.is_err());
+        assert!(!Path::new(path).exists());
+    }
+
+    #[test]
+    fn test_export_batch_with_partial_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CNAME");
+        let path = path.to_str().unwrap();
+
+        let invalid_ttl = CnameConfig {
+            domain: "ttl.example.com".to_string(),
+            ttl: 0,
+            format: None,
+        };
+        let summary = CnameGenerator::export_batch_with_options(
+            vec![
+                CnameConfig::new("example.com", None, None).unwrap(),
+                invalid_ttl,
+                CnameConfig::new("other.com", None, None).unwrap(),
+            ],
+            path,
+            "\n",
+            BatchExportOptions {
+                write_partial: true,
+                sort: false,
+                threads: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(summary.written, 2);
+        assert_eq!(summary.errors.len(), 1);
+        assert_eq!(summary.errors[0].0, "ttl.example.com");
+        assert!(matches!(
+            summary.errors[0].1,
+            CnameError::InvalidTtl(_)
+        ));
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(
+            content,
+            "example.com 3600 IN CNAME www.example.com\nother.com 3600 IN CNAME www.other.com"
+        );
+        let sidecar =
+            std::fs::read_to_string(format!("{}.errors", path))
+                .unwrap();
+        assert!(sidecar.starts_with("ttl.example.com\tInvalid TTL"));
+    }
+
+    #[test]
+    fn test_export_batch_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CNAME");
+        let path = path.to_str().unwrap();
+        std::fs::write(format!("{}.errors", path), "stale\tError\n")
+            .unwrap();
+
+        let summary = CnameGenerator::export_batch_with_options(
+            ["c.com", "a.com", "b.com"]
+                .iter()
+                .map(|d| CnameConfig::new(*d, None, None).unwrap())
+                .collect(),
+            path,
+            ",",
+            BatchExportOptions {
+                write_partial: false,
+                sort: true,
+                threads: Some(2),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(summary.written, 3);
+        assert!(summary.errors.is_empty());
+        let domains: Vec<String> = std::fs::read_to_string(path)
+            .unwrap()
+            .split(',')
+            .map(|record| record.split(' ').next().unwrap().to_string())
+            .collect();
+        assert_eq!(domains, ["a.com", "b.com", "c.com"]);
+        assert!(!Path::new(&format!("{}.errors", path)).exists());
+    }
+
+    #[test]
+    fn test_export_batch_order_is_deterministic() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CNAME");
+        let path = path.to_str().unwrap();
+        let configs = |order: &[usize]| -> Vec<CnameConfig> {
+            let all = [
+                ("b.com", 300),
+                ("A.com", 600),
+                ("b.com", 60),
+                ("a.com", 900),
+            ];
+            order
+                .iter()
+                .map(|&i| CnameConfig {
+                    domain: all[i].0.to_string(),
+                    ttl: all[i].1,
+                    format: None,
+                })
+                .collect()
+        };
+        let export = |order: &[usize], sort: bool| {
+            let _ = CnameGenerator::export_batch_with_options(
+                configs(order),
+                path,
+                "\n",
+                BatchExportOptions {
+                    write_partial: false,
+                    sort,
+                    threads: None,
+                },
+            )
+            .unwrap();
+            std::fs::read_to_string(path).unwrap()
+        };
+
+        let unsorted = export(&[2, 0, 3, 1], false);
+        let domains: Vec<&str> = unsorted
+            .lines()
+            .map(|line| line.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(domains, ["b.com", "b.com", "a.com", "A.com"]);
+        assert!(unsorted.starts_with("b.com 60 "));
+
+        let sorted = export(&[0, 1, 2, 3], true);
+        assert_eq!(sorted, export(&[3, 2, 1, 0], true));
+        let records: Vec<(&str, &str)> = sorted
+            .lines()
+            .map(|line| {
+                let mut parts = line.split(' ');
+                (parts.next().unwrap(), parts.next().unwrap())
+            })
+            .collect();
+        assert_eq!(
+            records,
+            [
+                ("A.com", "600"),
+                ("a.com", "900"),
+                ("b.com", "300"),
+                ("b.com", "60"),
+            ]
+        );
+    }
+
     #[test]
     fn test_export_batch_to_file_io_error() {
         let config =
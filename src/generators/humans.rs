@@ -27,11 +27,20 @@
 //! assert!(humans_content.contains("John Doe"));
 //! ```
 
-use dtt::dtt_parse;
+use crate::utilities::datetime::parse_flexible;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use url::Url;
 
+/// The conventional column at which long fields are wrapped.
+pub const DEFAULT_WRAP_WIDTH: usize = 78;
+
+/// The indentation of each field line.
+const FIELD_INDENT: &str = "    ";
+
+/// The indentation of wrapped continuation lines.
+pub const CONTINUATION_INDENT: &str = "        ";
+
 /// Maximum length for text fields
 const MAX_TEXT_LENGTH: usize = 100;
 
@@ -238,6 +247,9 @@ pub fn from_metadata(
 pub struct HumansGenerator {
     /// Configuration for generating `humans.txt`.
     pub config: HumansConfig,
+    /// The column at which long values are wrapped, or `None` to keep
+    /// every field on one line (the default).
+    pub wrap_width: Option<usize>,
 }
 
 impl HumansGenerator {
@@ -249,7 +261,40 @@ impl HumansGenerator {
     /// # Returns
     /// A new `HumansGenerator` instance.
     pub fn new(config: HumansConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            wrap_width: None,
+        }
+    }
+
+    /// Wraps fields longer than `width` columns onto continuation lines
+    /// indented by [`CONTINUATION_INDENT`].
+    ///
+    /// Lines break at spaces or after commas, never inside a word, so a
+    /// single word longer than `width` is kept whole. Use
+    /// [`DEFAULT_WRAP_WIDTH`] for the conventional 78 columns.
+    ///
+    /// # Example
+    /// ```rust
+    /// use staticdatagen::generators::humans::{
+    ///     HumansConfig, HumansGenerator, DEFAULT_WRAP_WIDTH,
+    /// };
+    ///
+    /// let config = HumansConfig::builder()
+    ///     .author("Jane Doe")
+    ///     .thanks("Alice, Bob, Carol, Dave, Erin, Frank, Grace, Heidi, Ivan, Judy, Mallory")
+    ///     .build()
+    ///     .unwrap();
+    /// let content = HumansGenerator::new(config)
+    ///     .with_wrap_width(DEFAULT_WRAP_WIDTH)
+    ///     .generate();
+    ///
+    /// assert!(content.lines().all(|line| line.len() <= DEFAULT_WRAP_WIDTH));
+    /// assert!(content.contains("\n        Mallory"));
+    /// ```
+    pub fn with_wrap_width(mut self, width: usize) -> Self {
+        self.wrap_width = Some(width);
+        self
     }
 
     /// Generates the content of a `humans.txt` file.
@@ -258,73 +303,80 @@ pub fn new(config: HumansConfig) -> Self {
     /// A string containing the formatted `humans.txt` content.
     pub fn generate(&self) -> String {
         let mut content = String::new();
+        let config = &self.config;
 
         // TEAM Section
         content.push_str("/* TEAM */\n");
-        if !self.config.author.is_empty() {
-            content.push_str(&format!(
-                "    Name: {}\n",
-                self.config.author
-            ));
-        }
-        if !self.config.author_website.is_empty() {
-            content.push_str(&format!(
-                "    Website: {}\n",
-                self.config.author_website
-            ));
-        }
-        if !self.config.author_twitter.is_empty() {
-            content.push_str(&format!(
-                "    Twitter: {}\n",
-                self.config.author_twitter
-            ));
-        }
-        if !self.config.author_location.is_empty() {
-            content.push_str(&format!(
-                "    Location: {}\n",
-                self.config.author_location
-            ));
-        }
+        self.push_field(&mut content, "Name", &config.author);
+        self.push_field(
+            &mut content,
+            "Website",
+            &config.author_website,
+        );
+        self.push_field(
+            &mut content,
+            "Twitter",
+            &config.author_twitter,
+        );
+        self.push_field(
+            &mut content,
+            "Location",
+            &config.author_location,
+        );
 
         // THANKS Section
         content.push_str("\n/* THANKS */\n");
-        if !self.config.thanks.is_empty() {
-            content.push_str(&format!(
-                "    Thanks: {}\n",
-                self.config.thanks
-            ));
-        }
+        self.push_field(&mut content, "Thanks", &config.thanks);
 
         // SITE Section
         content.push_str("\n/* SITE */\n");
-        if !self.config.site_last_updated.is_empty() {
-            content.push_str(&format!(
-                "    Last update: {}\n",
-                self.config.site_last_updated
-            ));
-        }
-        if !self.config.site_standards.is_empty() {
-            content.push_str(&format!(
-                "    Standards: {}\n",
-                self.config.site_standards
-            ));
-        }
-        if !self.config.site_components.is_empty() {
-            content.push_str(&format!(
-                "    Components: {}\n",
-                self.config.site_components
-            ));
-        }
-        if !self.config.site_software.is_empty() {
-            content.push_str(&format!(
-                "    Software: {}\n",
-                self.config.site_software
-            ));
-        }
+        self.push_field(
+            &mut content,
+            "Last update",
+            &config.site_last_updated,
+        );
+        self.push_field(
+            &mut content,
+            "Standards",
+            &config.site_standards,
+        );
+        self.push_field(
+            &mut content,
+            "Components",
+            &config.site_components,
+        );
+        self.push_field(
+            &mut content,
+            "Software",
+            &config.site_software,
+        );
 
         content
     }
 
+    /// Appends a `Label: value` line, wrapped if enabled. Empty values are
+    /// skipped.
+    fn push_field(
+        &self,
+        content: &mut String,
+        label: &str,
+        value: &str,
+    ) {
+        if value.is_empty() {
+            return;
+        }
+        match self.wrap_width {
+            Some(width) => {
+                content.push_str(&wrap_field(label, value, width));
+            }
+            None => content.push_str(&format!(
+                "{}{}: {}",
+                FIELD_INDENT, label, value
+            )),
+        }
+        content.push('\n');
+    }
+
     /// Exports the generated `humans.txt` content to a file.
     ///
     /// # Arguments
@@ -337,6 +389,36 @@ pub fn export_to_file(&self, path: &str) -> std::io::Result<()> {
     }
 }
 
+/// Wraps the `Label: value` field at `width` columns, breaking at spaces
+/// or after commas. The first word always stays on the label line, and a
+/// word longer than `width` is never split.
+fn wrap_field(label: &str, value: &str, width: usize) -> String {
+    let mut wrapped = String::new();
+    let mut line = format!("{}{}:", FIELD_INDENT, label);
+    let mut line_is_empty = true;
+
+    for word in value.split_whitespace() {
+        for (index, piece) in word.split_inclusive(',').enumerate() {
+            let separator = if index == 0 { " " } else { "" };
+            let length = line.chars().count()
+                + separator.len()
+                + piece.chars().count();
+            if length > width && !line_is_empty {
+                wrapped.push_str(&line);
+                wrapped.push('\n');
+                line = CONTINUATION_INDENT.to_string();
+            } else {
+                line.push_str(separator);
+            }
+            line.push_str(piece);
+            line_is_empty = false;
+        }
+    }
+
+    wrapped.push_str(&line);
+    wrapped
+}
+
 /// Sanitizes general text content
 fn sanitize_text(text: &str) -> String {
     text.trim()
@@ -378,14 +460,14 @@ fn sanitize_twitter_handle(handle: &str) -> String {
     }
 }
 
-/// Sanitizes and validates a date string (YYYY-MM-DD format)
+/// Sanitizes and validates a date string (see [`parse_flexible`])
 fn sanitize_date(date: &str) -> Result<String, HumansError> {
     let date = date.trim();
     if date.is_empty() {
         return Ok(String::new());
     }
 
-    match dtt_parse!(date) {
+    match parse_flexible(date) {
         Ok(_) => Ok(date.to_string()),
         Err(_) => Err(HumansError::InvalidDate(date.to_string())),
     }
@@ -682,4 +764,54 @@ fn test_generator_debug() {
         let generator = HumansGenerator::new(config);
         assert!(!format!("{:?}", generator).is_empty());
     }
+
+    fn long_config() -> HumansConfig {
+        HumansConfig {
+            author: "Jane Doe".to_string(),
+            thanks: "Alice Anderson, Bob Brown, Carol Clark, Dave Davis, Erin Evans, Frank Foster".to_string(),
+            site_components: "Rust,Serde,Tokio,Rayon,Pulldown-cmark,Minify-html,Quick-xml,Tera".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_wrapping_disabled_by_default() {
+        let content = HumansGenerator::new(long_config()).generate();
+
+        assert!(content.contains(
+            "    Thanks: Alice Anderson, Bob Brown, Carol Clark, Dave Davis, Erin Evans, Frank Foster\n"
+        ));
+    }
+
+    #[test]
+    fn test_wrap_long_fields() {
+        let content = HumansGenerator::new(long_config())
+            .with_wrap_width(40)
+            .generate();
+
+        assert!(content.contains(
+            "    Thanks: Alice Anderson, Bob Brown,\n        Carol Clark, Dave Davis, Erin\n        Evans, Frank Foster\n"
+        ));
+        assert!(content.contains(
+            "    Components: Rust,Serde,Tokio,Rayon,\n        Pulldown-cmark,Minify-html,\n        Quick-xml,Tera\n"
+        ));
+        assert!(content.contains("    Name: Jane Doe\n"));
+        assert!(content.lines().all(|line| line.len() <= 40));
+    }
+
+    #[test]
+    fn test_wrap_never_splits_words() {
+        let config = HumansConfig {
+            author: "Jane Doe".to_string(),
+            thanks: "Supercalifragilisticexpialidocious friends"
+                .to_string(),
+            ..Default::default()
+        };
+        let content =
+            HumansGenerator::new(config).with_wrap_width(20).generate();
+
+        assert!(content.contains(
+            "    Thanks: Supercalifragilisticexpialidocious\n        friends\n"
+        ));
+    }
 }
@@ -34,6 +34,9 @@
 //! ```
 
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
 use thiserror::Error;
 
 /// Constants defining default values for manifest fields.
@@ -56,6 +59,14 @@ pub mod defaults {
     pub const ICON_PURPOSE: &str = "any maskable";
 }
 
+/// The display modes accepted by the `display` member.
+pub const DISPLAY_MODES: [&str; 4] =
+    ["fullscreen", "standalone", "minimal-ui", "browser"];
+
+/// The display modes accepted only in `display_override`.
+pub const OVERRIDE_DISPLAY_MODES: [&str; 2] =
+    ["window-controls-overlay", "tabbed"];
+
 /// Errors that can occur during manifest generation and validation.
 #[derive(Debug, Error)]
 pub enum ManifestError {
@@ -75,9 +86,30 @@ pub enum ManifestError {
     #[error("Invalid display mode: {0}")]
     InvalidDisplayMode(String),
 
+    /// A `display_override` entry is unknown or repeated.
+    #[error("Invalid display_override entry: {0}")]
+    InvalidDisplayOverride(String),
+
+    /// `display_override` does not end with one of [`DISPLAY_MODES`].
+    #[error("display_override has no fallback display mode: {0}")]
+    MissingDisplayFallback(String),
+
+    /// A maskable icon lacks explicit `sizes` or an author check that its
+    /// content fits the safe zone.
+    #[error("Maskable icon needs review: {0}")]
+    MaskableIcon(String),
+
+    /// An icon file's dimensions differ from its declared `sizes`.
+    #[error("Icon size mismatch: {0}")]
+    IconSizeMismatch(String),
+
     /// JSON serialization failed.
     #[error("Failed to serialize manifest: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    /// Writing a manifest file failed.
+    #[error("Failed to write manifest: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 /// Configuration for manifest generation.
@@ -88,6 +120,7 @@ pub struct ManifestConfig {
     description: Option<String>,
     start_url: String,
     display: String,
+    display_override: Vec<String>,
     background_color: String,
     theme_color: Option<String>,
     icons: Vec<IconConfig>,
@@ -102,6 +135,7 @@ pub struct IconConfig {
     sizes: String,
     icon_type: Option<String>,
     purpose: Option<String>,
+    safe_zone_verified: bool,
 }
 
 impl IconConfig {
@@ -120,6 +154,7 @@ pub fn new(
             sizes: sizes.into(),
             icon_type: Some(defaults::ICON_TYPE.to_string()),
             purpose: Some(defaults::ICON_PURPOSE.to_string()),
+            safe_zone_verified: false,
         }
     }
 
@@ -134,6 +169,116 @@ pub fn purpose(mut self, purpose: impl Into<String>) -> Self {
         self.purpose = Some(purpose.into());
         self
     }
+
+    /// Records that the author checked the content of a maskable icon
+    /// fits the central 80% safe zone, so it is not clipped when masked.
+    pub fn safe_zone_verified(mut self, verified: bool) -> Self {
+        self.safe_zone_verified = verified;
+        self
+    }
+
+    /// Returns `true` if the icon's purpose includes `maskable`.
+    pub fn is_maskable(&self) -> bool {
+        self.purpose.as_deref().map_or(false, |purpose| {
+            purpose.split_whitespace().any(|p| p == "maskable")
+        })
+    }
+
+    /// Checks that the PNG file at `path` has one of the declared
+    /// `sizes`, reading only its `IHDR` header. Files that are not PNG
+    /// are not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManifestError::IconSizeMismatch`] if the dimensions
+    /// differ, or [`ManifestError::Io`] if the file cannot be read.
+    pub fn check_png_size(
+        &self,
+        path: &Path,
+    ) -> Result<(), ManifestError> {
+        let mut header = [0_u8; 24];
+        match File::open(path)?.read_exact(&mut header) {
+            Err(err)
+                if err.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                return Ok(());
+            }
+            result => result?,
+        }
+        if header[..8] != PNG_SIGNATURE || &header[12..16] != b"IHDR" {
+            return Ok(());
+        }
+
+        let dimension = |at: usize| {
+            u32::from_be_bytes([
+                header[at],
+                header[at + 1],
+                header[at + 2],
+                header[at + 3],
+            ])
+        };
+        let actual = format!("{}x{}", dimension(16), dimension(20));
+        if self
+            .sizes
+            .split_whitespace()
+            .any(|size| size.eq_ignore_ascii_case(&actual))
+        {
+            Ok(())
+        } else {
+            Err(ManifestError::IconSizeMismatch(format!(
+                "'{}' is {} but declares sizes '{}'",
+                self.src, actual, self.sizes
+            )))
+        }
+    }
+}
+
+/// The eight bytes every PNG file starts with.
+const PNG_SIGNATURE: [u8; 8] = *b"\x89PNG\r\n\x1a\n";
+
+/// Checks the maskable icons of a manifest, returning a warning for each
+/// icon without explicit `sizes` (`any` does not count) or not marked
+/// [`IconConfig::safe_zone_verified`].
+///
+/// Maskable icons are cropped to a circle or other shape on Android, so
+/// content outside the central 80% may be clipped. The crate cannot
+/// inspect the image itself, so the author vouches for it.
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::generators::manifest::{validate_icons, IconConfig};
+///
+/// let icon = IconConfig::new("/icon.png", "512x512").purpose("maskable");
+/// assert_eq!(validate_icons(&[icon.clone()]).len(), 1);
+/// assert!(validate_icons(&[icon.safe_zone_verified(true)]).is_empty());
+/// ```
+pub fn validate_icons(icons: &[IconConfig]) -> Vec<ManifestError> {
+    let mut warnings = Vec::new();
+    for icon in icons.iter().filter(|icon| icon.is_maskable()) {
+        let explicit_sizes = !icon.sizes.trim().is_empty()
+            && icon.sizes.split_whitespace().all(|size| {
+                size.split_once(['x', 'X']).map_or(false, |(w, h)| {
+                    !w.is_empty()
+                        && !h.is_empty()
+                        && w.chars().all(|c| c.is_ascii_digit())
+                        && h.chars().all(|c| c.is_ascii_digit())
+                })
+            });
+        if !explicit_sizes {
+            warnings.push(ManifestError::MaskableIcon(format!(
+                "'{}' declares sizes '{}'; give explicit WIDTHxHEIGHT sizes",
+                icon.src, icon.sizes
+            )));
+        }
+        if !icon.safe_zone_verified {
+            warnings.push(ManifestError::MaskableIcon(format!(
+                "'{}' is not marked safe_zone_verified; keep its content within the central 80%",
+                icon.src
+            )));
+        }
+    }
+    warnings
 }
 
 impl ManifestConfig {
@@ -143,6 +288,10 @@ pub fn builder() -> ManifestConfigBuilder {
     }
 
     /// Creates a manifest configuration from metadata.
+    ///
+    /// The `icon` key adds an icon, whose purpose is read from
+    /// `icon_purpose` and whose safe zone is vouched for by
+    /// `icon_safe_zone_verified: true` (see [`validate_icons`]).
     pub fn from_metadata(
         metadata: &HashMap<String, String>,
     ) -> Result<Self, ManifestError> {
@@ -164,9 +313,28 @@ pub fn from_metadata(
         {
             builder = builder.background_color(background_color);
         }
+        if let Some(modes) = metadata.get("display_override") {
+            builder = builder.display_override(
+                modes
+                    .trim()
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|mode| mode.trim().trim_matches('"'))
+                    .filter(|mode| !mode.is_empty()),
+            );
+        }
         if let Some(icon) = metadata.get("icon") {
-            builder = builder
-                .add_icon(IconConfig::new(icon, defaults::ICON_SIZE));
+            let mut icon = IconConfig::new(icon, defaults::ICON_SIZE)
+                .safe_zone_verified(
+                    metadata
+                        .get("icon_safe_zone_verified")
+                        .map_or(false, |value| value.trim() == "true"),
+                );
+            if let Some(purpose) = metadata.get("icon_purpose") {
+                icon = icon.purpose(purpose.trim());
+            }
+            builder = builder.add_icon(icon);
         }
 
         builder.build()
@@ -181,6 +349,7 @@ pub struct ManifestConfigBuilder {
     description: Option<String>,
     start_url: Option<String>,
     display: Option<String>,
+    display_override: Vec<String>,
     background_color: Option<String>,
     theme_color: Option<String>,
     icons: Vec<IconConfig>,
@@ -219,6 +388,20 @@ pub fn display(mut self, display: impl Into<String>) -> Self {
         self
     }
 
+    /// Sets the ordered `display_override` list, most preferred first.
+    ///
+    /// Entries are checked by [`validate_display_override`] when the
+    /// configuration is built.
+    pub fn display_override<I, S>(mut self, modes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.display_override =
+            modes.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Sets the background color.
     pub fn background_color(
         mut self,
@@ -264,6 +447,15 @@ pub fn build(self) -> Result<ManifestConfig, ManifestError> {
             ));
         }
 
+        for warning in
+            validate_display_override(&self.display_override)?
+        {
+            log::warn!("{}", warning);
+        }
+        for warning in validate_icons(&self.icons) {
+            log::warn!("{}", warning);
+        }
+
         Ok(ManifestConfig {
             name: sanitize_text(&name, 45),
             short_name: self.short_name.map(|n| sanitize_text(&n, 12)),
@@ -276,6 +468,7 @@ pub fn build(self) -> Result<ManifestConfig, ManifestError> {
             display: self
                 .display
                 .unwrap_or_else(|| defaults::DISPLAY.to_string()),
+            display_override: self.display_override,
             background_color: self
                 .background_color
                 .map(sanitize_color)
@@ -315,7 +508,7 @@ pub fn from_metadata(
 
     /// Generates the manifest JSON.
     pub fn generate(&self) -> Result<String, ManifestError> {
-        let manifest = serde_json::json!({
+        let mut manifest = serde_json::json!({
             "name": self.config.name,
             "short_name": self.config.short_name,
             "description": self.config.description,
@@ -338,14 +531,160 @@ pub fn generate(&self) -> Result<String, ManifestError> {
             "orientation": self.config.orientation,
             "scope": self.config.scope,
         });
+        if !self.config.display_override.is_empty() {
+            manifest["display_override"] =
+                serde_json::json!(self.config.display_override);
+        }
 
         serde_json::to_string_pretty(&manifest)
             .map_err(ManifestError::SerializationError)
     }
+
+    /// Generates multiple manifests in batch using parallel processing.
+    ///
+    /// # Returns
+    ///
+    /// A vector of results in input order, where each result is either
+    /// the manifest JSON or the `ManifestError` for that configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use staticdatagen::generators::manifest::{ManifestConfig, ManifestGenerator};
+    ///
+    /// let configs = ["en", "fr"]
+    ///     .iter()
+    ///     .map(|lang| ManifestConfig::builder().name(format!("App ({})", lang)).build())
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// let manifests = ManifestGenerator::batch_generate(configs);
+    /// assert_eq!(manifests.len(), 2);
+    /// assert!(manifests[1].as_ref().unwrap().contains("App (fr)"));
+    /// # Ok::<(), staticdatagen::generators::manifest::ManifestError>(())
+    /// ```
+    pub fn batch_generate(
+        configs: Vec<ManifestConfig>,
+    ) -> Vec<Result<String, ManifestError>> {
+        use rayon::prelude::*;
+
+        configs
+            .into_par_iter()
+            .map(|config| Self::new(config).generate())
+            .collect()
+    }
+
+    /// Generates multiple manifests in parallel and writes each to its
+    /// own path.
+    ///
+    /// Each manifest is written as soon as it is generated, so the batch
+    /// is never held in memory at once. A failure only affects its own
+    /// item.
+    ///
+    /// # Returns
+    ///
+    /// A vector of results in input order, one per `(config, path)` pair.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use staticdatagen::generators::manifest::{ManifestConfig, ManifestGenerator};
+    ///
+    /// let dir = tempfile::tempdir()?;
+    /// let batch = vec![
+    ///     (ManifestConfig::builder().name("App").build()?, dir.path().join("en.json")),
+    ///     (ManifestConfig::builder().name("App").build()?, dir.path().join("missing/fr.json")),
+    /// ];
+    ///
+    /// let results = ManifestGenerator::export_batch(batch);
+    /// assert!(results[0].is_ok());
+    /// assert!(results[1].is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn export_batch<P>(
+        batch: Vec<(ManifestConfig, P)>,
+    ) -> Vec<Result<(), ManifestError>>
+    where
+        P: AsRef<Path> + Send,
+    {
+        use rayon::prelude::*;
+
+        batch
+            .into_par_iter()
+            .map(|(config, path)| {
+                let manifest = Self::new(config).generate()?;
+                std::fs::write(path, manifest)?;
+                Ok(())
+            })
+            .collect()
+    }
 }
 
 // Helper functions
 
+/// Validates an ordered `display_override` list.
+///
+/// Every entry must be one of [`DISPLAY_MODES`] or
+/// [`OVERRIDE_DISPLAY_MODES`] and appear once. Browsers that do not
+/// support a mode skip to the next one, so the list should end with one
+/// of [`DISPLAY_MODES`]; a list that does not is still accepted, and the
+/// problem is returned as a warning.
+///
+/// # Returns
+///
+/// The warnings for a usable list, or an error for an invalid entry.
+///
+/// # Examples
+///
+/// ```rust
+/// use staticdatagen::generators::manifest::{
+///     validate_display_override, ManifestError,
+/// };
+///
+/// let modes = ["window-controls-overlay", "standalone"].map(String::from);
+/// assert!(validate_display_override(&modes).unwrap().is_empty());
+///
+/// let modes = ["tabbed".to_string()];
+/// assert!(matches!(
+///     validate_display_override(&modes).unwrap()[..],
+///     [ManifestError::MissingDisplayFallback(_)]
+/// ));
+///
+/// let modes = ["kiosk".to_string()];
+/// assert!(validate_display_override(&modes).is_err());
+/// ```
+pub fn validate_display_override(
+    modes: &[String],
+) -> Result<Vec<ManifestError>, ManifestError> {
+    for (index, mode) in modes.iter().enumerate() {
+        if !DISPLAY_MODES.contains(&mode.as_str())
+            && !OVERRIDE_DISPLAY_MODES.contains(&mode.as_str())
+        {
+            return Err(ManifestError::InvalidDisplayOverride(
+                format!("unknown display mode '{}'", mode),
+            ));
+        }
+        if modes[..index].contains(mode) {
+            return Err(ManifestError::InvalidDisplayOverride(
+                format!("'{}' is listed more than once", mode),
+            ));
+        }
+    }
+
+    let mut warnings = Vec::new();
+    if let Some(last) = modes.last() {
+        if !DISPLAY_MODES.contains(&last.as_str()) {
+            warnings.push(ManifestError::MissingDisplayFallback(
+                format!(
+                    "'{}' is last; end the list with one of {}",
+                    last,
+                    DISPLAY_MODES.join(", ")
+                ),
+            ));
+        }
+    }
+    Ok(warnings)
+}
+
 /// Sanitizes a text string by removing control characters and limiting its length.
 ///
 /// # Parameters
@@ -445,6 +784,134 @@ fn test_complete_manifest() {
         assert!(json.contains("#000000"));
     }
 
+    #[test]
+    fn test_display_override() {
+        let config = ManifestConfig::builder()
+            .name("Test App")
+            .display_override(["window-controls-overlay", "minimal-ui"])
+            .build()
+            .unwrap();
+        let json = ManifestGenerator::new(config).generate().unwrap();
+        let manifest: serde_json::Value =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            manifest["display_override"],
+            serde_json::json!([
+                "window-controls-overlay",
+                "minimal-ui"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_display_override_omitted_when_empty() {
+        let config =
+            ManifestConfig::builder().name("Test App").build().unwrap();
+        let json = ManifestGenerator::new(config).generate().unwrap();
+
+        assert!(!json.contains("display_override"));
+    }
+
+    #[test]
+    fn test_display_override_invalid_entries() {
+        for modes in [
+            vec!["standalone", "kiosk"],
+            vec!["tabbed", "tabbed", "browser"],
+        ] {
+            let result = ManifestConfig::builder()
+                .name("Test App")
+                .display_override(modes)
+                .build();
+            assert!(matches!(
+                result,
+                Err(ManifestError::InvalidDisplayOverride(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_display_override_fallback_warning() {
+        let modes =
+            ["standalone", "window-controls-overlay"].map(String::from);
+        let warnings = validate_display_override(&modes).unwrap();
+        assert!(matches!(
+            warnings[..],
+            [ManifestError::MissingDisplayFallback(_)]
+        ));
+
+        // A missing fallback is a warning, not an error.
+        assert!(ManifestConfig::builder()
+            .name("Test App")
+            .display_override(modes)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_display_override_from_metadata() {
+        let mut metadata = HashMap::new();
+        _ = metadata.insert("name".to_string(), "Test App".to_string());
+        _ = metadata.insert(
+            "display_override".to_string(),
+            "[tabbed, standalone]".to_string(),
+        );
+
+        let json = ManifestGenerator::from_metadata(&metadata).unwrap();
+        assert!(json.contains("\"tabbed\""));
+
+        _ = metadata.insert(
+            "display_override".to_string(),
+            "kiosk".to_string(),
+        );
+        assert!(ManifestConfig::from_metadata(&metadata).is_err());
+    }
+
+    #[test]
+    fn test_batch_generate_preserves_order() {
+        let configs: Vec<ManifestConfig> = (0..50)
+            .map(|i| {
+                ManifestConfig::builder()
+                    .name(format!("App {}", i))
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+
+        let manifests = ManifestGenerator::batch_generate(configs);
+        assert_eq!(manifests.len(), 50);
+        for (i, manifest) in manifests.iter().enumerate() {
+            let json: serde_json::Value =
+                serde_json::from_str(manifest.as_ref().unwrap())
+                    .unwrap();
+            assert_eq!(json["name"], format!("App {}", i));
+        }
+        assert!(
+            ManifestGenerator::batch_generate(Vec::new()).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_export_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = |name: &str| {
+            ManifestConfig::builder().name(name).build().unwrap()
+        };
+        let batch = vec![
+            (config("English"), dir.path().join("en.json")),
+            (config("Missing"), dir.path().join("missing/xx.json")),
+            (config("Français"), dir.path().join("fr.json")),
+        ];
+
+        let results = ManifestGenerator::export_batch(batch);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(ManifestError::Io(_))));
+        assert!(results[2].is_ok());
+        assert!(std::fs::read_to_string(dir.path().join("fr.json"))
+            .unwrap()
+            .contains("Français"));
+    }
+
     #[test]
     fn test_invalid_manifest() {
         let result = ManifestConfig::builder().name("").build();
@@ -551,6 +1018,61 @@ fn test_sanitize_color_validation() {
         );
     }
 
+    #[test]
+    fn test_validate_icons() {
+        let icons = [
+            IconConfig::new("/any.png", "192x192").purpose("any"),
+            IconConfig::new("/ok.png", "192x192 512X512")
+                .purpose("maskable")
+                .safe_zone_verified(true),
+            IconConfig::new("/sized.png", "any")
+                .purpose("any maskable")
+                .safe_zone_verified(true),
+            IconConfig::new("/unchecked.png", "512x512"),
+        ];
+
+        let warnings: Vec<String> = validate_icons(&icons)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(
+            warnings[0].contains("'/sized.png' declares sizes 'any'")
+        );
+        assert!(warnings[1].contains("'/unchecked.png' is not marked"));
+    }
+
+    #[test]
+    fn test_check_png_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend_from_slice(&[0, 0, 0, 13]);
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&192_u32.to_be_bytes());
+        png.extend_from_slice(&192_u32.to_be_bytes());
+        let path = dir.path().join("icon.png");
+        std::fs::write(&path, &png).unwrap();
+
+        let icon = IconConfig::new("/icon.png", "192x192 512x512");
+        assert!(icon.check_png_size(&path).is_ok());
+        assert!(matches!(
+            IconConfig::new("/icon.png", "512x512")
+                .check_png_size(&path),
+            Err(ManifestError::IconSizeMismatch(_))
+        ));
+
+        let svg = dir.path().join("icon.svg");
+        std::fs::write(&svg, "<svg></svg>").unwrap();
+        assert!(icon.check_png_size(&svg).is_ok());
+        let truncated = dir.path().join("truncated.png");
+        std::fs::write(&truncated, &png[..20]).unwrap();
+        assert!(icon.check_png_size(&truncated).is_ok());
+        assert!(matches!(
+            icon.check_png_size(&dir.path().join("missing.png")),
+            Err(ManifestError::Io(_))
+        ));
+    }
+
     #[test]
     fn test_icon_config_methods() {
         let icon = IconConfig::new("/icon.svg", "512x512");
@@ -612,6 +1134,23 @@ fn test_manifest_from_metadata_complete() {
         assert_eq!(config.background_color, "#ffffff");
         assert!(!config.icons.is_empty());
         assert_eq!(config.icons[0].src, "/icon.svg");
+        assert!(!validate_icons(&config.icons).is_empty());
+
+        _ = metadata
+            .insert("icon_purpose".to_string(), "any".to_string());
+        let config = ManifestConfig::from_metadata(&metadata).unwrap();
+        assert_eq!(config.icons[0].purpose.as_deref(), Some("any"));
+        assert!(validate_icons(&config.icons).is_empty());
+
+        _ = metadata
+            .insert("icon_purpose".to_string(), "maskable".to_string());
+        _ = metadata.insert(
+            "icon_safe_zone_verified".to_string(),
+            "true".to_string(),
+        );
+        let config = ManifestConfig::from_metadata(&metadata).unwrap();
+        assert!(config.icons[0].safe_zone_verified);
+        assert!(validate_icons(&config.icons).is_empty());
     }
 
     #[test]
@@ -13,5 +13,14 @@
 /// The `news_sitemap` module contains the news sitemap generator.
 pub mod news_sitemap;
 
+/// The `security` module contains the security.txt and PGP key generator.
+pub mod security;
+
 /// The `tags` module contains the tags generator.
 pub mod tags;
+
+/// The `verification` module contains the search console verification generator.
+pub mod verification;
+
+/// The `zonefile` module contains the DNS zone file generator.
+pub mod zonefile;
@@ -31,8 +31,11 @@
 //! ```
 
 use crate::models::data::NewsData;
+use crate::utilities::datetime::{
+    parse_flexible, to_rfc3339, DateTimeError,
+};
 use std::collections::HashMap;
-use time::{format_description, OffsetDateTime};
+use time::OffsetDateTime;
 use xml::writer::events::XmlEvent;
 use xml::writer::EmitterConfig;
 
@@ -40,12 +43,24 @@
 #[derive(Debug, Clone)]
 pub struct NewsSiteMapConfig {
     metadata: HashMap<String, String>,
+    strict: bool,
 }
 
 impl NewsSiteMapConfig {
     /// Creates a new `NewsSiteMapConfig` with the provided metadata.
     pub fn new(metadata: HashMap<String, String>) -> Self {
-        Self { metadata }
+        Self {
+            metadata,
+            strict: false,
+        }
+    }
+
+    /// Sets whether a missing or unparseable `news_publication_date` is
+    /// an error for [`NewsSiteMapConfig::try_to_news_data`] rather than
+    /// being replaced by the current time.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     /// Retrieves a sanitized value from the metadata or a default.
@@ -56,17 +71,43 @@ fn get_sanitized(&self, key: &str, default: &str) -> String {
     }
 
     /// Formats and retrieves the publication date from the metadata.
-    fn get_formatted_date(&self) -> String {
+    fn get_formatted_date(
+        &self,
+        strict: bool,
+    ) -> Result<String, DateTimeError> {
         format_publication_date(
             self.metadata
                 .get("news_publication_date")
                 .unwrap_or(&String::new()),
+            strict,
         )
     }
 
     /// Builds a `NewsData` object based on the metadata.
+    ///
+    /// A missing or unparseable publication date is replaced by the
+    /// current time, with a warning, even in strict mode.
     pub fn to_news_data(&self) -> NewsData {
-        NewsData {
+        self.news_data(false).unwrap_or_default()
+    }
+
+    /// Builds a `NewsData` object based on the metadata, honouring
+    /// [`NewsSiteMapConfig::strict`].
+    ///
+    /// # Errors
+    ///
+    /// In strict mode, returns a [`DateTimeError`] if
+    /// `news_publication_date` is missing or cannot be parsed.
+    pub fn try_to_news_data(&self) -> Result<NewsData, DateTimeError> {
+        self.news_data(self.strict)
+    }
+
+    /// Builds a `NewsData` object, failing on a bad date if `strict`.
+    fn news_data(
+        &self,
+        strict: bool,
+    ) -> Result<NewsData, DateTimeError> {
+        Ok(NewsData {
             news_genres: validate_genres(
                 self.metadata
                     .get("news_genres")
@@ -90,14 +131,14 @@ pub fn to_news_data(&self) -> NewsData {
             news_loc: validate_url(
                 self.metadata.get("news_loc").unwrap_or(&String::new()),
             ),
-            news_publication_date: self.get_formatted_date(),
+            news_publication_date: self.get_formatted_date(strict)?,
             news_publication_name: self.get_sanitized(
                 "news_publication_name",
                 "Unnamed Publication",
             ),
             news_title: self
                 .get_sanitized("news_title", "Untitled Article"),
-        }
+        })
     }
 }
 
@@ -115,85 +156,111 @@ pub fn new(config: NewsSiteMapConfig) -> Self {
     }
 
     /// Generates the news sitemap XML.
+    ///
+    /// A missing or unparseable publication date is replaced by the
+    /// current time; see [`NewsSiteMapGenerator::try_generate_xml`].
     pub fn generate_xml(&self) -> String {
-        let news_data = self.config.to_news_data();
-        //eprintln!("NewsData: {:?}", news_data);
-        let mut output = Vec::new();
-        let mut writer = EmitterConfig::new()
-            .perform_indent(true)
-            .create_writer(&mut output);
-
-        writer
-        .write(XmlEvent::start_element("urlset")
-            .attr("xmlns", "http://www.sitemaps.org/schemas/sitemap/0.9")
-            .attr("xmlns:news", "http://www.google.com/schemas/sitemap-news/0.9"))
+        write_xml(&self.config.to_news_data())
+    }
+
+    /// Generates the news sitemap XML, honouring
+    /// [`NewsSiteMapConfig::strict`].
+    ///
+    /// # Errors
+    ///
+    /// In strict mode, returns a [`DateTimeError`] if the publication
+    /// date is missing or cannot be parsed.
+    pub fn try_generate_xml(&self) -> Result<String, DateTimeError> {
+        Ok(write_xml(&self.config.try_to_news_data()?))
+    }
+}
+
+/// Writes the news sitemap XML for `news_data`.
+fn write_xml(news_data: &NewsData) -> String {
+    let mut output = Vec::new();
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(&mut output);
+
+    writer
+        .write(
+            XmlEvent::start_element("urlset")
+                .attr(
+                    "xmlns",
+                    "http://www.sitemaps.org/schemas/sitemap/0.9",
+                )
+                .attr(
+                    "xmlns:news",
+                    "http://www.google.com/schemas/sitemap-news/0.9",
+                ),
+        )
         .unwrap();
 
-        writer.write(XmlEvent::start_element("url")).unwrap();
-        writer.write(XmlEvent::start_element("loc")).unwrap();
-        writer
-            .write(XmlEvent::characters(&news_data.news_loc))
-            .unwrap();
-        writer.write(XmlEvent::end_element()).unwrap(); // End <loc>
+    writer.write(XmlEvent::start_element("url")).unwrap();
+    writer.write(XmlEvent::start_element("loc")).unwrap();
+    writer
+        .write(XmlEvent::characters(&news_data.news_loc))
+        .unwrap();
+    writer.write(XmlEvent::end_element()).unwrap(); // End <loc>
 
-        writer.write(XmlEvent::start_element("news:news")).unwrap();
-        writer
-            .write(XmlEvent::start_element("news:publication"))
-            .unwrap();
-        writer.write(XmlEvent::start_element("news:name")).unwrap();
-        writer
-            .write(XmlEvent::characters(
-                &news_data.news_publication_name,
-            ))
-            .unwrap();
-        writer.write(XmlEvent::end_element()).unwrap(); // End <news:name>
-        writer
-            .write(XmlEvent::start_element("news:language"))
-            .unwrap();
-        writer
-            .write(XmlEvent::characters(&news_data.news_language))
-            .unwrap();
-        writer.write(XmlEvent::end_element()).unwrap(); // End <news:language>
-        writer.write(XmlEvent::end_element()).unwrap(); // End <news:publication>
+    writer.write(XmlEvent::start_element("news:news")).unwrap();
+    writer
+        .write(XmlEvent::start_element("news:publication"))
+        .unwrap();
+    writer.write(XmlEvent::start_element("news:name")).unwrap();
+    writer
+        .write(XmlEvent::characters(&news_data.news_publication_name))
+        .unwrap();
+    writer.write(XmlEvent::end_element()).unwrap(); // End <news:name>
+    writer
+        .write(XmlEvent::start_element("news:language"))
+        .unwrap();
+    writer
+        .write(XmlEvent::characters(&news_data.news_language))
+        .unwrap();
+    writer.write(XmlEvent::end_element()).unwrap(); // End <news:language>
+    writer.write(XmlEvent::end_element()).unwrap(); // End <news:publication>
 
-        writer
-            .write(XmlEvent::start_element("news:publication_date"))
-            .unwrap();
-        writer
-            .write(XmlEvent::characters(
-                &news_data.news_publication_date,
-            ))
-            .unwrap(); // Debug here if needed
-        writer.write(XmlEvent::end_element()).unwrap(); // End <news:publication_date>
-
-        writer.write(XmlEvent::start_element("news:title")).unwrap();
-        writer
-            .write(XmlEvent::characters(&news_data.news_title))
-            .unwrap();
-        writer.write(XmlEvent::end_element()).unwrap(); // End <news:title>
+    writer
+        .write(XmlEvent::start_element("news:publication_date"))
+        .unwrap();
+    writer
+        .write(XmlEvent::characters(&news_data.news_publication_date))
+        .unwrap(); // Debug here if needed
+    writer.write(XmlEvent::end_element()).unwrap(); // End <news:publication_date>
+
+    writer.write(XmlEvent::start_element("news:title")).unwrap();
+    writer
+        .write(XmlEvent::characters(&news_data.news_title))
+        .unwrap();
+    writer.write(XmlEvent::end_element()).unwrap(); // End <news:title>
 
-        writer.write(XmlEvent::end_element()).unwrap(); // End <news:news>
-        writer.write(XmlEvent::end_element()).unwrap(); // End <url>
-        writer.write(XmlEvent::end_element()).unwrap(); // End <urlset>
+    writer.write(XmlEvent::end_element()).unwrap(); // End <news:news>
+    writer.write(XmlEvent::end_element()).unwrap(); // End <url>
+    writer.write(XmlEvent::end_element()).unwrap(); // End <urlset>
 
-        String::from_utf8(output).unwrap_or_default()
-    }
+    String::from_utf8(output).unwrap_or_default()
 }
 
-/// Formats publication dates from "Tue, 20 Feb 2024 15:15:15 GMT" to ISO 8601.
-fn format_publication_date(input: &str) -> String {
-    match OffsetDateTime::parse(
-        input,
-        &format_description::well_known::Rfc2822,
-    ) {
-        Ok(parsed) => parsed
-            .format(&format_description::well_known::Rfc3339)
-            .unwrap_or_default(),
+/// Formats publication dates such as "Tue, 20 Feb 2024 15:15:15 GMT" or
+/// "2024-02-20" (see [`parse_flexible`]) as RFC 3339. Calendar dates
+/// without a time are taken as midnight UTC.
+///
+/// A date that cannot be parsed is an error if `strict` is set, and is
+/// otherwise replaced by the current time with a warning.
+fn format_publication_date(
+    input: &str,
+    strict: bool,
+) -> Result<String, DateTimeError> {
+    match parse_flexible(input) {
+        Ok(parsed) => Ok(to_rfc3339(&parsed)),
+        Err(e) if strict => Err(e),
         Err(e) => {
-            eprintln!("Parsing failed: {}. Using fallback.", e);
-            OffsetDateTime::now_utc()
-                .format(&format_description::well_known::Rfc3339)
-                .unwrap_or_default()
+            log::warn!(
+                "{}; using the current time as news publication date",
+                e
+            );
+            Ok(to_rfc3339(&OffsetDateTime::now_utc()))
         }
     }
 }
@@ -267,6 +334,7 @@ fn sanitize_text(text: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use time::format_description;
 
     #[test]
     fn test_news_sitemap_generation() {
@@ -315,7 +383,7 @@ fn test_date_parsing_debug() {
     fn test_format_publication_date() {
         let input = "Tue, 20 Feb 2024 15:15:15 GMT";
 
-        let result = format_publication_date(input);
+        let result = format_publication_date(input, false).unwrap();
 
         // Assert that the result is either "2024-02-20T15:15:15Z" or "2024-02-20T15:15:15+00:00"
         assert!(
@@ -323,12 +391,61 @@ fn test_format_publication_date() {
                 || result == "2024-02-20T15:15:15+00:00"
         );
 
+        // Other accepted layouts are normalized too
+        assert_eq!(
+            format_publication_date("2024-02-20", true).unwrap(),
+            "2024-02-20T00:00:00Z"
+        );
+
         // Invalid formats should fall back
-        let fallback = format_publication_date("Invalid Date");
+        let fallback =
+            format_publication_date("Invalid Date", false).unwrap();
         let fallback_now = OffsetDateTime::now_utc()
             .format(&format_description::well_known::Rfc3339)
             .unwrap();
         assert!(fallback.starts_with(&fallback_now[..10])); // Compare only the date part
+
+        // Strict mode reports the bad date instead
+        assert_eq!(
+            format_publication_date("Invalid Date", true),
+            Err(DateTimeError::Invalid("Invalid Date".to_string()))
+        );
+        assert_eq!(
+            format_publication_date("", true),
+            Err(DateTimeError::Empty)
+        );
+    }
+
+    #[test]
+    fn test_try_generate_xml_strict() {
+        let mut metadata = HashMap::new();
+        let _ = metadata.insert(
+            "news_publication_date".to_string(),
+            "next Tuesday".to_string(),
+        );
+
+        let lenient = NewsSiteMapGenerator::new(
+            NewsSiteMapConfig::new(metadata.clone()),
+        );
+        assert!(lenient.try_generate_xml().is_ok());
+
+        let strict = NewsSiteMapGenerator::new(
+            NewsSiteMapConfig::new(metadata.clone()).strict(true),
+        );
+        assert!(strict.try_generate_xml().is_err());
+        assert!(!strict.generate_xml().is_empty());
+
+        let _ = metadata.insert(
+            "news_publication_date".to_string(),
+            "2024-02-20".to_string(),
+        );
+        let strict = NewsSiteMapGenerator::new(
+            NewsSiteMapConfig::new(metadata).strict(true),
+        );
+        assert!(strict
+            .try_generate_xml()
+            .unwrap()
+            .contains("2024-02-20T00:00:00Z"));
     }
 
     #[test]
@@ -424,15 +541,21 @@ fn test_get_formatted_date() {
         let config = NewsSiteMapConfig::new(metadata);
 
         // Valid date
-        assert_eq!(config.get_formatted_date(), "2024-02-20T15:15:15Z");
+        assert_eq!(
+            config.get_formatted_date(false).unwrap(),
+            "2024-02-20T15:15:15Z"
+        );
 
         // Missing date
         let empty_config = NewsSiteMapConfig::new(HashMap::new());
-        assert!(empty_config.get_formatted_date().starts_with(
-            &OffsetDateTime::now_utc()
-                .format(&format_description::well_known::Rfc3339)
-                .unwrap()[..10]
-        ));
+        assert!(empty_config
+            .get_formatted_date(false)
+            .unwrap()
+            .starts_with(
+                &OffsetDateTime::now_utc()
+                    .format(&format_description::well_known::Rfc3339)
+                    .unwrap()[..10]
+            ));
     }
 
     #[test]
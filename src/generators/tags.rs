@@ -17,6 +17,8 @@
 //! - **Flexible creation of `TagsData` structures from metadata.**
 //! - **Generating accessible HTML content that lists tags with their associated pages.**
 //! - **Writing the resulting HTML into an existing `index.html` file.**
+//! - **Ranking related pages by the tags they share.**
+//! - **Rendering a tag cloud weighted by page count.**
 //!
 //! ## Example Usage
 //! ```rust
@@ -71,12 +73,17 @@
 
 use crate::models::data::{FileData, PageData, TagsData};
 use crate::utilities::directory::to_title_case;
+use crate::utilities::html::html_escape;
+use crate::utilities::metadata::{meta_str, parse_date};
+use metadata_gen::extract_and_prepare_metadata;
 use std::{
-    collections::HashMap,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     fs,
     io::{self, Read, Write},
     path::Path,
 };
+use time::OffsetDateTime;
 
 /// ## Tag Sanitization
 ///
@@ -92,32 +99,129 @@ pub fn sanitize_tag(tag: &str) -> String {
     tag.chars().filter(|c| c.is_alphanumeric()).collect()
 }
 
+/// Front matter keys read for tags by default.
+pub const DEFAULT_TAG_KEYS: [&str; 2] = ["tags", "categories"];
+
+/// ## Tag Delimiter
+///
+/// Separates the tags written in a single front matter value. Inline
+/// YAML lists such as `[rust, web]` are always split on commas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagDelimiter {
+    /// `rust, web` (the default).
+    Comma,
+    /// `rust web`; any run of whitespace separates tags.
+    Whitespace,
+    /// Any other separator character, such as `;` or `|`.
+    Char(char),
+}
+
+impl Default for TagDelimiter {
+    fn default() -> Self {
+        TagDelimiter::Comma
+    }
+}
+
+/// ## Tag Options
+///
+/// Controls which front matter keys hold tags and how their values are
+/// split.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagOptions {
+    /// The keys read for tags, in order. Tags found under several keys
+    /// are only counted once.
+    pub keys: Vec<String>,
+    /// The separator used between tags in a value.
+    pub delimiter: TagDelimiter,
+}
+
+impl Default for TagOptions {
+    /// Reads [`DEFAULT_TAG_KEYS`], separated by commas.
+    fn default() -> Self {
+        TagOptions {
+            keys: DEFAULT_TAG_KEYS
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            delimiter: TagDelimiter::default(),
+        }
+    }
+}
+
+/// ## Parse Tags
+///
+/// Reads the tags of a page from `metadata` using `options`, trimming
+/// whitespace and surrounding quotes and dropping empty or duplicate
+/// entries. Tags are returned as written, before sanitization.
+pub fn parse_tags(
+    metadata: &HashMap<String, String>,
+    options: &TagOptions,
+) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for value in options.keys.iter().filter_map(|key| metadata.get(key))
+    {
+        let value = value.trim();
+        let (value, delimiter) = match value
+            .strip_prefix('[')
+            .and_then(|list| list.strip_suffix(']'))
+        {
+            Some(list) => (list, TagDelimiter::Comma),
+            None => (value, options.delimiter),
+        };
+
+        let parts: Vec<&str> = match delimiter {
+            TagDelimiter::Comma => value.split(',').collect(),
+            TagDelimiter::Whitespace => {
+                value.split_whitespace().collect()
+            }
+            TagDelimiter::Char(c) => value.split(c).collect(),
+        };
+        for part in parts {
+            let tag = part
+                .trim()
+                .trim_matches(|c| c == '"' || c == '\'')
+                .trim();
+            if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+
+    tags
+}
+
 /// ## Generate Tags
 ///
 /// Creates a mapping of sanitized tags to associated metadata, based on the
-/// contents of a [`FileData`] and a metadata map.
+/// contents of a [`FileData`] and a metadata map, reading tags with the
+/// default [`TagOptions`].
 pub fn generate_tags(
     file: &FileData,
     metadata: &HashMap<String, String>,
+) -> HashMap<String, Vec<HashMap<String, String>>> {
+    generate_tags_with_options(file, metadata, &TagOptions::default())
+}
+
+/// ## Generate Tags With Options
+///
+/// Like [`generate_tags`], reading tags with the given [`TagOptions`].
+pub fn generate_tags_with_options(
+    file: &FileData,
+    metadata: &HashMap<String, String>,
+    options: &TagOptions,
 ) -> HashMap<String, Vec<HashMap<String, String>>> {
     let mut keywords_data_map = HashMap::new();
 
     let file_content = &file.content;
-    let default_tags = String::new();
-    let target_tags: Vec<&str> = metadata
-        .get("tags")
-        .unwrap_or(&default_tags)
-        .split(',')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .collect();
+    let target_tags = parse_tags(metadata, options);
 
     if target_tags.is_empty() {
         println!("No tags found in metadata.");
         return keywords_data_map;
     }
 
-    for tag in target_tags {
+    for tag in &target_tags {
         let sanitized_tag = sanitize_tag(tag);
 
         // Skip tags we do not want to include
@@ -194,6 +298,9 @@ pub fn create_tags_data(
 /// Creates an HTML snippet showing each tag (with a post count) and the list
 /// of pages under that tag. Uses `<section>` elements to group each tag, with
 /// `<h3>` headings for clarity. Links have unique `aria-label`s.
+///
+/// Tags are listed alphabetically and pages are ordered with
+/// [`sort_tag_pages`], so the output is byte-stable across builds.
 pub fn generate_tags_html(
     global_tags_data: &HashMap<String, Vec<PageData>>,
 ) -> String {
@@ -218,7 +325,8 @@ pub fn generate_tags_html(
 
     // For each tag, create a <section> with a heading and a <ul>
     for (tag_index, key) in keys.iter().enumerate() {
-        let pages = &global_tags_data[*key];
+        let mut pages = global_tags_data[*key].clone();
+        sort_tag_pages(&mut pages);
         let count = pages.len();
         let heading_label =
             format!("Tag: {}, {} Posts", to_title_case(key), count);
@@ -228,8 +336,8 @@ pub fn generate_tags_html(
         // <h3> heading for the tag
         html_content.push_str(&format!(
             "<h3 class=\"{}\" id=\"h3-{}\" tabindex=\"0\" role=\"heading\" aria-level=\"3\" aria-label=\"{}\">{} ({} Posts)</h3>\n",
-            key.replace(' ', "-"),
-            key.replace(' ', "-"),
+            tag_slug(key),
+            tag_slug(key),
             html_escape(&heading_label),
             to_title_case(key),
             count
@@ -243,12 +351,8 @@ pub fn generate_tags_html(
             // Use single quotes around aria-label to allow double quotes inside
             let link_label =
                 format!("Visit the \"{}\" page", page.title);
-            let item_id = format!(
-                "li-{}-{}-{}",
-                key.replace(' ', "-"),
-                tag_index,
-                i
-            );
+            let item_id =
+                format!("li-{}-{}-{}", tag_slug(key), tag_index, i);
 
             // Example: adjusting descriptive text in <strong>
             // to provide more unique or useful info:
@@ -285,12 +389,188 @@ pub fn generate_tags_html(
     html_content
 }
 
-/// Minimal escaping for <, >, and & to avoid HTML injection issues.
-fn html_escape(input: &str) -> String {
-    input
-        .replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
+/// ## Sort Tag Pages
+///
+/// Orders the pages of a tag by date, newest first, then by title and
+/// permalink. Pages whose date cannot be parsed come after dated pages.
+///
+/// Dates may be RFC 3339, RFC 2822, `YYYY-MM-DD`, `July 12, 2023` or
+/// `12 July 2023`.
+pub fn sort_tag_pages(pages: &mut [PageData]) {
+    pages.sort_by_cached_key(|page| {
+        (
+            Reverse(parse_page_date(&page.date)),
+            page.title.clone(),
+            page.permalink.clone(),
+        )
+    });
+}
+
+/// ## Compute Related Pages
+///
+/// Finds, for every page, the `max` other pages sharing the most tags
+/// with it, for "you might also like" sections. Pages sharing the same
+/// number of tags are ordered newest first, then by title and permalink;
+/// pages sharing no tags are never related.
+///
+/// The result is keyed by [`FileData::name`] and holds an entry, possibly
+/// empty, for every page whose front matter can be read. Tags are read
+/// with the default [`TagOptions`] and compared after [`sanitize_tag`],
+/// ignoring case.
+pub fn compute_related(
+    files: &[FileData],
+    max: usize,
+) -> HashMap<String, Vec<PageData>> {
+    compute_related_with_options(files, max, &TagOptions::default())
+}
+
+/// ## Compute Related Pages With Options
+///
+/// Like [`compute_related`], reading tags with the given [`TagOptions`].
+pub fn compute_related_with_options(
+    files: &[FileData],
+    max: usize,
+    options: &TagOptions,
+) -> HashMap<String, Vec<PageData>> {
+    let pages: Vec<(&str, PageData, HashSet<String>)> = files
+        .iter()
+        .filter_map(|file| {
+            let (metadata, _, _) =
+                extract_and_prepare_metadata(&file.content).ok()?;
+            let tags = parse_tags(&metadata, options)
+                .iter()
+                .map(|tag| sanitize_tag(tag).to_lowercase())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+            let text = |key: &str| {
+                meta_str(&metadata, key).unwrap_or_default().to_string()
+            };
+            let page = PageData::new(
+                text("title"),
+                text("description"),
+                text("date"),
+                text("permalink"),
+            );
+            Some((file.name.as_str(), page, tags))
+        })
+        .collect();
+
+    pages
+        .iter()
+        .map(|(name, _, tags)| {
+            let mut scored: Vec<(usize, &PageData)> = pages
+                .iter()
+                .filter(|(other, _, _)| other != name)
+                .map(|(_, page, other_tags)| {
+                    (tags.intersection(other_tags).count(), page)
+                })
+                .filter(|(shared, _)| *shared > 0)
+                .collect();
+            scored.sort_by_cached_key(|(shared, page)| {
+                (
+                    Reverse(*shared),
+                    Reverse(parse_page_date(&page.date)),
+                    page.title.clone(),
+                    page.permalink.clone(),
+                )
+            });
+            let related = scored
+                .into_iter()
+                .take(max)
+                .map(|(_, page)| page.clone())
+                .collect();
+            (name.to_string(), related)
+        })
+        .collect()
+}
+
+/// ## Generate Related Pages HTML
+///
+/// Creates an accessible list linking to `pages`, in order, or an empty
+/// string if there are none.
+pub fn generate_related_html(pages: &[PageData]) -> String {
+    if pages.is_empty() {
+        return String::new();
+    }
+
+    let mut html_content = String::from(
+        "<ul role=\"list\" class=\"related-pages\" aria-label=\"Related pages\">\n",
+    );
+    for page in pages {
+        html_content.push_str(&format!(
+            "<li role=\"listitem\" class=\"related-page-item\"><a href=\"{}\">{}</a></li>\n",
+            html_escape(&page.permalink),
+            html_escape(&page.title)
+        ));
+    }
+    html_content.push_str("</ul>\n");
+    html_content
+}
+
+/// ## Generate Tag Cloud HTML
+///
+/// Creates an accessible list linking every tag to its section of the
+/// tags page, or an empty string if there are no tags.
+///
+/// Tags are listed alphabetically. Each link gets one of `classes`
+/// (smallest first, e.g. `["tag-sm", "tag-md", "tag-lg", "tag-xl"]`),
+/// picked by scaling its page count linearly between the least and most
+/// used tags. When every tag has the same count, the middle class is
+/// used; with no classes, links get none.
+pub fn generate_tag_cloud(
+    global_tags_data: &HashMap<String, Vec<PageData>>,
+    classes: &[&str],
+) -> String {
+    if global_tags_data.is_empty() {
+        return String::new();
+    }
+
+    let mut keys: Vec<&String> = global_tags_data.keys().collect();
+    keys.sort();
+
+    let counts = global_tags_data.values().map(|pages| pages.len());
+    let min = counts.clone().min().unwrap_or_default();
+    let max = counts.max().unwrap_or_default();
+
+    let mut html_content = String::from(
+        "<ul role=\"list\" class=\"tag-cloud\" aria-label=\"Tag cloud\">\n",
+    );
+    for key in keys {
+        let count = global_tags_data[key].len();
+        let class = match classes.len() {
+            0 => String::new(),
+            len if max == min => {
+                format!(" class=\"{}\"", classes[(len - 1) / 2])
+            }
+            len => {
+                let bucket = ((count - min) * (len - 1)
+                    + (max - min) / 2)
+                    / (max - min);
+                format!(" class=\"{}\"", classes[bucket])
+            }
+        };
+        let label = to_title_case(key);
+        html_content.push_str(&format!(
+            "<li role=\"listitem\"><a href=\"/tags/#h3-{}\"{} aria-label=\"Tag: {}, {} Posts\">{}</a></li>\n",
+            html_escape(&tag_slug(key)),
+            class,
+            html_escape(&label),
+            count,
+            html_escape(&label)
+        ));
+    }
+    html_content.push_str("</ul>\n");
+    html_content
+}
+
+/// Returns the fragment-safe form of a tag used in ids and classes.
+fn tag_slug(tag: &str) -> String {
+    tag.replace(' ', "-")
+}
+
+/// Parses a page date into a timestamp, if it uses a known layout.
+fn parse_page_date(date: &str) -> Option<i64> {
+    parse_date(date).map(OffsetDateTime::unix_timestamp)
 }
 
 /// ## Write Tags HTML to File
@@ -300,16 +580,27 @@ pub fn write_tags_html_to_file(
     html_content: &str,
     output_path: &Path,
 ) -> io::Result<()> {
-    let file_path = output_path.join("tags/index.html");
+    write_tags_html_to_page(
+        html_content,
+        &output_path.join("tags/index.html"),
+    )
+}
 
-    let mut file = fs::File::open(&file_path)?;
+/// Replaces `[[content]]` in the tags page at `file_path` with the
+/// generated snippet, for sites whose tags page is not written to
+/// `tags/index.html`.
+pub fn write_tags_html_to_page(
+    html_content: &str,
+    file_path: &Path,
+) -> io::Result<()> {
+    let mut file = fs::File::open(file_path)?;
     let mut base_html = String::new();
     // Use `_ = ...` to ignore number-of-bytes result
     _ = file.read_to_string(&mut base_html)?;
 
     let updated_html = base_html.replace("[[content]]", html_content);
 
-    let mut file = fs::File::create(&file_path)?;
+    let mut file = fs::File::create(file_path)?;
     file.write_all(updated_html.as_bytes())?;
 
     Ok(())
@@ -319,6 +610,55 @@ pub fn write_tags_html_to_file(
 mod tests {
     use super::*;
     use crate::models::data::FileData;
+    use crate::test_support::metadata;
+
+    #[test]
+    fn test_parse_tags_default_keys() {
+        let metadata = metadata(&[
+            ("tags", "rust, web"),
+            ("categories", "[\"web\", 'tools']"),
+        ]);
+
+        assert_eq!(
+            parse_tags(&metadata, &TagOptions::default()),
+            vec!["rust", "web", "tools"]
+        );
+    }
+
+    #[test]
+    fn test_parse_tags_custom_delimiter() {
+        let metadata =
+            metadata(&[("keywords", "rust  web"), ("tags", "a;b")]);
+
+        let whitespace = TagOptions {
+            keys: vec!["keywords".to_string()],
+            delimiter: TagDelimiter::Whitespace,
+        };
+        assert_eq!(
+            parse_tags(&metadata, &whitespace),
+            vec!["rust", "web"]
+        );
+
+        let semicolon = TagOptions {
+            delimiter: TagDelimiter::Char(';'),
+            ..TagOptions::default()
+        };
+        assert_eq!(parse_tags(&metadata, &semicolon), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_generate_tags_from_categories() {
+        let file = FileData {
+            content: "All about rust".to_string(),
+            ..Default::default()
+        };
+        let metadata =
+            metadata(&[("categories", "[rust]"), ("title", "Rust")]);
+
+        let tags = generate_tags(&file, &metadata);
+
+        assert_eq!(tags["rust"][0]["title"], "Rust");
+    }
 
     /// This test fails if "tag" is blacklisted. Either remove "tag"
     /// from the blacklist, or rename the test/metadata to something else.
@@ -404,4 +744,146 @@ fn test_generate_tags_partial_blacklist() {
             "Expected 'goodtag' to appear, but it wasn't found."
         );
     }
+
+    fn page(title: &str, date: &str) -> PageData {
+        PageData {
+            title: title.to_string(),
+            description: String::new(),
+            date: date.to_string(),
+            permalink: format!("/{}/", title.to_lowercase()),
+        }
+    }
+
+    #[test]
+    fn test_sort_tag_pages() {
+        let mut pages = vec![
+            page("Undated", "someday"),
+            page("Beta", "2023-07-12"),
+            page("Old", "Tue, 20 Feb 2018 15:15:15 GMT"),
+            page("New", "2024-03-01T10:00:00Z"),
+            page("Alpha", "July 12, 2023"),
+            page("Another", ""),
+        ];
+
+        sort_tag_pages(&mut pages);
+
+        let titles: Vec<&str> =
+            pages.iter().map(|page| page.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["New", "Alpha", "Beta", "Old", "Another", "Undated"]
+        );
+    }
+
+    #[test]
+    fn test_generate_tags_html_is_stable() {
+        let mut first = HashMap::new();
+        let _ = first.insert(
+            "rust".to_string(),
+            vec![
+                page("Older", "2023-01-01"),
+                page("Newer", "2024-01-01"),
+            ],
+        );
+        let _ = first.insert("web".to_string(), vec![page("Web", "")]);
+
+        let mut second = HashMap::new();
+        let _ = second.insert("web".to_string(), vec![page("Web", "")]);
+        let _ = second.insert(
+            "rust".to_string(),
+            vec![
+                page("Newer", "2024-01-01"),
+                page("Older", "2023-01-01"),
+            ],
+        );
+
+        let html = generate_tags_html(&first);
+        assert_eq!(html, generate_tags_html(&second));
+        assert!(
+            html.find("Newer").unwrap() < html.find("Older").unwrap()
+        );
+        assert!(
+            html.find("h3-rust").unwrap()
+                < html.find("h3-web").unwrap()
+        );
+    }
+
+    fn source(name: &str, date: &str, tags: &str) -> FileData {
+        FileData::new(
+            name.to_string(),
+            format!(
+                "---\ntitle: \"{0}\"\ndate: \"{1}\"\npermalink: \"/{0}/\"\ntags: \"{2}\"\n---\nBody",
+                name, date, tags
+            ),
+        )
+    }
+
+    #[test]
+    fn test_compute_related() {
+        let files = vec![
+            source("rust-web", "2024-01-01", "rust, web, wasm"),
+            source("rust-cli", "2023-01-01", "Rust, cli"),
+            source("wasm-web", "2022-01-01", "web, wasm"),
+            source("newer-rust", "2024-06-01", "rust"),
+            source("cooking", "2024-02-01", "food"),
+        ];
+
+        let related = compute_related(&files, 2);
+
+        let titles = |name: &str| -> Vec<String> {
+            related[name].iter().map(|p| p.title.clone()).collect()
+        };
+        // Two shared tags beat one; ties go to the newest page.
+        assert_eq!(titles("rust-web"), ["wasm-web", "newer-rust"]);
+        assert_eq!(titles("rust-cli"), ["newer-rust", "rust-web"]);
+        assert!(related["cooking"].is_empty());
+        assert_eq!(related["wasm-web"][0].permalink, "/rust-web/");
+        assert_eq!(related.len(), files.len());
+    }
+
+    #[test]
+    fn test_generate_related_html() {
+        assert_eq!(generate_related_html(&[]), "");
+
+        let html = generate_related_html(&[page("R&D", "")]);
+        assert!(html
+            .starts_with("<ul role=\"list\" class=\"related-pages\""));
+        assert!(html.contains("<a href=\"/r&amp;d/\">R&amp;D</a>"));
+    }
+
+    #[test]
+    fn test_generate_tag_cloud() {
+        let classes = ["tag-sm", "tag-md", "tag-lg"];
+        assert_eq!(generate_tag_cloud(&HashMap::new(), &classes), "");
+
+        let mut tags = HashMap::new();
+        let _ = tags
+            .insert("rust lang".to_string(), vec![page("A", ""); 9]);
+        let _ = tags.insert("a&b".to_string(), vec![page("B", "")]);
+        let _ = tags.insert("web".to_string(), vec![page("C", ""); 5]);
+
+        let html = generate_tag_cloud(&tags, &classes);
+        assert!(html.starts_with(
+            "<ul role=\"list\" class=\"tag-cloud\" aria-label=\"Tag cloud\">\n"
+        ));
+        assert!(html.contains(
+            "<a href=\"/tags/#h3-a&amp;b\" class=\"tag-sm\" aria-label=\"Tag: A&amp;b, 1 Posts\">A&amp;b</a>"
+        ));
+        assert!(html.contains(
+            "<a href=\"/tags/#h3-rust-lang\" class=\"tag-lg\""
+        ));
+        assert!(
+            html.contains("<a href=\"/tags/#h3-web\" class=\"tag-md\"")
+        );
+        assert!(
+            html.find("a&amp;b").unwrap() < html.find("web").unwrap()
+        );
+
+        let _ = tags.remove("a&b");
+        let _ = tags.insert("x".to_string(), vec![page("D", ""); 9]);
+        let _ = tags.remove("web");
+        assert!(generate_tag_cloud(&tags, &classes)
+            .contains("class=\"tag-md\""));
+        assert!(!generate_tag_cloud(&tags, &[]).contains("tag-md"));
+    }
 }
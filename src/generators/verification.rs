@@ -0,0 +1,277 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Site Verification Module
+//!
+//! Search consoles prove site ownership either with a file served from the
+//! site root or with a `<meta>` tag in the home page head. This module
+//! turns the `google_verification` and `bing_verification` tokens into
+//! both, so they survive clean builds.
+//!
+//! - A Google token of the form `google0123456789abcdef` (optionally with
+//!   `.html`) is the name of an HTML verification file; any other Google
+//!   token is the content of a `google-site-verification` meta tag.
+//! - A Bing token (32 hex digits) serves both `BingSiteAuth.xml` and the
+//!   `msvalidate.01` meta tag.
+//!
+//! ## Example Usage
+//! ```rust
+//! use std::collections::HashMap;
+//! use staticdatagen::generators::verification::VerificationConfig;
+//!
+//! let mut metadata = HashMap::new();
+//! metadata.insert("google_verification".to_string(), "google0123456789abcdef".to_string());
+//! metadata.insert("bing_verification".to_string(), "0123456789ABCDEF0123456789ABCDEF".to_string());
+//!
+//! let config = VerificationConfig::from_metadata(&metadata);
+//! let files = config.files().unwrap();
+//! assert_eq!(files[0].0, "google0123456789abcdef.html");
+//! assert_eq!(files[1].0, "BingSiteAuth.xml");
+//! assert!(config.meta_tags().unwrap().contains("msvalidate.01"));
+//! ```
+
+use std::collections::HashMap;
+
+/// The file Bing reads to verify a site.
+pub const BING_VERIFICATION_FILE: &str = "BingSiteAuth.xml";
+
+/// ## Errors in Site Verification
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VerificationError {
+    /// A token does not have the format its search console issues.
+    #[error(
+        "Invalid {provider} verification token '{token}': {message}"
+    )]
+    InvalidToken {
+        /// The search console, `google` or `bing`.
+        provider: &'static str,
+        /// The token found.
+        token: String,
+        /// The expected format.
+        message: &'static str,
+    },
+}
+
+/// ## Site Verification Configuration
+///
+/// The ownership tokens issued by search consoles. Empty tokens are
+/// treated as absent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct VerificationConfig {
+    /// The Google Search Console token: an HTML file name or a meta tag
+    /// value.
+    pub google: Option<String>,
+    /// The Bing Webmaster Tools token.
+    pub bing: Option<String>,
+}
+
+/// The form of a Google token.
+enum GoogleToken<'a> {
+    /// The stem of an HTML verification file.
+    File(&'a str),
+    /// The content of a meta tag.
+    Meta(&'a str),
+}
+
+impl VerificationConfig {
+    /// Reads the `google_verification` and `bing_verification` keys.
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Self {
+        let token = |key: &str| {
+            metadata
+                .get(key)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            google: token("google_verification"),
+            bing: token("bing_verification"),
+        }
+    }
+
+    /// Returns `true` if no token is set.
+    pub fn is_empty(&self) -> bool {
+        self.google.is_none() && self.bing.is_none()
+    }
+
+    /// Checks the format of every token.
+    pub fn validate(&self) -> Result<(), VerificationError> {
+        let _ = self.google_token()?;
+        let _ = self.bing_token()?;
+        Ok(())
+    }
+
+    /// Returns the verification files to write at the site root, as
+    /// `(file name, content)` pairs.
+    pub fn files(
+        &self,
+    ) -> Result<Vec<(String, String)>, VerificationError> {
+        let mut files = Vec::new();
+        if let Some(GoogleToken::File(stem)) = self.google_token()? {
+            files.push((
+                format!("{}.html", stem),
+                format!("google-site-verification: {}.html", stem),
+            ));
+        }
+        if let Some(token) = self.bing_token()? {
+            files.push((
+                BING_VERIFICATION_FILE.to_string(),
+                format!(
+                    "<?xml version=\"1.0\"?>\n<users>\n\t<user>{}</user>\n</users>\n",
+                    token
+                ),
+            ));
+        }
+        Ok(files)
+    }
+
+    /// Returns the verification meta tags for the page head, one per
+    /// line.
+    pub fn meta_tags(&self) -> Result<String, VerificationError> {
+        let mut tags = String::new();
+        if let Some(GoogleToken::Meta(token)) = self.google_token()? {
+            tags.push_str(&format!(
+                "<meta name=\"google-site-verification\" content=\"{}\">\n",
+                token
+            ));
+        }
+        if let Some(token) = self.bing_token()? {
+            tags.push_str(&format!(
+                "<meta name=\"msvalidate.01\" content=\"{}\">\n",
+                token
+            ));
+        }
+        Ok(tags)
+    }
+
+    /// Validates the Google token and tells its form.
+    fn google_token(
+        &self,
+    ) -> Result<Option<GoogleToken<'_>>, VerificationError> {
+        let token = match token(&self.google) {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+        let stem = token.strip_suffix(".html").unwrap_or(token);
+        let hex = stem.strip_prefix("google").unwrap_or_default();
+        if hex.len() == 16 && hex.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Ok(Some(GoogleToken::File(stem)));
+        }
+        if (20..=100).contains(&token.len())
+            && token.chars().all(|c| {
+                c.is_ascii_alphanumeric() || c == '-' || c == '_'
+            })
+        {
+            return Ok(Some(GoogleToken::Meta(token)));
+        }
+        Err(VerificationError::InvalidToken {
+            provider: "google",
+            token: token.to_string(),
+            message: "expected a file name like google0123456789abcdef.html or a meta tag token of 20 to 100 letters, digits, '-' or '_'",
+        })
+    }
+
+    /// Validates the Bing token.
+    fn bing_token(&self) -> Result<Option<&str>, VerificationError> {
+        match token(&self.bing) {
+            Some(token)
+                if token.len() == 32
+                    && token.chars().all(|c| c.is_ascii_hexdigit()) =>
+            {
+                Ok(Some(token))
+            }
+            Some(token) => Err(VerificationError::InvalidToken {
+                provider: "bing",
+                token: token.to_string(),
+                message: "expected 32 hexadecimal digits",
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Returns the trimmed token, or `None` if it is absent or empty.
+fn token(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BING: &str = "0123456789ABCDEF0123456789abcdef";
+
+    fn config(google: &str, bing: &str) -> VerificationConfig {
+        VerificationConfig {
+            google: Some(google.to_string()),
+            bing: Some(bing.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_google_file_token() {
+        let config = config("google0123456789abcdef.html", BING);
+        let files = config.files().unwrap();
+
+        assert_eq!(
+            files[0],
+            (
+                "google0123456789abcdef.html".to_string(),
+                "google-site-verification: google0123456789abcdef.html"
+                    .to_string()
+            )
+        );
+        assert!(files[1].1.contains(&format!("<user>{}</user>", BING)));
+        assert_eq!(
+            config.meta_tags().unwrap(),
+            format!(
+                "<meta name=\"msvalidate.01\" content=\"{}\">\n",
+                BING
+            )
+        );
+    }
+
+    #[test]
+    fn test_google_meta_token() {
+        let config =
+            config("Abc_DEF-0123456789xyzXYZ0123456789abcdefgh", "");
+
+        assert!(config.files().unwrap().is_empty());
+        assert_eq!(
+            config.meta_tags().unwrap(),
+            "<meta name=\"google-site-verification\" content=\"Abc_DEF-0123456789xyzXYZ0123456789abcdefgh\">\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_tokens() {
+        for (google, bing) in [
+            ("short", ""),
+            ("bad\"><script>alert(1)</script>token", ""),
+            ("", "0123"),
+            ("", "0123456789ABCDEF0123456789ABCDEG"),
+        ] {
+            let config = config(google, bing);
+            assert!(config.validate().is_err(), "{} {}", google, bing);
+            assert!(config.files().is_err());
+            assert!(config.meta_tags().is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        let mut metadata = HashMap::new();
+        let _ = metadata
+            .insert("google_verification".to_string(), " ".to_string());
+        assert!(VerificationConfig::from_metadata(&metadata).is_empty());
+
+        let _ = metadata
+            .insert("bing_verification".to_string(), BING.to_string());
+        let config = VerificationConfig::from_metadata(&metadata);
+        assert_eq!(config.bing.as_deref(), Some(BING));
+        assert!(config.validate().is_ok());
+    }
+}
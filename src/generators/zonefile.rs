@@ -0,0 +1,698 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # DNS Zone File Generation Module
+//!
+//! This module assembles a complete BIND-style zone file from a set of
+//! resource records, such as those produced by the
+//! [`CnameGenerator`](crate::generators::cname::CnameGenerator). The zone
+//! is prefixed with `$ORIGIN` and `$TTL` directives, a validated `SOA`
+//! record and an `NS` record for the primary name server.
+//!
+//! ## Features
+//! - **SOA Validation**: Checks name servers, the administrator mailbox and
+//!   the refresh/retry/expire/minimum timers.
+//! - **Date-Based Serials**: Serials follow the `YYYYMMDDnn` convention and
+//!   are incremented automatically from the previous value.
+//! - **Metadata Conversion**: Build a zone from front matter, including the
+//!   page's CNAME record.
+//!
+//! ## Example Usage
+//! ```rust
+//! use staticdatagen::generators::zonefile::{
+//!     SoaRecord, ZonefileConfig, ZonefileGenerator,
+//! };
+//!
+//! let soa = SoaRecord::new("ns1.example.com", "hostmaster@example.com", Some(2025010101)).unwrap();
+//! let records = vec!["www 3600 IN CNAME example.com.".to_string()];
+//! let config = ZonefileConfig::new("example.com", None, soa, records).unwrap();
+//! let zone = ZonefileGenerator::new(config).generate();
+//!
+//! assert!(zone.starts_with("$ORIGIN example.com.\n$TTL 3600\n"));
+//! assert!(zone.contains("IN SOA ns1.example.com. hostmaster.example.com."));
+//! ```
+
+use crate::generators::cname::{CnameConfig, CnameError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+use time::{Date, OffsetDateTime};
+
+/// The largest TTL or timer value allowed by RFC 2181.
+const MAX_TTL: u32 = 2_147_483_647;
+
+/// ## Errors in Zone File Generation
+///
+/// Represents the errors that may occur while validating a zone and its
+/// `SOA` record.
+#[derive(Debug, Error)]
+pub enum ZonefileError {
+    /// A domain name in the zone is invalid.
+    #[error("Invalid domain: {0}")]
+    InvalidDomain(#[from] CnameError),
+    /// The administrator email address is invalid.
+    #[error("Invalid administrator email: {0}")]
+    InvalidEmail(String),
+    /// A TTL or SOA timer value is invalid.
+    #[error("Invalid timer value: {0}")]
+    InvalidTimer(String),
+    /// The serial number is invalid or cannot be incremented.
+    #[error("Invalid serial number: {0}")]
+    InvalidSerial(String),
+    /// Required metadata is missing.
+    #[error("Missing required metadata field: {0}")]
+    MissingMetadata(String),
+}
+
+/// ## SOA Record
+///
+/// The Start of Authority record that heads every zone file.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SoaRecord {
+    /// The fully-qualified primary name server (e.g. `ns1.example.com.`).
+    pub primary_ns: String,
+    /// The administrator mailbox in DNS form (e.g. `hostmaster.example.com.`).
+    pub admin_email: String,
+    /// The zone serial number in `YYYYMMDDnn` form.
+    pub serial: u32,
+    /// Seconds before secondaries refresh the zone.
+    pub refresh: u32,
+    /// Seconds before secondaries retry a failed refresh.
+    pub retry: u32,
+    /// Seconds after which secondaries stop answering for the zone.
+    pub expire: u32,
+    /// The negative caching TTL in seconds.
+    pub minimum: u32,
+}
+
+impl SoaRecord {
+    /// The default refresh interval (2 hours).
+    const DEFAULT_REFRESH: u32 = 7200;
+    /// The default retry interval (1 hour).
+    const DEFAULT_RETRY: u32 = 3600;
+    /// The default expiry (2 weeks).
+    const DEFAULT_EXPIRE: u32 = 1_209_600;
+    /// The default negative caching TTL (1 hour).
+    const DEFAULT_MINIMUM: u32 = 3600;
+
+    /// Creates a new validated SOA record with default timers.
+    ///
+    /// The serial is derived from today's date and `previous_serial` using
+    /// [`next_serial`].
+    ///
+    /// # Arguments
+    ///
+    /// - `primary_ns`: The primary name server for the zone.
+    /// - `admin_email`: The administrator email (e.g. `hostmaster@example.com`).
+    /// - `previous_serial`: The serial of the currently published zone, if any.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the validated `SoaRecord` or a `ZonefileError`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use staticdatagen::generators::zonefile::SoaRecord;
+    ///
+    /// let soa = SoaRecord::new("ns1.example.com", "admin@example.com", None).unwrap();
+    /// assert_eq!(soa.primary_ns, "ns1.example.com.");
+    /// assert_eq!(soa.admin_email, "admin.example.com.");
+    /// ```
+    pub fn new(
+        primary_ns: &str,
+        admin_email: &str,
+        previous_serial: Option<u32>,
+    ) -> Result<Self, ZonefileError> {
+        let today = OffsetDateTime::now_utc().date();
+
+        Ok(Self {
+            primary_ns: fully_qualify(primary_ns)?,
+            admin_email: email_to_mailbox(admin_email)?,
+            serial: next_serial(previous_serial, today)?,
+            refresh: Self::DEFAULT_REFRESH,
+            retry: Self::DEFAULT_RETRY,
+            expire: Self::DEFAULT_EXPIRE,
+            minimum: Self::DEFAULT_MINIMUM,
+        })
+    }
+
+    /// Overrides the refresh, retry, expire and minimum timers.
+    ///
+    /// # Returns
+    ///
+    /// The updated `SoaRecord`, or a `ZonefileError` if the timers are
+    /// inconsistent.
+    pub fn with_timers(
+        mut self,
+        refresh: u32,
+        retry: u32,
+        expire: u32,
+        minimum: u32,
+    ) -> Result<Self, ZonefileError> {
+        self.refresh = refresh;
+        self.retry = retry;
+        self.expire = expire;
+        self.minimum = minimum;
+        self.validate_timers()?;
+        Ok(self)
+    }
+
+    /// Validates the SOA timers.
+    ///
+    /// Every timer must be non-zero and within the RFC 2181 range, the
+    /// retry interval must be shorter than the refresh interval, and the
+    /// expiry must exceed the refresh interval.
+    fn validate_timers(&self) -> Result<(), ZonefileError> {
+        for (name, value) in [
+            ("refresh", self.refresh),
+            ("retry", self.retry),
+            ("expire", self.expire),
+            ("minimum", self.minimum),
+        ] {
+            validate_ttl(name, value)?;
+        }
+
+        if self.retry >= self.refresh {
+            return Err(ZonefileError::InvalidTimer(
+                "retry must be shorter than refresh.".to_string(),
+            ));
+        }
+        if self.expire <= self.refresh {
+            return Err(ZonefileError::InvalidTimer(
+                "expire must be longer than refresh.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// ## Zone File Configuration
+///
+/// The origin, default TTL, SOA record and resource records of a zone.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ZonefileConfig {
+    /// The fully-qualified zone origin (e.g. `example.com.`).
+    pub origin: String,
+    /// The default TTL applied to records without an explicit TTL.
+    pub ttl: u32,
+    /// The Start of Authority record.
+    pub soa: SoaRecord,
+    /// The resource record lines, written in order after the SOA.
+    pub records: Vec<String>,
+}
+
+impl ZonefileConfig {
+    /// The default zone TTL.
+    const DEFAULT_TTL: u32 = 3600;
+
+    /// Creates a new validated zone file configuration.
+    ///
+    /// # Arguments
+    ///
+    /// - `origin`: The zone origin (e.g. `example.com`).
+    /// - `ttl`: The default TTL (defaults to `3600` seconds if `None`).
+    /// - `soa`: The SOA record for the zone.
+    /// - `records`: Resource record lines; owner names are relative to the
+    ///   origin unless they end with a dot.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the validated `ZonefileConfig` or a `ZonefileError`.
+    pub fn new(
+        origin: &str,
+        ttl: Option<u32>,
+        soa: SoaRecord,
+        records: Vec<String>,
+    ) -> Result<Self, ZonefileError> {
+        let ttl = ttl.unwrap_or(Self::DEFAULT_TTL);
+        validate_ttl("ttl", ttl)?;
+        soa.validate_timers()?;
+
+        Ok(Self {
+            origin: fully_qualify(origin)?,
+            ttl,
+            soa,
+            records,
+        })
+    }
+
+    /// Creates a zone file configuration from metadata.
+    ///
+    /// The `zone_origin` key (falling back to `cname`), `soa_primary_ns`
+    /// and `soa_admin_email` keys are mandatory. `zone_ttl`, `soa_serial`
+    /// (the previously published serial), `soa_refresh`, `soa_retry`,
+    /// `soa_expire` and `soa_minimum` are optional. When a `cname` key is
+    /// present, a record aliasing its `www` host to the apex is generated
+    /// with the CNAME generator and added to the zone.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ZonefileConfig` or a `ZonefileError`.
+    pub fn from_metadata(
+        metadata: &HashMap<String, String>,
+    ) -> Result<Self, ZonefileError> {
+        let required = |key: &str| {
+            metadata
+                .get(key)
+                .filter(|value| !value.trim().is_empty())
+                .ok_or_else(|| {
+                    ZonefileError::MissingMetadata(key.to_string())
+                })
+        };
+
+        let origin = metadata
+            .get("zone_origin")
+            .or_else(|| metadata.get("cname"))
+            .ok_or_else(|| {
+                ZonefileError::MissingMetadata(
+                    "zone_origin".to_string(),
+                )
+            })?;
+
+        let previous_serial = parse_number(metadata, "soa_serial")
+            .map_err(|_| {
+                ZonefileError::InvalidSerial(
+                    "soa_serial must be a number.".to_string(),
+                )
+            })?;
+
+        let soa = SoaRecord::new(
+            required("soa_primary_ns")?,
+            required("soa_admin_email")?,
+            previous_serial,
+        )?;
+        let timer = |key: &str, default: u32| {
+            parse_number(metadata, key).map(|v| v.unwrap_or(default))
+        };
+        let (refresh, retry, expire, minimum) = (
+            timer("soa_refresh", soa.refresh)?,
+            timer("soa_retry", soa.retry)?,
+            timer("soa_expire", soa.expire)?,
+            timer("soa_minimum", soa.minimum)?,
+        );
+        let soa = soa.with_timers(refresh, retry, expire, minimum)?;
+
+        let ttl = parse_number(metadata, "zone_ttl")?;
+
+        let mut records = Vec::new();
+        if let Some(domain) = metadata.get("cname") {
+            // Alias `www` to the apex: a CNAME cannot sit at the apex
+            // beside the SOA and NS records. Fully qualify both names so
+            // they are not made relative to `$ORIGIN`.
+            let cname = CnameConfig::new(
+                domain,
+                ttl,
+                Some("www.{domain}. {ttl} IN CNAME {domain}.".into()),
+            )?;
+            records.push(cname.generate_custom());
+        }
+
+        Self::new(origin, ttl, soa, records)
+    }
+}
+
+/// ## Zone File Generator
+///
+/// Renders a [`ZonefileConfig`] as a BIND-style zone file.
+#[derive(Debug)]
+pub struct ZonefileGenerator {
+    /// The configuration for the zone file.
+    pub config: ZonefileConfig,
+}
+
+impl ZonefileGenerator {
+    /// Creates a new generator with the provided configuration.
+    pub fn new(config: ZonefileConfig) -> Self {
+        Self { config }
+    }
+
+    /// Generates the zone file as a string.
+    ///
+    /// # Returns
+    ///
+    /// The `$ORIGIN` and `$TTL` directives, the SOA record, an NS record
+    /// for the SOA's primary name server and the resource records, one
+    /// per line.
+    pub fn generate(&self) -> String {
+        let config = &self.config;
+        let soa = &config.soa;
+
+        let mut zone = format!(
+            "$ORIGIN {origin}\n$TTL {ttl}\n\
+             @ IN SOA {ns} {email} (\n\
+             \x20   {serial} ; serial\n\
+             \x20   {refresh} ; refresh\n\
+             \x20   {retry} ; retry\n\
+             \x20   {expire} ; expire\n\
+             \x20   {minimum} ; minimum\n\
+             )\n\
+             @ IN NS {ns}\n",
+            origin = config.origin,
+            ttl = config.ttl,
+            ns = soa.primary_ns,
+            email = soa.admin_email,
+            serial = soa.serial,
+            refresh = soa.refresh,
+            retry = soa.retry,
+            expire = soa.expire,
+            minimum = soa.minimum,
+        );
+
+        for record in &config.records {
+            zone.push_str(record.trim());
+            zone.push('\n');
+        }
+
+        zone
+    }
+
+    /// Exports the generated zone file.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: The path to the file where the zone will be written.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure.
+    pub fn export_to_file(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.generate())
+    }
+
+    /// Creates a zone file from metadata provided as a key-value map.
+    ///
+    /// See [`ZonefileConfig::from_metadata`] for the recognised keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use staticdatagen::generators::zonefile::ZonefileGenerator;
+    ///
+    /// let mut metadata = HashMap::new();
+    /// metadata.insert("cname".to_string(), "example.com".to_string());
+    /// metadata.insert("soa_primary_ns".to_string(), "ns1.example.com".to_string());
+    /// metadata.insert("soa_admin_email".to_string(), "admin@example.com".to_string());
+    ///
+    /// let zone = ZonefileGenerator::from_metadata(&metadata).unwrap();
+    /// assert!(zone.contains("@ IN NS ns1.example.com.\n"));
+    /// assert!(zone.contains("www.example.com. 3600 IN CNAME example.com."));
+    /// ```
+    pub fn from_metadata(
+        metadata: &HashMap<String, String>,
+    ) -> Result<String, ZonefileError> {
+        let config = ZonefileConfig::from_metadata(metadata)?;
+        Ok(ZonefileGenerator::new(config).generate())
+    }
+}
+
+/// Computes the next date-based serial number.
+///
+/// Serials use the `YYYYMMDDnn` convention. If the previous serial is
+/// older than today, the first serial of the day (`nn = 00`) is returned;
+/// otherwise the previous serial is incremented so that the serial always
+/// increases.
+///
+/// # Example
+///
+/// ```rust
+/// use staticdatagen::generators::zonefile::next_serial;
+/// use time::{Date, Month};
+///
+/// let today = Date::from_calendar_date(2025, Month::March, 4).unwrap();
+/// assert_eq!(next_serial(None, today).unwrap(), 2025030400);
+/// assert_eq!(next_serial(Some(2024123105), today).unwrap(), 2025030400);
+/// assert_eq!(next_serial(Some(2025030400), today).unwrap(), 2025030401);
+/// ```
+pub fn next_serial(
+    previous: Option<u32>,
+    today: Date,
+) -> Result<u32, ZonefileError> {
+    let year = u32::try_from(today.year()).map_err(|_| {
+        ZonefileError::InvalidSerial(format!(
+            "year {} cannot be encoded in a serial.",
+            today.year()
+        ))
+    })?;
+
+    let base = year
+        .checked_mul(1_000_000)
+        .and_then(|serial| {
+            serial.checked_add(
+                u32::from(u8::from(today.month())) * 10_000,
+            )
+        })
+        .and_then(|serial| {
+            serial.checked_add(u32::from(today.day()) * 100)
+        })
+        .ok_or_else(|| {
+            ZonefileError::InvalidSerial(format!(
+                "year {} cannot be encoded in a serial.",
+                year
+            ))
+        })?;
+
+    match previous {
+        Some(previous) if previous >= base => {
+            previous.checked_add(1).ok_or_else(|| {
+                ZonefileError::InvalidSerial(
+                    "serial cannot be incremented further.".to_string(),
+                )
+            })
+        }
+        _ => Ok(base),
+    }
+}
+
+/// Validates and fully qualifies a domain name with a trailing dot.
+fn fully_qualify(domain: &str) -> Result<String, ZonefileError> {
+    let config = CnameConfig::new(
+        domain.trim().trim_end_matches('.'),
+        None,
+        None,
+    )?;
+    Ok(format!("{}.", config.domain))
+}
+
+/// Converts an email address to the SOA mailbox form.
+///
+/// Dots in the local part are escaped and the `@` becomes a dot, so
+/// `john.doe@example.com` becomes `john\.doe.example.com.`.
+fn email_to_mailbox(email: &str) -> Result<String, ZonefileError> {
+    let email = email.trim();
+    let (local, domain) = email
+        .split_once('@')
+        .filter(|(local, domain)| {
+            !local.is_empty() && !domain.contains('@')
+        })
+        .ok_or_else(|| {
+            ZonefileError::InvalidEmail(email.to_string())
+        })?;
+
+    if !local.chars().all(|c| {
+        c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+')
+    }) {
+        return Err(ZonefileError::InvalidEmail(email.to_string()));
+    }
+
+    let domain = fully_qualify(domain)
+        .map_err(|_| ZonefileError::InvalidEmail(email.to_string()))?;
+
+    Ok(format!("{}.{}", local.replace('.', "\\."), domain))
+}
+
+/// Validates a TTL or SOA timer value.
+fn validate_ttl(name: &str, value: u32) -> Result<(), ZonefileError> {
+    if value == 0 || value > MAX_TTL {
+        return Err(ZonefileError::InvalidTimer(format!(
+            "{} must be between 1 and {}.",
+            name, MAX_TTL
+        )));
+    }
+    Ok(())
+}
+
+/// Parses an optional numeric metadata value.
+fn parse_number(
+    metadata: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<u32>, ZonefileError> {
+    metadata
+        .get(key)
+        .map(|value| value.trim().parse::<u32>())
+        .transpose()
+        .map_err(|_| {
+            ZonefileError::InvalidTimer(format!(
+                "{} must be a number.",
+                key
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::metadata;
+    use time::Month;
+
+    fn soa() -> SoaRecord {
+        SoaRecord::new(
+            "ns1.example.com",
+            "hostmaster@example.com",
+            Some(4_000_000_000),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_generate_zone_file() {
+        let config = ZonefileConfig::new(
+            "example.com",
+            Some(7200),
+            soa(),
+            vec!["www IN A 192.0.2.1".to_string()],
+        )
+        .unwrap();
+        let zone = ZonefileGenerator::new(config).generate();
+
+        assert_eq!(
+            zone,
+            "$ORIGIN example.com.\n$TTL 7200\n\
+             @ IN SOA ns1.example.com. hostmaster.example.com. (\n\
+             \x20   4000000001 ; serial\n\
+             \x20   7200 ; refresh\n\
+             \x20   3600 ; retry\n\
+             \x20   1209600 ; expire\n\
+             \x20   3600 ; minimum\n\
+             )\n\
+             @ IN NS ns1.example.com.\n\
+             www IN A 192.0.2.1\n"
+        );
+    }
+
+    #[test]
+    fn test_next_serial() {
+        let today =
+            Date::from_calendar_date(2025, Month::January, 9).unwrap();
+
+        assert_eq!(next_serial(None, today).unwrap(), 2025010900);
+        assert_eq!(
+            next_serial(Some(2025010800), today).unwrap(),
+            2025010900
+        );
+        assert_eq!(
+            next_serial(Some(2025010907), today).unwrap(),
+            2025010908
+        );
+        assert!(next_serial(Some(u32::MAX), today).is_err());
+    }
+
+    #[test]
+    fn test_email_to_mailbox() {
+        assert_eq!(
+            email_to_mailbox("john.doe@example.com").unwrap(),
+            "john\\.doe.example.com."
+        );
+        for invalid in [
+            "",
+            "example.com",
+            "@example.com",
+            "a@b@example.com",
+            "a b@example.com",
+        ] {
+            assert!(
+                email_to_mailbox(invalid).is_err(),
+                "Email should be invalid: {}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_soa_fields() {
+        assert!(matches!(
+            SoaRecord::new("invalid", "admin@example.com", None),
+            Err(ZonefileError::InvalidDomain(_))
+        ));
+        assert!(matches!(
+            soa().with_timers(3600, 7200, 1_209_600, 3600),
+            Err(ZonefileError::InvalidTimer(_))
+        ));
+        assert!(matches!(
+            soa().with_timers(7200, 3600, 3600, 3600),
+            Err(ZonefileError::InvalidTimer(_))
+        ));
+        assert!(matches!(
+            soa().with_timers(7200, 3600, 1_209_600, 0),
+            Err(ZonefileError::InvalidTimer(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_zone_ttl() {
+        assert!(ZonefileConfig::new(
+            "example.com",
+            Some(0),
+            soa(),
+            vec![]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        let metadata = metadata(&[
+            ("cname", "example.com"),
+            ("zone_ttl", "600"),
+            ("soa_primary_ns", "ns1.example.com."),
+            ("soa_admin_email", "admin@example.com"),
+            ("soa_serial", "4000000000"),
+            ("soa_refresh", "86400"),
+        ]);
+        let zone = ZonefileGenerator::from_metadata(&metadata).unwrap();
+
+        assert!(zone.starts_with("$ORIGIN example.com.\n$TTL 600\n"));
+        assert!(zone.contains("4000000001 ; serial"));
+        assert!(zone.contains("86400 ; refresh"));
+        assert!(zone.contains(")\n@ IN NS ns1.example.com.\n"));
+        assert!(zone.ends_with(
+            "\nwww.example.com. 600 IN CNAME example.com.\n"
+        ));
+    }
+
+    #[test]
+    fn test_from_metadata_missing_fields() {
+        let missing_ns = metadata(&[
+            ("zone_origin", "example.com"),
+            ("soa_admin_email", "admin@example.com"),
+        ]);
+        assert!(matches!(
+            ZonefileConfig::from_metadata(&missing_ns),
+            Err(ZonefileError::MissingMetadata(key)) if key == "soa_primary_ns"
+        ));
+
+        let missing_origin = metadata(&[
+            ("soa_primary_ns", "ns1.example.com"),
+            ("soa_admin_email", "admin@example.com"),
+        ]);
+        assert!(matches!(
+            ZonefileConfig::from_metadata(&missing_origin),
+            Err(ZonefileError::MissingMetadata(key)) if key == "zone_origin"
+        ));
+    }
+
+    #[test]
+    fn test_from_metadata_invalid_serial() {
+        let metadata = metadata(&[
+            ("zone_origin", "example.com"),
+            ("soa_primary_ns", "ns1.example.com"),
+            ("soa_admin_email", "admin@example.com"),
+            ("soa_serial", "latest"),
+        ]);
+        assert!(matches!(
+            ZonefileConfig::from_metadata(&metadata),
+            Err(ZonefileError::InvalidSerial(_))
+        ));
+    }
+}
@@ -49,6 +49,10 @@
 #[macro_use]
 pub mod macros;
 
+/// Factories shared by the unit tests.
+#[cfg(test)]
+mod test_support;
+
 /// Re-exports the `compile` function from [`compiler::service`].
 ///
 /// This function is central for parsing, transforming, and validating
@@ -56,6 +60,19 @@
 /// content during the process.
 pub use compiler::service::compile;
 
+/// Re-exports the template `Engine` type from `staticweaver`.
+///
+/// Pass an engine to
+/// [`compile_with_hooks`](compiler::service::compile_with_hooks) to keep
+/// its render cache across builds.
+pub use staticweaver::Engine;
+
+/// Re-exports the template `Context` type from `staticweaver`.
+///
+/// Holds the variables a template is rendered with, as checked by
+/// [`validate_template`](modules::template::validate_template).
+pub use staticweaver::Context;
+
 /// Re-exports the `Server` type from `http_handle`.
 ///
 /// This server structure can be employed to host or serve generated
@@ -63,6 +80,12 @@
 /// making it suitable for production environments.
 pub use http_handle::Server;
 
+/// Re-exports the [`DevServer`](utilities::server::DevServer) type.
+///
+/// Wraps the static file server with a `/healthz` endpoint and a
+/// shutdown handle, for use in CI or behind a proxy.
+pub use utilities::server::DevServer;
+
 /// Re-exports the `generate_unique_string` function from [`utilities::uuid`].
 ///
 /// This utility function produces a randomised UUID-like string for
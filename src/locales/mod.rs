@@ -11,3 +11,55 @@
 pub mod fr;
 /// Template module for language-specific templates.
 pub mod template;
+
+/// Languages written right to left, by primary language subtag.
+const RTL_LANGUAGES: [&str; 14] = [
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ku", "ps", "sd",
+    "syr", "ug", "ur",
+];
+
+/// Scripts written right to left, by script subtag.
+const RTL_SCRIPTS: [&str; 6] =
+    ["adlm", "arab", "hebr", "nkoo", "syrc", "thaa"];
+
+/// Returns `true` if text in the `lang` language tag (e.g. `ar` or
+/// `pa-Arab`) is written right to left.
+///
+/// A script subtag decides the direction when present; otherwise the
+/// primary language does. Matching ignores case.
+///
+/// # Example
+///
+/// ```
+/// use staticdatagen::locales::is_rtl;
+///
+/// assert!(is_rtl("ar-EG"));
+/// assert!(is_rtl("pa-Arab"));
+/// assert!(!is_rtl("en-GB"));
+/// assert!(!is_rtl("ku-Latn"));
+/// ```
+pub fn is_rtl(lang: &str) -> bool {
+    let mut subtags = lang.trim().split(['-', '_']);
+    let language = subtags.next().unwrap_or_default().to_lowercase();
+    let script = subtags
+        .find(|subtag| {
+            subtag.len() == 4
+                && subtag.chars().all(|c| c.is_ascii_alphabetic())
+        })
+        .map(str::to_lowercase);
+
+    match script {
+        Some(script) => RTL_SCRIPTS.contains(&script.as_str()),
+        None => RTL_LANGUAGES.contains(&language.as_str()),
+    }
+}
+
+/// Returns the text direction of `lang`, `"rtl"` or `"ltr"`, as used by
+/// the HTML `dir` attribute.
+pub fn direction(lang: &str) -> &'static str {
+    if is_rtl(lang) {
+        "rtl"
+    } else {
+        "ltr"
+    }
+}
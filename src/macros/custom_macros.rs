@@ -195,6 +195,8 @@ macro_rules! macro_get_args {
 ///
 /// The macro is typically used in contexts where metadata is stored in a data structure that supports the `get` and `cloned` methods, such as a `HashMap<String, String>`.
 ///
+/// Because a missing key and an empty value both yield the default, new code should prefer the typed helpers in `utilities::metadata` (`meta_str`, `meta_date`, `meta_bool` and `meta_url`), which keep the two apart and report invalid values.
+///
 /// ## Example
 ///
 /// ```rust
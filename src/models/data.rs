@@ -44,7 +44,7 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use thiserror::Error;
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
@@ -169,6 +169,86 @@ pub fn validate_date(date: &str) -> Result<(), DataError> {
         Ok(())
     }
 
+    /// Validates a security.txt `Contact` value.
+    ///
+    /// RFC 9116 requires each contact to be a `mailto:`, `tel:` or
+    /// `https:` URI. A bare email address is rejected with a hint to
+    /// prefix it with `mailto:`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use staticdatagen::models::data::validation::validate_security_contact;
+    ///
+    /// assert!(validate_security_contact("mailto:security@example.com").is_ok());
+    /// assert!(validate_security_contact("tel:+1-201-555-0123").is_ok());
+    /// assert!(validate_security_contact("https://example.com/report").is_ok());
+    /// assert!(validate_security_contact("security@example.com").is_err());
+    /// assert!(validate_security_contact("http://example.com/report").is_err());
+    /// ```
+    pub fn validate_security_contact(
+        contact: &str,
+    ) -> Result<(), DataError> {
+        let contact = contact.trim();
+        let invalid = |reason: &str| {
+            Err(DataError::InvalidUrl(format!(
+                "Invalid security contact '{}': {}",
+                contact, reason
+            )))
+        };
+
+        let (scheme, rest) = match contact.split_once(':') {
+            Some((scheme, rest)) => (scheme.to_ascii_lowercase(), rest),
+            None if contact.contains('@') => {
+                return invalid(&format!(
+                    "email addresses must be written as 'mailto:{}'",
+                    contact
+                ));
+            }
+            None => {
+                return invalid(
+                    "expected a mailto:, tel: or https: URI",
+                )
+            }
+        };
+
+        match scheme.as_str() {
+            "mailto" => match rest.split_once('@') {
+                Some((local, domain))
+                    if !local.is_empty()
+                        && domain.contains('.')
+                        && !rest.chars().any(char::is_whitespace) =>
+                {
+                    validate_url(contact)
+                }
+                _ => {
+                    invalid("expected an email address after 'mailto:'")
+                }
+            },
+            "tel" => {
+                if rest.chars().any(|c| c.is_ascii_digit())
+                    && rest.chars().all(|c| {
+                        c.is_ascii_digit() || "+-.()".contains(c)
+                    })
+                {
+                    Ok(())
+                } else {
+                    invalid("expected a phone number after 'tel:'")
+                }
+            }
+            "https" => {
+                let has_host = Url::parse(contact)
+                    .map_or(false, |url| url.has_host());
+                if has_host {
+                    validate_url(contact)
+                } else {
+                    invalid("expected an absolute https: URL")
+                }
+            }
+            _ => invalid("expected a mailto:, tel: or https: URI"),
+        }
+    }
+
     /// Validates language code against ISO 639-1
     pub fn validate_language_code(code: &str) -> Result<(), DataError> {
         if code.is_empty() {
@@ -516,6 +596,106 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// Represents the mobile and AMP variants of a page for sitemaps
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct AlternateData {
+    /// The absolute URL of the AMP version of the page
+    pub amp_url: String,
+    /// Whether the page is a mobile-specific page
+    pub mobile: bool,
+}
+
+impl AlternateData {
+    /// Creates an `AlternateData` instance from page metadata
+    ///
+    /// Reads the `amp_url` and `mobile` front matter keys. `mobile` is
+    /// enabled by `true`, `yes` or `1` (case-insensitive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use staticdatagen::models::data::AlternateData;
+    ///
+    /// let mut metadata = HashMap::new();
+    /// metadata.insert("amp_url".to_string(), "https://example.com/amp/".to_string());
+    /// metadata.insert("mobile".to_string(), "true".to_string());
+    ///
+    /// let alternates = AlternateData::from_metadata(&metadata).unwrap();
+    /// assert!(alternates.mobile);
+    /// assert_eq!(alternates.amp_url, "https://example.com/amp/");
+    /// ```
+    pub fn from_metadata(
+        metadata: &HashMap<String, String>,
+    ) -> Result<Self, DataError> {
+        let data = Self {
+            amp_url: metadata
+                .get("amp_url")
+                .map(|url| url.trim().to_string())
+                .unwrap_or_default(),
+            mobile: metadata.get("mobile").map_or(false, |value| {
+                matches!(
+                    value.trim().to_ascii_lowercase().as_str(),
+                    "true" | "yes" | "1"
+                )
+            }),
+        };
+        data.validate()?;
+        Ok(data)
+    }
+
+    /// Validates the alternate data
+    ///
+    /// Checks:
+    /// - `amp_url` is an absolute `http` or `https` URL (if present)
+    pub fn validate(&self) -> Result<(), DataError> {
+        if self.amp_url.is_empty() {
+            return Ok(());
+        }
+
+        validation::validate_url(&self.amp_url)?;
+        let url = Url::parse(&self.amp_url).map_err(|e| {
+            DataError::InvalidUrl(format!("Invalid AMP URL: {}", e))
+        })?;
+        if !matches!(url.scheme(), "http" | "https")
+            || url.host_str().is_none()
+        {
+            return Err(DataError::InvalidUrl(format!(
+                "AMP URL must be an absolute http(s) URL: {}",
+                self.amp_url
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the page declares no alternate versions
+    pub fn is_empty(&self) -> bool {
+        self.amp_url.is_empty() && !self.mobile
+    }
+}
+
+/// Machine-readable metadata of a compiled page, written as its
+/// `page.json` sidecar
+#[derive(
+    Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
+)]
+pub struct PageMetadata {
+    /// The title, description, date and permalink of the page
+    #[serde(flatten)]
+    pub page: PageData,
+    /// The page tags, as written in the front matter
+    pub tags: Vec<String>,
+    /// The number of words in the page body
+    pub word_count: usize,
+    /// The plain-text excerpt of the page
+    pub excerpt: String,
+    /// Every front matter value of the page
+    pub metadata: BTreeMap<String, String>,
+}
+
 /// Represents the content and metadata of a file
 #[derive(
     Debug, Default, PartialEq, Eq, Hash, Clone, Serialize, Deserialize,
@@ -543,6 +723,16 @@ pub struct FileData {
     pub sitemap_news: String,
     /// The robots.txt content
     pub txt: String,
+    /// The `page.json` metadata sidecar content, if enabled
+    #[serde(default)]
+    pub json: String,
+    /// The alternate renderings of the page, as `(template, HTML)` pairs
+    #[serde(default)]
+    pub alternates: Vec<(String, String)>,
+    /// The path of the page's HTML relative to the build directory, or
+    /// empty for the default `<name>/index.html`
+    #[serde(default)]
+    pub output_path: String,
 }
 
 impl FileData {
@@ -577,6 +767,9 @@ pub fn new(name: String, content: String) -> Self {
             sitemap: String::new(),
             sitemap_news: String::new(),
             txt: String::new(),
+            json: String::new(),
+            alternates: Vec::new(),
+            output_path: String::new(),
         }
     }
 
@@ -1443,6 +1636,19 @@ pub fn validate(&self) -> Result<(), DataError> {
 pub struct TxtData {
     /// The permalink of the website
     pub permalink: String,
+    /// Paths crawlers may access (`Allow` directives)
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Paths crawlers must not access (`Disallow` directives)
+    #[serde(default)]
+    pub disallow: Vec<String>,
+    /// The delay between requests, in seconds (`Crawl-delay`)
+    #[serde(default)]
+    pub crawl_delay: Option<u32>,
+    /// The absolute URLs of the sitemaps to advertise (`Sitemap`
+    /// directives). When empty, `<permalink>/sitemap.xml` is advertised.
+    #[serde(default)]
+    pub sitemaps: Vec<String>,
 }
 
 impl TxtData {
@@ -1452,7 +1658,82 @@ impl TxtData {
     ///
     /// * `permalink` - The permalink of the website
     pub fn new(permalink: String) -> Self {
-        TxtData { permalink }
+        TxtData {
+            permalink,
+            ..TxtData::default()
+        }
+    }
+
+    /// Creates a new `TxtData` instance from metadata
+    ///
+    /// Reads `permalink`, `robots_allow` and `robots_disallow` (comma- or
+    /// newline-separated paths) and `robots_crawl_delay` (seconds).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a path does not start with `/` or the crawl
+    /// delay is not a non-negative integer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use staticdatagen::models::data::TxtData;
+    ///
+    /// let mut metadata = HashMap::new();
+    /// metadata.insert("robots_disallow".to_string(), "/drafts/, /search/".to_string());
+    /// metadata.insert("robots_crawl_delay".to_string(), "10".to_string());
+    ///
+    /// let txt = TxtData::from_metadata(&metadata).unwrap();
+    /// assert_eq!(txt.disallow, vec!["/drafts/", "/search/"]);
+    /// assert_eq!(txt.crawl_delay, Some(10));
+    /// ```
+    pub fn from_metadata(
+        metadata: &HashMap<String, String>,
+    ) -> Result<Self, DataError> {
+        let paths = |key: &str| -> Result<Vec<String>, DataError> {
+            metadata
+                .get(key)
+                .map_or("", String::as_str)
+                .split([',', '\n'])
+                .map(str::trim)
+                .filter(|path| !path.is_empty())
+                .map(|path| {
+                    if path.starts_with('/') {
+                        Ok(path.to_string())
+                    } else {
+                        Err(DataError::InvalidMetadata(format!(
+                            "Invalid {} path '{}': must start with '/'",
+                            key, path
+                        )))
+                    }
+                })
+                .collect()
+        };
+
+        let crawl_delay = match metadata
+            .get("robots_crawl_delay")
+            .map(|value| value.trim())
+        {
+            None | Some("") => None,
+            Some(value) => Some(value.parse().map_err(|_| {
+                DataError::InvalidMetadata(format!(
+                    "Invalid robots_crawl_delay '{}': expected seconds",
+                    value
+                ))
+            })?),
+        };
+
+        Ok(TxtData {
+            permalink: metadata
+                .get("permalink")
+                .cloned()
+                .unwrap_or_default(),
+            allow: paths("robots_allow")?,
+            disallow: paths("robots_disallow")?,
+            crawl_delay,
+            sitemaps: Vec::new(),
+        })
     }
 
     /// Validates the robots.txt data
@@ -1465,14 +1746,66 @@ pub fn validate(&self) -> Result<(), DataError> {
 
         validation::validate_url(&self.permalink)?;
 
+        if let Some(path) = self
+            .allow
+            .iter()
+            .chain(&self.disallow)
+            .find(|path| !path.starts_with('/'))
+        {
+            return Err(DataError::InvalidMetadata(format!(
+                "Invalid robots path '{}': must start with '/'",
+                path
+            )));
+        }
+
         Ok(())
     }
 
+    /// Returns the `Allow`, `Disallow` and `Crawl-delay` lines, each
+    /// terminated by a newline, or an empty string if there are none.
+    pub fn directives(&self) -> String {
+        let mut directives = String::new();
+        for path in &self.allow {
+            directives.push_str(&format!("Allow: {}\n", path));
+        }
+        for path in &self.disallow {
+            directives.push_str(&format!("Disallow: {}\n", path));
+        }
+        if let Some(delay) = self.crawl_delay {
+            directives.push_str(&format!("Crawl-delay: {}\n", delay));
+        }
+        directives
+    }
+
+    /// Returns a `Sitemap` line for each of [`TxtData::sitemaps`], or
+    /// for `<permalink>/sitemap.xml` if there are none, joined by
+    /// newlines.
+    pub fn sitemap_directives(&self) -> String {
+        if self.sitemaps.is_empty() {
+            return format!(
+                "Sitemap: {}/sitemap.xml",
+                self.permalink.trim_end_matches('/')
+            );
+        }
+        self.sitemaps
+            .iter()
+            .map(|url| format!("Sitemap: {}", url))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Generates the robots.txt content
+    ///
+    /// Allows everything when no `Allow` or `Disallow` rules are set.
     pub fn generate_content(&self) -> String {
+        let mut directives = self.directives();
+        if self.allow.is_empty() && self.disallow.is_empty() {
+            directives.insert_str(0, "Allow: /\n");
+        }
         format!(
-            "User-agent: *\nAllow: /\nSitemap: {}/sitemap.xml",
-            self.permalink.trim_end_matches('/')
+            "User-agent: *\n{}{}",
+            directives,
+            self.sitemap_directives()
         )
     }
 }
@@ -1794,19 +2127,9 @@ pub fn validate(&self) -> Result<(), DataError> {
             return Err(DataError::MissingField("expires".to_string()));
         }
 
-        // Validate contact URLs/emails
+        // Validate contact URIs (RFC 9116 section 2.5.3)
         for contact in &self.contact {
-            // Allow mailto: and https:// URLs
-            if contact.starts_with("mailto:") {
-                // Basic email validation for mailto: URLs
-                if !contact.contains('@') {
-                    return Err(DataError::InvalidUrl(
-                        "Invalid email in contact".to_string(),
-                    ));
-                }
-            } else {
-                validation::validate_url(contact)?;
-            }
+            validation::validate_security_contact(contact)?;
         }
 
         // Validate expiration date
@@ -1976,6 +2299,40 @@ fn test_cname_data() {
         }
     }
 
+    #[test]
+    fn test_alternate_data() {
+        let metadata = HashMap::from([
+            (
+                "amp_url".to_string(),
+                "https://example.com/amp/".to_string(),
+            ),
+            ("mobile".to_string(), "Yes".to_string()),
+        ]);
+        let alternates = AlternateData::from_metadata(&metadata)
+            .expect("Alternates should be valid");
+        assert!(alternates.mobile);
+        assert!(!alternates.is_empty());
+
+        assert!(AlternateData::from_metadata(&HashMap::new())
+            .expect("Empty metadata should be valid")
+            .is_empty());
+
+        for invalid in ["/amp/", "ftp://example.com/amp/", "amp"] {
+            let metadata = HashMap::from([(
+                "amp_url".to_string(),
+                invalid.to_string(),
+            )]);
+            assert!(
+                matches!(
+                    AlternateData::from_metadata(&metadata),
+                    Err(DataError::InvalidUrl(_))
+                ),
+                "AMP URL should be invalid: {}",
+                invalid
+            );
+        }
+    }
+
     #[test]
     fn test_page_data() {
         // Test valid case
@@ -2378,6 +2735,49 @@ fn test_txt_data() {
         );
     }
 
+    #[test]
+    fn test_txt_data_from_metadata() {
+        let metadata: HashMap<String, String> = [
+            ("permalink", "https://example.com"),
+            ("robots_allow", "/public/"),
+            ("robots_disallow", "/drafts/,\n/search/\n"),
+            ("robots_crawl_delay", " 5 "),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let txt = TxtData::from_metadata(&metadata).unwrap();
+        assert!(txt.validate().is_ok());
+        assert_eq!(txt.allow, vec!["/public/"]);
+        assert_eq!(txt.disallow, vec!["/drafts/", "/search/"]);
+        assert_eq!(txt.crawl_delay, Some(5));
+        assert_eq!(
+            txt.generate_content(),
+            "User-agent: *\nAllow: /public/\nDisallow: /drafts/\nDisallow: /search/\nCrawl-delay: 5\nSitemap: https://example.com/sitemap.xml"
+        );
+
+        let mut invalid = metadata.clone();
+        let _ = invalid.insert(
+            "robots_disallow".to_string(),
+            "drafts/".to_string(),
+        );
+        assert!(matches!(
+            TxtData::from_metadata(&invalid),
+            Err(DataError::InvalidMetadata(_))
+        ));
+
+        let mut invalid = metadata;
+        let _ = invalid.insert(
+            "robots_crawl_delay".to_string(),
+            "soon".to_string(),
+        );
+        assert!(matches!(
+            TxtData::from_metadata(&invalid),
+            Err(DataError::InvalidMetadata(_))
+        ));
+    }
+
     #[test]
     fn test_rss_data() {
         // Test valid case
@@ -2746,6 +3146,51 @@ fn test_manifest_data_field_lengths() {
         ));
     }
 
+    #[test]
+    fn test_security_data_contact_validation() {
+        for contact in [
+            "mailto:security@example.com",
+            "tel:+1-201-555-0123",
+            "https://example.com/security",
+        ] {
+            let data = SecurityData::new(
+                vec![contact.to_string()],
+                "2024-12-31T23:59:59Z".to_string(),
+            );
+            assert!(data.validate().is_ok(), "{}", contact);
+        }
+
+        for contact in [
+            "security@example.com",
+            "mailto:security",
+            "tel:",
+            "http://example.com/security",
+            "https://",
+            "ftp://example.com/security",
+        ] {
+            let data = SecurityData::new(
+                vec![contact.to_string()],
+                "2024-12-31T23:59:59Z".to_string(),
+            );
+            assert!(
+                matches!(
+                    data.validate(),
+                    Err(DataError::InvalidUrl(_))
+                ),
+                "{}",
+                contact
+            );
+        }
+
+        let error = validation::validate_security_contact(
+            "security@example.com",
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("mailto:security@example.com"));
+    }
+
     #[test]
     fn test_security_data_field_validation() {
         let mut data = SecurityData::new(
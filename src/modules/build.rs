@@ -0,0 +1,159 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Build identifiers
+//!
+//! A build id names the content a site was generated from, so a deployed
+//! site can be traced back to its sources and assets can be cache-busted
+//! per build. [`content_build_id`] derives the id from the source files
+//! alone, so identical inputs always yield the same id, and
+//! [`generate_build_json`] records it in `build.json` alongside the build
+//! time and the crate [`VERSION`].
+//!
+//! # Examples
+//!
+//! ```
+//! use staticdatagen::models::data::FileData;
+//! use staticdatagen::modules::build::content_build_id;
+//!
+//! let files = vec![FileData::new(
+//!     "index.md".to_string(),
+//!     "---\ntitle: Home\n---\nWelcome.".to_string(),
+//! )];
+//!
+//! let id = content_build_id(&files);
+//! assert_eq!(id.len(), 16);
+//! assert_eq!(id, content_build_id(&files));
+//! ```
+
+use crate::models::data::FileData;
+use crate::utilities::datetime::to_rfc3339;
+use crate::VERSION;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// The file holding the build information.
+pub const BUILD_JSON_FILE: &str = "build.json";
+
+/// The FNV-1a 64-bit offset basis.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The information written to `build.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// The build identifier.
+    pub build_id: String,
+    /// When the build ran, in RFC 3339 format.
+    pub timestamp: String,
+    /// The version of `staticdatagen` that ran the build.
+    pub version: String,
+}
+
+/// Derives a build id from the names and contents of `files`.
+///
+/// The id is the 64-bit FNV-1a hash of the files sorted by name, written
+/// as 16 lowercase hex digits. It does not depend on the order of `files`
+/// or on the toolchain, so identical sources give identical ids.
+pub fn content_build_id(files: &[FileData]) -> String {
+    let mut files: Vec<&FileData> = files.iter().collect();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let fields = files
+        .iter()
+        .flat_map(|file| [file.name.as_str(), file.content.as_str()]);
+    format!("{:016x}", fnv1a_fields(fields))
+}
+
+/// Hashes `fields` with 64-bit FNV-1a.
+///
+/// Each field is prefixed with its length, so `("ab", "c")` and
+/// `("a", "bc")` hash differently.
+pub(crate) fn fnv1a_fields<I, S>(fields: I) -> u64
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut hash = FNV_OFFSET_BASIS;
+    for field in fields {
+        let field = field.as_ref();
+        let length = field.len() as u64;
+        for byte in length.to_le_bytes().iter().chain(field.as_bytes())
+        {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    hash
+}
+
+/// Generates the `build.json` content for `build_id`, stamped with
+/// `timestamp`.
+///
+/// # Errors
+///
+/// Returns an error if the information cannot be serialized.
+pub fn generate_build_json(
+    build_id: &str,
+    timestamp: &OffsetDateTime,
+) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&BuildInfo {
+        build_id: build_id.to_string(),
+        timestamp: to_rfc3339(timestamp),
+        version: VERSION.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::datetime::parse_flexible;
+
+    fn file(name: &str, content: &str) -> FileData {
+        FileData::new(name.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn test_content_build_id_is_deterministic() {
+        let files = vec![file("a.md", "Alpha"), file("b.md", "Beta")];
+        let reversed =
+            vec![file("b.md", "Beta"), file("a.md", "Alpha")];
+
+        assert_eq!(
+            content_build_id(&files),
+            content_build_id(&reversed)
+        );
+        assert_eq!(content_build_id(&[]), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn test_content_build_id_changes_with_content() {
+        let id = content_build_id(&[file("a.md", "Alpha")]);
+
+        assert_ne!(id, content_build_id(&[file("a.md", "Alpha!")]));
+        assert_ne!(id, content_build_id(&[file("b.md", "Alpha")]));
+        assert_ne!(
+            content_build_id(&[file("ab", "c")]),
+            content_build_id(&[file("a", "bc")])
+        );
+    }
+
+    #[test]
+    fn test_generate_build_json() {
+        let timestamp = parse_flexible("2025-01-02").unwrap();
+        let json = generate_build_json("0123456789abcdef", &timestamp)
+            .unwrap();
+        let info: BuildInfo = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            info,
+            BuildInfo {
+                build_id: "0123456789abcdef".to_string(),
+                timestamp: "2025-01-02T00:00:00Z".to_string(),
+                version: VERSION.to_string(),
+            }
+        );
+    }
+}
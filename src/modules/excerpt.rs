@@ -0,0 +1,246 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Excerpt extraction
+//!
+//! Splits rendered HTML into an excerpt and the full body. Authors mark
+//! the cut point with a separator (`<!--more-->` by default, overridable
+//! through the `excerpt_separator` front matter key). When no separator
+//! is present, the first paragraph is used as the excerpt.
+
+/// The separator used when the page does not configure one.
+pub const DEFAULT_EXCERPT_SEPARATOR: &str = "<!--more-->";
+
+/// Block-level closing tags after which a space is inserted when the
+/// excerpt is flattened to text, so that adjacent words are not joined.
+const BLOCK_TAGS: [&str; 12] = [
+    "p",
+    "div",
+    "li",
+    "ul",
+    "ol",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "blockquote",
+];
+
+/// The result of splitting a rendered page at its excerpt separator.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Excerpt {
+    /// The HTML excerpt, or an empty string if none could be found.
+    pub excerpt: String,
+    /// The full HTML body with the separator removed.
+    pub body: String,
+    /// Whether the excerpt was delimited by an explicit separator.
+    pub explicit: bool,
+}
+
+/// Splits rendered HTML into an excerpt and the full body.
+///
+/// Everything before the first occurrence of `separator` becomes the
+/// excerpt and the separator itself is removed from the body. When the
+/// separator is absent (or empty), the first `<p>` element is used as the
+/// excerpt and the body is returned unchanged.
+///
+/// # Arguments
+///
+/// * `html` - The rendered HTML content.
+/// * `separator` - The excerpt separator (e.g. `<!--more-->`).
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::modules::excerpt::{extract_excerpt, DEFAULT_EXCERPT_SEPARATOR};
+///
+/// let html = "<p>Intro.</p>\n<!--more-->\n<p>Rest.</p>";
+/// let excerpt = extract_excerpt(html, DEFAULT_EXCERPT_SEPARATOR);
+///
+/// assert_eq!(excerpt.excerpt, "<p>Intro.</p>");
+/// assert_eq!(excerpt.body, "<p>Intro.</p>\n\n<p>Rest.</p>");
+/// assert!(excerpt.explicit);
+/// ```
+pub fn extract_excerpt(html: &str, separator: &str) -> Excerpt {
+    if !separator.is_empty() {
+        if let Some(index) = html.find(separator) {
+            let (before, after) = html.split_at(index);
+            let after = &after[separator.len()..];
+            return Excerpt {
+                excerpt: before.trim().to_string(),
+                body: format!("{}{}", before, after),
+                explicit: true,
+            };
+        }
+    }
+
+    Excerpt {
+        excerpt: first_paragraph(html).unwrap_or_default(),
+        body: html.to_string(),
+        explicit: false,
+    }
+}
+
+/// Returns the first `<p>` element of the HTML, including its tags.
+fn first_paragraph(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let mut offset = 0;
+
+    while let Some(pos) = lower[offset..].find("<p") {
+        let start = offset + pos;
+        // Skip tags such as `<pre>` or `<param>` that share the prefix.
+        let next = lower[start + 2..].chars().next();
+        if matches!(
+            next,
+            Some('>') | Some(' ') | Some('\t') | Some('\n')
+        ) {
+            let end = lower[start..]
+                .find("</p>")
+                .map(|end| start + end + "</p>".len())?;
+            return Some(html[start..end].to_string());
+        }
+        offset = start + 2;
+    }
+
+    None
+}
+
+/// Flattens an HTML excerpt into plain text suitable for feeds.
+///
+/// Tags are removed, a space is kept wherever a block element or line
+/// break separated two words, common entities are decoded and runs of
+/// whitespace are collapsed.
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::modules::excerpt::excerpt_text;
+///
+/// assert_eq!(
+///     excerpt_text("<p>Hello <em>big</em></p><p>world &amp; co</p>"),
+///     "Hello big world & co"
+/// );
+/// ```
+pub fn excerpt_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut tag = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' if !in_tag => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                if is_word_break(&tag) {
+                    text.push(' ');
+                }
+            }
+            _ if in_tag => tag.push(c),
+            _ => text.push(c),
+        }
+    }
+
+    let decoded = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns `true` if the tag separates words when rendered.
+fn is_word_break(tag: &str) -> bool {
+    let name = tag
+        .trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    name == "br" || BLOCK_TAGS.contains(&name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_excerpt_with_default_separator() {
+        let html =
+            "<p>One.</p>\n<p>Two.</p>\n<!--more-->\n<p>Three.</p>";
+        let excerpt = extract_excerpt(html, DEFAULT_EXCERPT_SEPARATOR);
+
+        assert!(excerpt.explicit);
+        assert_eq!(excerpt.excerpt, "<p>One.</p>\n<p>Two.</p>");
+        assert!(!excerpt.body.contains(DEFAULT_EXCERPT_SEPARATOR));
+        assert!(excerpt.body.contains("<p>Three.</p>"));
+    }
+
+    #[test]
+    fn test_extract_excerpt_with_custom_separator() {
+        let html = "<p>Intro.</p><!-- cut --><p>Rest.</p>";
+        let excerpt = extract_excerpt(html, "<!-- cut -->");
+
+        assert!(excerpt.explicit);
+        assert_eq!(excerpt.excerpt, "<p>Intro.</p>");
+        assert_eq!(excerpt.body, "<p>Intro.</p><p>Rest.</p>");
+    }
+
+    #[test]
+    fn test_extract_excerpt_falls_back_to_first_paragraph() {
+        let html = "<h1>Title</h1>\n<pre>code</pre>\n<p class=\"lead\">Lead.</p>\n<p>More.</p>";
+        let excerpt = extract_excerpt(html, DEFAULT_EXCERPT_SEPARATOR);
+
+        assert!(!excerpt.explicit);
+        assert_eq!(excerpt.excerpt, "<p class=\"lead\">Lead.</p>");
+        assert_eq!(excerpt.body, html);
+    }
+
+    #[test]
+    fn test_extract_excerpt_without_paragraph() {
+        let excerpt = extract_excerpt(
+            "<h1>Title</h1>",
+            DEFAULT_EXCERPT_SEPARATOR,
+        );
+
+        assert!(excerpt.excerpt.is_empty());
+        assert_eq!(excerpt.body, "<h1>Title</h1>");
+    }
+
+    #[test]
+    fn test_extract_excerpt_empty_separator() {
+        let excerpt = extract_excerpt("<p>Only.</p>", "");
+
+        assert!(!excerpt.explicit);
+        assert_eq!(excerpt.excerpt, "<p>Only.</p>");
+    }
+
+    #[test]
+    fn test_excerpt_text_keeps_word_boundaries() {
+        assert_eq!(
+            excerpt_text("<p>end</p><p>start</p>line<br/>break"),
+            "end start line break"
+        );
+        assert_eq!(
+            excerpt_text("<p>in<strong>line</strong> tags</p>"),
+            "inline tags"
+        );
+    }
+
+    #[test]
+    fn test_excerpt_text_decodes_entities() {
+        assert_eq!(
+            excerpt_text("<p>&lt;tag&gt; &amp;amp; &quot;q&quot;</p>"),
+            "<tag> &amp; \"q\""
+        );
+    }
+}
@@ -6,14 +6,24 @@
 //! This module provides functions for generating various data files including
 //! CNAME records, humans.txt, manifests, news sitemaps, robots.txt, and RSS feeds.
 
-use crate::models::data::validation::sanitize_path;
+use crate::models::data::validation::{
+    sanitize_path, validate_security_contact,
+};
 use crate::models::data::{
-    CnameData, HumansData, ManifestData, NewsData, NewsVisitOptions,
-    SecurityData, TxtData,
+    AlternateData, CnameData, HumansData, ManifestData, NewsData,
+    NewsVisitOptions, SecurityData, TxtData,
+};
+use crate::utilities::permalink::{
+    resolve_url, TrailingSlash, UrlOptions,
 };
+use crate::utilities::url::UrlMode;
 use serde_json::{json, Map};
 use sitemap_gen::SiteMapData;
-use std::{fs, io, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::Path,
+};
 use xml::writer::{EmitterConfig, XmlEvent};
 
 /// Reusable XML generation utility
@@ -128,15 +138,29 @@ pub fn cname(options: &CnameData) -> String {
 /// assert!(content.contains("Expires:"));
 /// ```
 pub fn security(options: &SecurityData) -> String {
+    // Keep only RFC 9116 contacts: `mailto:`, `tel:` or `https:` URIs
+    let contacts: Vec<&str> = options
+        .contact
+        .iter()
+        .map(|contact| contact.trim())
+        .filter(|contact| match validate_security_contact(contact) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Dropping security.txt contact: {}", e);
+                false
+            }
+        })
+        .collect();
+
     // Verify required fields are present
-    if options.contact.is_empty() || options.expires.is_empty() {
+    if contacts.is_empty() || options.expires.is_empty() {
         return String::new();
     }
 
     let mut content = String::with_capacity(500);
 
     // Add required fields
-    for contact in &options.contact {
+    for contact in contacts {
         content.push_str(&format!("Contact: {}\n", contact));
     }
     content.push_str(&format!("Expires: {}\n", options.expires));
@@ -332,52 +356,150 @@ pub fn news_sitemap(options: NewsData) -> String {
 }
 
 /// Helper function to visit directories for sitemap generation
+///
+/// A missing `base_dir` yields no URLs unless `strict` is set. Subdirectories
+/// that vanish or cannot be read (e.g. permission denied) are logged and
+/// skipped so that one unreadable branch does not abort the whole walk.
+/// Directories beyond [`SitemapOptions::max_depth`] are skipped.
+///
+/// Returns the number of directories skipped for their depth.
 fn visit_dirs(
     base_dir: &Path,
-    dir: &Path,
-    base_url: &str,
-    changefreq: &str,
-    lastmod: &str,
+    entry: &UrlEntry<'_>,
     urls: &mut Vec<String>,
-) -> io::Result<()> {
-    let mut stack = vec![dir.to_path_buf()];
+) -> io::Result<usize> {
+    let mut stack = vec![(base_dir.to_path_buf(), 0)];
+    let mut skipped = 0;
+
+    while let Some((current_dir, depth)) = stack.pop() {
+        let entries = match fs::read_dir(&current_dir) {
+            Ok(entries) => entries,
+            Err(e) if current_dir == base_dir => {
+                if entry.options.strict
+                    || e.kind() != io::ErrorKind::NotFound
+                {
+                    return Err(e);
+                }
+                log::warn!(
+                    "Sitemap directory {} does not exist; no URLs generated",
+                    base_dir.display()
+                );
+                return Ok(0);
+            }
+            Err(e) if is_skippable(&e) => {
+                log::warn!(
+                    "Skipping {} in sitemap: {}",
+                    current_dir.display(),
+                    e
+                );
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
 
-    while let Some(current_dir) = stack.pop() {
-        for entry in fs::read_dir(&current_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+        for dir_entry in entries {
+            let path = match dir_entry {
+                Ok(dir_entry) => dir_entry.path(),
+                Err(e) if is_skippable(&e) => {
+                    log::warn!(
+                        "Skipping entry in {}: {}",
+                        current_dir.display(),
+                        e
+                    );
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
 
             if path.is_dir() {
-                // Push subdirectories onto the stack
-                stack.push(path);
-            } else if let Some(file_name) = path.file_name() {
-                if file_name == "index.html" {
-                    // Process the index.html file
-                    process_file(
-                        &path, base_dir, base_url, changefreq, lastmod,
-                        urls,
-                    )?;
+                if entry
+                    .options
+                    .max_depth
+                    .map_or(false, |max_depth| depth >= max_depth)
+                {
+                    skipped += 1;
+                } else {
+                    // Push subdirectories onto the stack
+                    stack.push((path, depth + 1));
                 }
+            } else if is_page(&path, &entry.options.urls) {
+                process_file(&path, base_dir, entry, urls)?;
             }
         }
     }
 
-    Ok(())
+    Ok(skipped)
+}
+
+/// Returns `true` if `path` is a page: a directory index, or, unless every
+/// page is a directory index ([`TrailingSlash::Always`]), any `.html`
+/// file.
+fn is_page(path: &Path, urls: &UrlOptions) -> bool {
+    let file_name = match path.file_name() {
+        Some(file_name) => file_name.to_string_lossy(),
+        None => return false,
+    };
+    file_name == urls.index_file.as_str()
+        || (urls.policy != TrailingSlash::Always
+            && path
+                .extension()
+                .map_or(false, |ext| ext.eq_ignore_ascii_case("html")))
+}
+
+/// Returns `true` for errors that only affect a single directory entry.
+fn is_skippable(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied
+    )
+}
+
+/// The per-sitemap values shared by every `<url>` entry.
+struct UrlEntry<'a> {
+    url_mode: UrlMode,
+    changefreq: String,
+    lastmod: &'a str,
+    options: &'a SitemapOptions,
 }
 
 fn process_file(
     file_path: &Path,
     base_dir: &Path,
-    base_url: &str,
-    changefreq: &str,
-    lastmod: &str,
+    entry: &UrlEntry<'_>,
     urls: &mut Vec<String>,
 ) -> io::Result<()> {
     if let Ok(stripped_path) = file_path.strip_prefix(base_dir) {
-        if let Some(url) = stripped_path.to_str() {
+        // Pages are keyed by their output directory: the parent of a
+        // directory index, or the flat file's path without extension.
+        let is_index =
+            stripped_path.file_name().map_or(false, |name| {
+                name.to_string_lossy()
+                    == entry.options.urls.index_file.as_str()
+            });
+        let key = if is_index {
+            stripped_path.parent().map(Path::to_path_buf)
+        } else {
+            Some(stripped_path.with_extension(""))
+        };
+        let key = key.as_deref().and_then(Path::to_str);
+        let written = stripped_path.to_str();
+        if let (Some(directory), Some(written)) = (key, written) {
+            if entry.options.excluded.contains(directory)
+                || is_disallowed(directory, &entry.options.disallowed)
+            {
+                return Ok(());
+            }
+            // The URL of the file as written, shared with navigation.
+            let url = resolve_url(
+                &written.replace('\\', "/"),
+                &entry.options.urls,
+            );
             let mut buffer = Vec::new();
+            // The entry is embedded in the `<urlset>` document, so it
+            // must not carry its own XML declaration.
             let mut writer = EmitterConfig::new()
                 .perform_indent(true)
+                .write_document_declaration(false)
                 .create_writer(&mut buffer);
 
             writer
@@ -386,14 +508,22 @@ fn process_file(
             generate_xml_element(
                 &mut writer,
                 "changefreq",
-                changefreq,
+                &entry.changefreq,
             )?;
-            generate_xml_element(&mut writer, "lastmod", lastmod)?;
             generate_xml_element(
                 &mut writer,
-                "loc",
-                &format!("{}/{}", base_url, url),
+                "lastmod",
+                entry.lastmod,
             )?;
+            let loc = entry.url_mode.resolve(&url).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, e)
+            })?;
+            generate_xml_element(&mut writer, "loc", &loc)?;
+            if let Some(alternates) =
+                entry.options.alternates.get(directory)
+            {
+                write_alternates(&mut writer, alternates)?;
+            }
             writer
                 .write(XmlEvent::end_element())
                 .map_err(to_io_error)?; // close <url>
@@ -405,6 +535,43 @@ fn process_file(
     Ok(())
 }
 
+/// Returns `true` if the page in `directory` falls under one of the
+/// robots.txt `disallowed` path prefixes.
+fn is_disallowed(directory: &str, disallowed: &[String]) -> bool {
+    let path = match directory.replace('\\', "/").as_str() {
+        "" => "/".to_string(),
+        directory => format!("/{}/", directory),
+    };
+    disallowed.iter().any(|prefix| {
+        !prefix.is_empty() && path.starts_with(prefix.as_str())
+    })
+}
+
+/// Writes the `<mobile:mobile/>` marker and the AMP alternate link of a
+/// page.
+fn write_alternates<W: io::Write>(
+    writer: &mut xml::writer::EventWriter<W>,
+    alternates: &AlternateData,
+) -> io::Result<()> {
+    if !alternates.amp_url.is_empty() {
+        writer
+            .write(
+                XmlEvent::start_element("xhtml:link")
+                    .attr("rel", "amphtml")
+                    .attr("href", &alternates.amp_url),
+            )
+            .map_err(to_io_error)?;
+        writer.write(XmlEvent::end_element()).map_err(to_io_error)?;
+    }
+    if alternates.mobile {
+        writer
+            .write(XmlEvent::start_element("mobile:mobile"))
+            .map_err(to_io_error)?;
+        writer.write(XmlEvent::end_element()).map_err(to_io_error)?;
+    }
+    Ok(())
+}
+
 /// Helper function to convert `xml::writer::Error` to `std::io::Error`
 fn to_io_error(err: xml::writer::Error) -> io::Error {
     io::Error::new(io::ErrorKind::Other, err)
@@ -471,11 +638,96 @@ pub fn generate_news_sitemap_entry(
     )
 }
 
+/// Options controlling how the sitemap is generated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SitemapOptions {
+    /// How `<loc>` paths are written, and the index file name that marks
+    /// a page directory.
+    pub urls: UrlOptions,
+    /// Whether a missing output directory is an error rather than an
+    /// empty sitemap.
+    pub strict: bool,
+    /// Mobile and AMP variants, keyed by the page's output directory
+    /// relative to the site root (`""` for the home page).
+    pub alternates: HashMap<String, AlternateData>,
+    /// Pages left out of the sitemap (e.g. `noindex` pages), keyed like
+    /// [`SitemapOptions::alternates`].
+    pub excluded: HashSet<String>,
+    /// Path prefixes disallowed by robots.txt (e.g. `/drafts/`); pages
+    /// whose URL path starts with one of them are left out.
+    pub disallowed: Vec<String>,
+    /// The deepest subdirectory level searched for pages, the site root
+    /// being level `0`. Deeper directories are skipped; `None` searches
+    /// the whole tree.
+    pub max_depth: Option<usize>,
+    /// How `<loc>` URLs are written. `None` makes them absolute under the
+    /// `loc` of the [`SiteMapData`].
+    pub url_mode: Option<UrlMode>,
+}
+
 /// Generates a sitemap based on provided configuration
+///
+/// A missing `dir` produces an empty `<urlset>` rather than an error; use
+/// [`sitemap_with_policy`] with `strict` set to fail instead.
 pub fn sitemap(
     options: SiteMapData,
     dir: &Path,
 ) -> Result<String, io::Error> {
+    sitemap_with_policy(options, dir, TrailingSlash::Always, false)
+}
+
+/// Generates a sitemap whose `<loc>` entries follow the given
+/// [`TrailingSlash`] policy.
+///
+/// Every `index.html` found under `dir`, and with any other policy than
+/// [`TrailingSlash::Always`] every flat `.html` file, is listed at the
+/// URL [`resolve_url`] gives the file, so the sitemap agrees with the
+/// links produced by the navigation generator.
+///
+/// When `strict` is `false`, a missing `dir` is treated as a site with no
+/// URLs. Unreadable subdirectories are always logged and skipped.
+pub fn sitemap_with_policy(
+    options: SiteMapData,
+    dir: &Path,
+    policy: TrailingSlash,
+    strict: bool,
+) -> Result<String, io::Error> {
+    sitemap_with_options(
+        options,
+        dir,
+        &SitemapOptions {
+            urls: UrlOptions {
+                policy,
+                ..UrlOptions::default()
+            },
+            strict,
+            ..SitemapOptions::default()
+        },
+    )
+}
+
+/// Generates a sitemap using the given [`SitemapOptions`].
+///
+/// This behaves like [`sitemap_with_policy`] and additionally emits an
+/// `<xhtml:link rel="amphtml">` alternate and a `<mobile:mobile/>` marker
+/// for pages listed in [`SitemapOptions::alternates`], and leaves out the
+/// pages listed in [`SitemapOptions::excluded`] or disallowed by
+/// [`SitemapOptions::disallowed`].
+pub fn sitemap_with_options(
+    options: SiteMapData,
+    dir: &Path,
+    sitemap_options: &SitemapOptions,
+) -> Result<String, io::Error> {
+    let urls = sitemap_urls(options, dir, sitemap_options)?;
+    Ok(render_urlset(&urls))
+}
+
+/// Collects the `<url>` entries of the sitemap of `dir`.
+fn sitemap_urls(
+    options: SiteMapData,
+    dir: &Path,
+    sitemap_options: &SitemapOptions,
+) -> Result<Vec<String>, io::Error> {
     let dir_str = dir.to_str().ok_or_else(|| {
         io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -484,17 +736,34 @@ pub fn sitemap(
     })?;
     let base_dir =
         sanitize_path(dir_str).expect("Failed to sanitize path");
+    let url_mode = sitemap_options
+        .url_mode
+        .clone()
+        .unwrap_or_else(|| UrlMode::Absolute(options.loc.to_string()));
+    url_mode
+        .validate()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let entry = UrlEntry {
+        url_mode,
+        changefreq: options.changefreq.to_string(),
+        lastmod: &options.lastmod,
+        options: sitemap_options,
+    };
     let mut urls = vec![];
-    visit_dirs(
-        &base_dir,
-        &base_dir,
-        options.loc.as_str(),
-        &options.changefreq.to_string(),
-        &options.lastmod,
-        &mut urls,
-    )?;
-
-    Ok(format!(
+    let skipped = visit_dirs(&base_dir, &entry, &mut urls)?;
+    if skipped > 0 {
+        log::warn!(
+            "Skipped {} directories deeper than {} levels in sitemap",
+            skipped,
+            sitemap_options.max_depth.unwrap_or_default()
+        );
+    }
+    Ok(urls)
+}
+
+/// Wraps sitemap `<url>` entries in the `<urlset>` document.
+fn render_urlset(urls: &[String]) -> String {
+    format!(
         r#"<?xml version="1.0" encoding="UTF-8"?>
 <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
         xmlns:news="http://www.google.com/schemas/sitemap-news/0.9"
@@ -504,12 +773,255 @@ pub fn sitemap(
         xmlns:video="http://www.google.com/schemas/sitemap-video/1.1">
     {}</urlset>"#,
         urls.join("\n")
-    ))
+    )
+}
+
+/// Generates the sitemap of `dir` like [`sitemap_with_options`] and
+/// writes it to `output`, unless the sitemap already there lists the
+/// same URL set (see [`same_url_set`]).
+///
+/// An unchanged sitemap is left alone and keeps its modification time.
+/// Nothing but `output` is written.
+///
+/// Returns `true` if the sitemap was rewritten.
+///
+/// # Errors
+///
+/// Returns an error if the sitemap cannot be generated or written.
+pub fn write_sitemap_if_changed(
+    options: SiteMapData,
+    dir: &Path,
+    sitemap_options: &SitemapOptions,
+    output: &Path,
+) -> Result<bool, io::Error> {
+    let sitemap =
+        render_urlset(&sitemap_urls(options, dir, sitemap_options)?);
+    if fs::read_to_string(output)
+        .map_or(false, |previous| same_url_set(&previous, &sitemap))
+    {
+        return Ok(false);
+    }
+
+    fs::write(output, sitemap)?;
+    Ok(true)
+}
+
+/// Returns whether two sitemaps list the same `<url>` entries, in any
+/// order.
+///
+/// # Example
+///
+/// ```
+/// use staticdatagen::modules::json::same_url_set;
+///
+/// let a = "<urlset><url><loc>/a/</loc></url><url><loc>/b/</loc></url></urlset>";
+/// let b = "<urlset><url><loc>/b/</loc></url><url><loc>/a/</loc></url></urlset>";
+/// assert!(same_url_set(a, b));
+/// assert!(!same_url_set(a, "<urlset><url><loc>/a/</loc></url></urlset>"));
+/// ```
+pub fn same_url_set(a: &str, b: &str) -> bool {
+    fn entries(sitemap: &str) -> Vec<&str> {
+        let mut entries: Vec<&str> = sitemap
+            .split("<url>")
+            .skip(1)
+            .map(|entry| {
+                entry.split("</url>").next().unwrap_or_default().trim()
+            })
+            .collect();
+        entries.sort_unstable();
+        entries
+    }
+    entries(a) == entries(b)
+}
+
+/// The namespace of the sitemap protocol.
+pub const SITEMAP_NAMESPACE: &str =
+    "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// The most URLs a single sitemap may list.
+pub const MAX_SITEMAP_URLS: usize = 50_000;
+
+/// The longest `<loc>` URL a sitemap may hold, in characters.
+pub const MAX_SITEMAP_LOC_LENGTH: usize = 2_048;
+
+/// The values accepted by `<changefreq>`.
+const CHANGEFREQ_VALUES: [&str; 7] = [
+    "always", "hourly", "daily", "weekly", "monthly", "yearly", "never",
+];
+
+/// Checks a generated sitemap against the rules of the sitemap protocol
+/// that its XSD enforces.
+///
+/// The document must be well-formed XML whose root is a `<urlset>` in
+/// [`SITEMAP_NAMESPACE`], with at most [`MAX_SITEMAP_URLS`] `<url>`
+/// entries. Each entry needs exactly one `<loc>` holding an absolute
+/// http(s) URL of at most [`MAX_SITEMAP_LOC_LENGTH`] characters; a
+/// `<priority>` must be a number from 0.0 to 1.0, and a `<changefreq>`
+/// one of the protocol's values. Elements of other namespaces, such as
+/// `<xhtml:link>` or `<news:news>`, are not checked.
+///
+/// # Errors
+///
+/// Returns every problem found, each naming the entry it belongs to. A
+/// document that does not parse, or has the wrong root, yields a single
+/// problem.
+///
+/// # Example
+///
+/// ```
+/// use staticdatagen::modules::json::validate_sitemap_xml;
+///
+/// let xml = r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+///     <url><loc>https://example.com/</loc><priority>0.5</priority></url>
+/// </urlset>"#;
+/// assert!(validate_sitemap_xml(xml).is_ok());
+///
+/// let xml = xml.replace("0.5", "2").replace("https://example.com/", "/");
+/// assert_eq!(validate_sitemap_xml(&xml).unwrap_err().len(), 2);
+/// ```
+pub fn validate_sitemap_xml(xml: &str) -> Result<(), Vec<String>> {
+    use xml::reader::{EventReader, XmlEvent as ReadEvent};
+
+    let mut problems = Vec::new();
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+    let mut urls = 0;
+    let mut locs = 0;
+
+    for event in EventReader::from_str(xml) {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => return Err(vec![format!("Invalid XML: {}", e)]),
+        };
+        match event {
+            ReadEvent::StartElement { name, .. } => {
+                let in_sitemap = name.namespace.as_deref()
+                    == Some(SITEMAP_NAMESPACE);
+                if path.is_empty()
+                    && !(in_sitemap && name.local_name == "urlset")
+                {
+                    return Err(vec![format!(
+                        "The root element is <{}>, expected <urlset> in the {} namespace",
+                        name, SITEMAP_NAMESPACE
+                    )]);
+                }
+                if in_sitemap
+                    && path.len() == 1
+                    && name.local_name == "url"
+                {
+                    urls += 1;
+                    locs = 0;
+                }
+                // Elements of other namespaces are not checked.
+                path.push(if in_sitemap {
+                    name.local_name
+                } else {
+                    String::new()
+                });
+                text.clear();
+            }
+            ReadEvent::Characters(chars) | ReadEvent::CData(chars) => {
+                text.push_str(&chars)
+            }
+            ReadEvent::EndElement { .. } => {
+                let element = path.pop().unwrap_or_default();
+                let in_url = path.len() == 2 && path[1] == "url";
+                let value = text.trim();
+                match element.as_str() {
+                    "loc" if in_url => {
+                        locs += 1;
+                        if let Err(problem) = check_loc(value) {
+                            problems.push(format!(
+                                "URL {}: {}",
+                                urls, problem
+                            ));
+                        }
+                    }
+                    "priority"
+                        if in_url
+                            && !value
+                                .parse::<f64>()
+                                .map_or(false, |p| {
+                                    (0.0..=1.0).contains(&p)
+                                }) =>
+                    {
+                        problems.push(format!(
+                            "URL {}: <priority> '{}' is not a number from 0.0 to 1.0",
+                            urls, value
+                        ));
+                    }
+                    "changefreq"
+                        if in_url
+                            && !CHANGEFREQ_VALUES.contains(&value) =>
+                    {
+                        problems.push(format!(
+                            "URL {}: <changefreq> '{}' is not one of {}",
+                            urls,
+                            value,
+                            CHANGEFREQ_VALUES.join(", ")
+                        ));
+                    }
+                    "url" if path.len() == 1 && locs != 1 => {
+                        problems.push(format!(
+                            "URL {}: expected one <loc>, found {}",
+                            urls, locs
+                        ));
+                    }
+                    _ => {}
+                }
+                text.clear();
+            }
+            _ => {}
+        }
+    }
+
+    if urls > MAX_SITEMAP_URLS {
+        problems.push(format!(
+            "The sitemap lists {} URLs, more than the limit of {}",
+            urls, MAX_SITEMAP_URLS
+        ));
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
+
+/// Checks that a `<loc>` value is an absolute http(s) URL within the
+/// length limit.
+fn check_loc(loc: &str) -> Result<(), String> {
+    if loc.chars().count() > MAX_SITEMAP_LOC_LENGTH {
+        return Err(format!(
+            "<loc> is longer than {} characters",
+            MAX_SITEMAP_LOC_LENGTH
+        ));
+    }
+    match url::Url::parse(loc) {
+        Ok(url)
+            if matches!(url.scheme(), "http" | "https")
+                && url.has_host() =>
+        {
+            Ok(())
+        }
+        _ => Err(format!(
+            "<loc> '{}' is not an absolute http(s) URL",
+            loc
+        )),
+    }
 }
 
 /// Generates robots.txt content
+///
+/// Every sitemap in [`TxtData::sitemaps`] gets a `Sitemap` line, so
+/// crawlers find the news sitemap as well as the main one.
 pub fn txt(options: &TxtData) -> String {
-    format!("User-agent: *\nSitemap: {}/sitemap.xml", options.permalink)
+    format!(
+        "User-agent: *\n{}{}",
+        options.directives(),
+        options.sitemap_directives()
+    )
 }
 
 #[cfg(test)]
@@ -529,12 +1041,37 @@ fn test_cname_generation() {
     fn test_txt_generation() {
         let options = TxtData {
             permalink: "https://example.com".to_string(),
+            ..TxtData::default()
         };
         let content = txt(&options);
         assert_eq!(
             content,
             "User-agent: *\nSitemap: https://example.com/sitemap.xml"
         );
+
+        let options = TxtData {
+            permalink: "https://example.com".to_string(),
+            disallow: vec!["/drafts/".to_string()],
+            crawl_delay: Some(10),
+            ..TxtData::default()
+        };
+        assert_eq!(
+            txt(&options),
+            "User-agent: *\nDisallow: /drafts/\nCrawl-delay: 10\nSitemap: https://example.com/sitemap.xml"
+        );
+
+        let options = TxtData {
+            permalink: "https://example.com".to_string(),
+            sitemaps: vec![
+                "https://example.com/sitemap.xml".to_string(),
+                "https://example.com/news-sitemap.xml".to_string(),
+            ],
+            ..TxtData::default()
+        };
+        assert_eq!(
+            txt(&options),
+            "User-agent: *\nSitemap: https://example.com/sitemap.xml\nSitemap: https://example.com/news-sitemap.xml"
+        );
     }
 
     #[test]
@@ -665,6 +1202,473 @@ fn test_security_txt_multiple_contacts() {
         );
     }
 
+    #[test]
+    fn test_security_txt_drops_invalid_contacts() {
+        let options = SecurityData {
+            contact: vec![
+                "security@example.com".to_string(),
+                "http://example.com/security".to_string(),
+                " mailto:security@example.com ".to_string(),
+                "tel:call-us".to_string(),
+            ],
+            expires: "2024-12-31T23:59:59Z".to_string(),
+            ..Default::default()
+        };
+
+        let content = security(&options);
+        assert_eq!(content.matches("Contact:").count(), 1);
+        assert!(
+            content.contains("Contact: mailto:security@example.com\n")
+        );
+
+        let options = SecurityData {
+            contact: vec!["security@example.com".to_string()],
+            ..options
+        };
+        assert!(security(&options).is_empty());
+    }
+
+    #[test]
+    fn test_sitemap_with_policy() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        fs::create_dir_all(dir.path().join("about"))
+            .expect("Failed to create directory");
+        fs::write(dir.path().join("index.html"), "")
+            .expect("Failed to write file");
+        fs::write(dir.path().join("about/index.html"), "")
+            .expect("Failed to write file");
+        fs::write(dir.path().join("contact.html"), "")
+            .expect("Failed to write file");
+
+        let options = SiteMapData {
+            changefreq: sitemap_gen::ChangeFreq::Weekly,
+            lastmod: "2025-01-01".to_string(),
+            loc: url::Url::parse("https://example.com")
+                .expect("Valid URL"),
+        };
+
+        let always = sitemap_with_policy(
+            options.clone(),
+            dir.path(),
+            TrailingSlash::Always,
+            true,
+        )
+        .expect("Sitemap generation should succeed");
+        assert!(always
+            .contains("<loc>https://example.com/index.html</loc>"));
+        assert!(always.contains(
+            "<loc>https://example.com/about/index.html</loc>"
+        ));
+
+        assert!(!always.contains("contact.html"));
+
+        // Flat pages are listed as written; a directory index is still
+        // listed at its own file.
+        let never = sitemap_with_policy(
+            options,
+            dir.path(),
+            TrailingSlash::Never,
+            true,
+        )
+        .expect("Sitemap generation should succeed");
+        assert!(
+            never.contains("<loc>https://example.com/index.html</loc>")
+        );
+        assert!(never
+            .contains("<loc>https://example.com/contact.html</loc>"));
+        assert!(never.contains(
+            "<loc>https://example.com/about/index.html</loc>"
+        ));
+    }
+
+    #[test]
+    fn test_sitemap_url_options() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        fs::create_dir_all(dir.path().join("about"))
+            .expect("Failed to create directory");
+        fs::write(dir.path().join("about/index.htm"), "")
+            .expect("Failed to write file");
+        fs::write(dir.path().join("index.html"), "")
+            .expect("Failed to write file");
+        let options = SiteMapData {
+            changefreq: sitemap_gen::ChangeFreq::Weekly,
+            lastmod: "2025-01-01".to_string(),
+            loc: url::Url::parse("https://example.com")
+                .expect("Valid URL"),
+        };
+        let sitemap_options = SitemapOptions {
+            urls: UrlOptions {
+                pretty_urls: true,
+                index_file: "index.htm".to_string(),
+                ..UrlOptions::default()
+            },
+            ..SitemapOptions::default()
+        };
+
+        let xml =
+            sitemap_with_options(options, dir.path(), &sitemap_options)
+                .expect("Sitemap generation should succeed");
+        assert!(xml.contains("<loc>https://example.com/about/</loc>"));
+        assert!(!xml.contains("<loc>https://example.com/</loc>"));
+    }
+
+    #[test]
+    fn test_sitemap_with_alternates() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        for page in ["amp", "mobile", "plain"] {
+            fs::create_dir_all(dir.path().join(page))
+                .expect("Failed to create directory");
+            fs::write(dir.path().join(page).join("index.html"), "")
+                .expect("Failed to write file");
+        }
+
+        let mut sitemap_options = SitemapOptions::default();
+        let _ = sitemap_options.alternates.insert(
+            "amp".to_string(),
+            AlternateData {
+                amp_url: "https://example.com/amp/amp/".to_string(),
+                mobile: false,
+            },
+        );
+        let _ = sitemap_options.alternates.insert(
+            "mobile".to_string(),
+            AlternateData {
+                amp_url: String::new(),
+                mobile: true,
+            },
+        );
+
+        let options = SiteMapData {
+            changefreq: sitemap_gen::ChangeFreq::Weekly,
+            lastmod: "2025-01-01".to_string(),
+            loc: url::Url::parse("https://example.com")
+                .expect("Valid URL"),
+        };
+        let xml =
+            sitemap_with_options(options, dir.path(), &sitemap_options)
+                .expect("Sitemap generation should succeed");
+
+        assert!(xml.contains(
+            r#"<xhtml:link rel="amphtml" href="https://example.com/amp/amp/" />"#
+        ));
+        assert_eq!(xml.matches("<mobile:mobile />").count(), 1);
+        assert_eq!(xml.matches("<xhtml:link").count(), 1);
+    }
+
+    #[test]
+    fn test_sitemap_excludes_pages() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(dir.path().join("index.html"), "")
+            .expect("Failed to write file");
+        for page in ["public", "thanks"] {
+            fs::create_dir_all(dir.path().join(page))
+                .expect("Failed to create directory");
+            fs::write(dir.path().join(page).join("index.html"), "")
+                .expect("Failed to write file");
+        }
+
+        let mut sitemap_options = SitemapOptions::default();
+        let _ = sitemap_options.excluded.insert("thanks".to_string());
+        let _ = sitemap_options.excluded.insert(String::new());
+
+        let options = SiteMapData {
+            changefreq: sitemap_gen::ChangeFreq::Weekly,
+            lastmod: "2025-01-01".to_string(),
+            loc: url::Url::parse("https://example.com")
+                .expect("Valid URL"),
+        };
+        let xml =
+            sitemap_with_options(options, dir.path(), &sitemap_options)
+                .expect("Sitemap generation should succeed");
+
+        assert!(xml.contains("https://example.com/public/"));
+        assert!(!xml.contains("thanks"));
+        assert_eq!(xml.matches("<loc>").count(), 1);
+    }
+
+    #[test]
+    fn test_sitemap_excludes_disallowed_pages() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(dir.path().join("index.html"), "")
+            .expect("Failed to write file");
+        for page in ["drafts", "drafts/post", "blog", "search-results"]
+        {
+            fs::create_dir_all(dir.path().join(page))
+                .expect("Failed to create directory");
+            fs::write(dir.path().join(page).join("index.html"), "")
+                .expect("Failed to write file");
+        }
+
+        let sitemap_options = SitemapOptions {
+            disallowed: vec![
+                "/drafts/".to_string(),
+                "/search".to_string(),
+            ],
+            ..SitemapOptions::default()
+        };
+        let options = SiteMapData {
+            changefreq: sitemap_gen::ChangeFreq::Weekly,
+            lastmod: "2025-01-01".to_string(),
+            loc: url::Url::parse("https://example.com")
+                .expect("Valid URL"),
+        };
+        let xml =
+            sitemap_with_options(options, dir.path(), &sitemap_options)
+                .expect("Sitemap generation should succeed");
+
+        assert!(
+            xml.contains("<loc>https://example.com/index.html</loc>")
+        );
+        assert!(xml.contains("https://example.com/blog/"));
+        assert!(!xml.contains("drafts"));
+        assert!(!xml.contains("search"));
+        assert_eq!(xml.matches("<loc>").count(), 2);
+    }
+
+    #[test]
+    fn test_sitemap_max_depth() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(dir.path().join("index.html"), "")
+            .expect("Failed to write file");
+        for page in ["blog", "blog/2024", "blog/2024/post"] {
+            fs::create_dir_all(dir.path().join(page))
+                .expect("Failed to create directory");
+            fs::write(dir.path().join(page).join("index.html"), "")
+                .expect("Failed to write file");
+        }
+
+        let count = |max_depth| {
+            let options = SiteMapData {
+                changefreq: sitemap_gen::ChangeFreq::Weekly,
+                lastmod: "2025-01-01".to_string(),
+                loc: url::Url::parse("https://example.com")
+                    .expect("Valid URL"),
+            };
+            let sitemap_options = SitemapOptions {
+                max_depth,
+                ..SitemapOptions::default()
+            };
+            sitemap_with_options(options, dir.path(), &sitemap_options)
+                .expect("Sitemap generation should succeed")
+                .matches("<loc>")
+                .count()
+        };
+
+        assert_eq!(count(Some(0)), 1);
+        assert_eq!(count(Some(2)), 3);
+        assert_eq!(count(None), 4);
+
+        let skipped = |max_depth| {
+            let sitemap_options = SitemapOptions {
+                max_depth,
+                ..SitemapOptions::default()
+            };
+            let entry = UrlEntry {
+                url_mode: UrlMode::Relative,
+                changefreq: "weekly".to_string(),
+                lastmod: "2025-01-01",
+                options: &sitemap_options,
+            };
+            let mut urls = Vec::new();
+            visit_dirs(dir.path(), &entry, &mut urls)
+                .expect("Walk should succeed")
+        };
+        assert_eq!(skipped(Some(0)), 1);
+        assert_eq!(skipped(Some(2)), 1);
+        assert_eq!(skipped(None), 0);
+    }
+
+    #[test]
+    fn test_validate_sitemap_xml() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        fs::create_dir_all(dir.path().join("a&b"))
+            .expect("Failed to create directory");
+        fs::write(dir.path().join("a&b/index.html"), "")
+            .expect("Failed to write file");
+        let options = SiteMapData {
+            changefreq: sitemap_gen::ChangeFreq::Weekly,
+            lastmod: "2025-01-01".to_string(),
+            loc: url::Url::parse("https://example.com")
+                .expect("Valid URL"),
+        };
+        let xml = sitemap(options, dir.path())
+            .expect("Sitemap generation should succeed");
+        assert_eq!(validate_sitemap_xml(&xml), Ok(()));
+
+        let urlset = |urls: &str| {
+            format!(
+                r#"<urlset xmlns="{}" xmlns:xhtml="http://www.w3.org/1999/xhtml">{}</urlset>"#,
+                SITEMAP_NAMESPACE, urls
+            )
+        };
+        let problems = validate_sitemap_xml(&urlset(
+            r#"<url><loc>https://example.com/</loc><xhtml:link loc="x"/></url>
+            <url><loc>ftp://example.com/</loc><changefreq>often</changefreq></url>
+            <url><priority>1.5</priority></url>"#,
+        ))
+        .unwrap_err();
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems[0].starts_with("URL 2: <loc> 'ftp://"));
+        assert!(problems[1].starts_with("URL 2: <changefreq> 'often'"));
+        assert!(problems[2].starts_with("URL 3: <priority> '1.5'"));
+        assert_eq!(problems[3], "URL 3: expected one <loc>, found 0");
+
+        let long = format!(
+            "<url><loc>https://example.com/{}</loc></url>",
+            "a".repeat(MAX_SITEMAP_LOC_LENGTH)
+        );
+        assert!(validate_sitemap_xml(&urlset(&long)).unwrap_err()[0]
+            .contains("longer than"));
+
+        let many = "<url><loc>https://example.com/</loc></url>"
+            .repeat(MAX_SITEMAP_URLS + 1);
+        assert!(validate_sitemap_xml(&urlset(&many)).unwrap_err()[0]
+            .contains("more than the limit"));
+
+        for xml in [
+            "<urlset><url>",
+            "<urlset><url><loc>a & b</loc></url></urlset>",
+            "<urlset/>",
+            r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"/>"#,
+        ] {
+            assert_eq!(
+                validate_sitemap_xml(xml).unwrap_err().len(),
+                1,
+                "{}",
+                xml
+            );
+        }
+    }
+
+    #[test]
+    fn test_sitemap_url_mode() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        fs::create_dir_all(dir.path().join("about"))
+            .expect("Failed to create directory");
+        fs::write(dir.path().join("about/index.html"), "")
+            .expect("Failed to write file");
+
+        let generate = |url_mode| {
+            let options = SiteMapData {
+                changefreq: sitemap_gen::ChangeFreq::Weekly,
+                lastmod: "2025-01-01".to_string(),
+                loc: url::Url::parse("https://example.com")
+                    .expect("Valid URL"),
+            };
+            sitemap_with_options(
+                options,
+                dir.path(),
+                &SitemapOptions {
+                    url_mode,
+                    ..SitemapOptions::default()
+                },
+            )
+        };
+
+        let xml = generate(Some(UrlMode::Relative))
+            .expect("Sitemap generation should succeed");
+        assert!(xml.contains("<loc>/about/index.html</loc>"));
+
+        let xml = generate(Some(UrlMode::Absolute(
+            "https://preview.example.org/site/".to_string(),
+        )))
+        .expect("Sitemap generation should succeed");
+        assert!(xml.contains(
+            "<loc>https://preview.example.org/site/about/index.html</loc>"
+        ));
+
+        let error =
+            generate(Some(UrlMode::Absolute("not a url".to_string())))
+                .expect_err("Invalid base URL should fail");
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_write_sitemap_if_changed() {
+        let site =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        let out =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(site.path().join("index.html"), "")
+            .expect("Failed to write file");
+        let output = out.path().join("sitemap.xml");
+        let options = SiteMapData {
+            changefreq: sitemap_gen::ChangeFreq::Weekly,
+            lastmod: "2025-01-01".to_string(),
+            loc: url::Url::parse("https://example.com")
+                .expect("Valid URL"),
+        };
+        let write = || {
+            write_sitemap_if_changed(
+                options.clone(),
+                site.path(),
+                &SitemapOptions::default(),
+                &output,
+            )
+            .expect("Sitemap should be written")
+        };
+
+        assert!(write());
+        assert!(!write());
+        assert_eq!(
+            fs::read_dir(out.path()).unwrap().count(),
+            1,
+            "only the sitemap is written"
+        );
+        fs::write(&output, "stale").expect("Failed to write file");
+        assert!(write());
+        assert!(!write());
+
+        fs::create_dir_all(site.path().join("about"))
+            .expect("Failed to create directory");
+        fs::write(site.path().join("about/index.html"), "")
+            .expect("Failed to write file");
+        assert!(write());
+        let xml = fs::read_to_string(&output).unwrap();
+        assert!(xml.contains("https://example.com/about/"));
+        assert!(!write());
+
+        fs::remove_file(&output).expect("Failed to remove file");
+        assert!(write());
+    }
+
+    #[test]
+    fn test_sitemap_missing_directory() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        let missing = dir.path().join("missing");
+        let options = SiteMapData {
+            changefreq: sitemap_gen::ChangeFreq::Weekly,
+            lastmod: "2025-01-01".to_string(),
+            loc: url::Url::parse("https://example.com")
+                .expect("Valid URL"),
+        };
+
+        let lenient = sitemap(options.clone(), &missing)
+            .expect("Missing directory should not fail");
+        assert!(lenient.contains("<urlset"));
+        assert!(!lenient.contains("<url>"));
+
+        let strict = sitemap_with_policy(
+            options,
+            &missing,
+            TrailingSlash::Always,
+            true,
+        );
+        assert_eq!(
+            strict.expect_err("Strict mode should fail").kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
     #[test]
     fn test_generate_xml_element_with_attrs() {
         let mut buffer = Vec::new();
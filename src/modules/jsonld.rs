@@ -0,0 +1,448 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Site-level JSON-LD graph
+//!
+//! Builds a single schema.org `@graph` describing the whole site: one
+//! `WebSite`, the `Organization` that publishes it and a `WebPage` or
+//! `Article` node for every page. Nodes reference each other by `@id`, so
+//! search engines can connect the pages to the site and its publisher.
+//!
+//! The graph is meant to be emitted once, on the home page or as a
+//! standalone `.jsonld` file.
+//!
+//! # Examples
+//!
+//! ```
+//! use staticdatagen::models::data::FileData;
+//! use staticdatagen::modules::jsonld::{generate_site_jsonld, SiteConfig};
+//! use staticdatagen::utilities::permalink::UrlOptions;
+//!
+//! let site = SiteConfig {
+//!     name: "Example".to_string(),
+//!     url: "https://example.com/".to_string(),
+//!     twitter: "@example".to_string(),
+//!     urls: UrlOptions { pretty_urls: true, ..UrlOptions::default() },
+//!     ..SiteConfig::default()
+//! };
+//! let files = vec![FileData::new(
+//!     "about.md".to_string(),
+//!     "---\ntitle: About\npermalink: /about/\n---\nAbout us.".to_string(),
+//! )];
+//!
+//! let jsonld = generate_site_jsonld(&files, &site);
+//! assert!(jsonld.contains("\"@id\": \"https://example.com/#website\""));
+//! assert!(jsonld.contains("\"url\": \"https://example.com/about/\""));
+//! assert!(jsonld.contains("https://twitter.com/example"));
+//! ```
+
+use crate::models::data::FileData;
+use crate::modules::robots::MetaRobots;
+use crate::modules::urls::page_url;
+use crate::utilities::datetime::to_rfc3339;
+use crate::utilities::metadata::{
+    meta_date, meta_str, with_raw_scalars,
+};
+use crate::utilities::permalink::UrlOptions;
+use crate::utilities::url::to_absolute;
+use metadata_gen::extract_and_prepare_metadata;
+use serde_json::{json, Map, Value};
+use url::Url;
+
+/// The page layouts described as `Article` rather than `WebPage`.
+const ARTICLE_LAYOUTS: [&str; 3] = ["article", "blog", "post"];
+
+/// Site-wide settings for the JSON-LD graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SiteConfig {
+    /// The site name.
+    pub name: String,
+    /// The absolute URL of the home page, such as `https://example.com/`.
+    pub url: String,
+    /// A short description of the site.
+    pub description: String,
+    /// The site language, such as `en-GB`.
+    pub language: String,
+    /// The publishing organization; defaults to the site name.
+    pub organization: String,
+    /// The URL of the organization logo, resolved against
+    /// [`SiteConfig::url`] when relative.
+    pub logo: String,
+    /// The Twitter/X handle, with or without the leading `@`.
+    pub twitter: String,
+    /// Other profile URLs for `sameAs`, such as GitHub or Mastodon.
+    pub social_profiles: Vec<String>,
+    /// How page URLs are written, matching the compile's
+    /// [`CompileOptions::url`](crate::compiler::service::CompileOptions::url)
+    /// so the graph names the same URLs as the sitemap and `urls.txt`.
+    pub urls: UrlOptions,
+}
+
+impl SiteConfig {
+    /// Returns the `@id` of the `WebSite` node.
+    pub fn website_id(&self) -> String {
+        format!("{}#website", self.base_url())
+    }
+
+    /// Returns the `@id` of the `Organization` node.
+    pub fn organization_id(&self) -> String {
+        format!("{}#organization", self.base_url())
+    }
+
+    /// Returns the `sameAs` links: the Twitter/X profile, then every
+    /// valid absolute URL from `social_profiles`.
+    pub fn same_as(&self) -> Vec<String> {
+        let handle = self.twitter.trim().trim_start_matches('@');
+        let twitter = (!handle.is_empty())
+            .then(|| format!("https://twitter.com/{}", handle));
+
+        twitter
+            .into_iter()
+            .chain(
+                self.social_profiles
+                    .iter()
+                    .filter(|profile| Url::parse(profile).is_ok())
+                    .cloned(),
+            )
+            .collect()
+    }
+
+    /// Returns the home page URL with a trailing slash.
+    fn base_url(&self) -> String {
+        to_absolute(&self.url, "").unwrap_or_else(|_| {
+            format!("{}/", self.url.trim().trim_end_matches('/'))
+        })
+    }
+
+    /// Returns the absolute URL of the logo, or an empty string if it
+    /// is unset or cannot be resolved.
+    fn logo_url(&self) -> String {
+        match self.logo.trim() {
+            "" => String::new(),
+            logo => to_absolute(&self.url, logo).unwrap_or_default(),
+        }
+    }
+}
+
+/// Generates the site-level JSON-LD graph for `files`.
+///
+/// Each page becomes an `Article` when its `layout` is `post`, `article`
+/// or `blog`, and a `WebPage` otherwise. Pages marked `noindex` and
+/// files whose metadata cannot be read are left out. Page URLs come from
+/// `permalink`, or from the file name when no permalink is set, written
+/// with [`SiteConfig::urls`] and resolved against [`SiteConfig::url`]
+/// when relative, exactly as in `urls.txt`.
+///
+/// # Returns
+///
+/// The pretty-printed JSON-LD document, with pages ordered by URL.
+pub fn generate_site_jsonld(
+    files: &[FileData],
+    site_config: &SiteConfig,
+) -> String {
+    let website_id = site_config.website_id();
+    let organization_id = site_config.organization_id();
+    let organization_name = if site_config.organization.is_empty() {
+        &site_config.name
+    } else {
+        &site_config.organization
+    };
+
+    let mut website = Map::new();
+    let _ = website.insert("@type".to_string(), json!("WebSite"));
+    let _ = website.insert("@id".to_string(), json!(website_id));
+    let _ = website
+        .insert("url".to_string(), json!(site_config.base_url()));
+    insert_text(&mut website, "name", &site_config.name);
+    insert_text(&mut website, "description", &site_config.description);
+    insert_text(&mut website, "inLanguage", &site_config.language);
+    let _ = website.insert(
+        "publisher".to_string(),
+        json!({ "@id": organization_id }),
+    );
+
+    let mut organization = Map::new();
+    let _ =
+        organization.insert("@type".to_string(), json!("Organization"));
+    let _ =
+        organization.insert("@id".to_string(), json!(organization_id));
+    let _ = organization
+        .insert("url".to_string(), json!(site_config.base_url()));
+    insert_text(&mut organization, "name", organization_name);
+    insert_text(&mut organization, "logo", &site_config.logo_url());
+    let same_as = site_config.same_as();
+    if !same_as.is_empty() {
+        let _ =
+            organization.insert("sameAs".to_string(), json!(same_as));
+    }
+
+    let mut pages: Vec<(String, Value)> = files
+        .iter()
+        .filter_map(|file| page_node(file, site_config))
+        .collect();
+    pages.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let graph: Vec<Value> = vec![website.into(), organization.into()]
+        .into_iter()
+        .chain(pages.into_iter().map(|(_, node)| node))
+        .collect();
+
+    serde_json::to_string_pretty(&json!({
+        "@context": "https://schema.org",
+        "@graph": graph,
+    }))
+    .unwrap_or_default()
+}
+
+/// Builds the `WebPage` or `Article` node of a file, with its URL.
+fn page_node(
+    file: &FileData,
+    site_config: &SiteConfig,
+) -> Option<(String, Value)> {
+    let (metadata, _, _) =
+        extract_and_prepare_metadata(&file.content).ok()?;
+    let robots =
+        with_raw_scalars(&file.content, &metadata, &["robots"]);
+    if MetaRobots::from_metadata(&robots).is_noindex() {
+        return None;
+    }
+
+    let url = page_url(
+        file,
+        &metadata,
+        &site_config.base_url(),
+        &site_config.urls,
+    )?;
+    let is_article =
+        meta_str(&metadata, "layout").map_or(false, |layout| {
+            ARTICLE_LAYOUTS
+                .contains(&layout.trim().to_lowercase().as_str())
+        });
+    let title = meta_str(&metadata, "title").unwrap_or_default();
+
+    let mut node = Map::new();
+    if is_article {
+        let _ = node.insert("@type".to_string(), json!("Article"));
+        let _ = node.insert(
+            "@id".to_string(),
+            json!(format!("{}#article", url)),
+        );
+        insert_text(&mut node, "headline", title);
+        let _ = node.insert(
+            "mainEntityOfPage".to_string(),
+            json!({ "@id": url }),
+        );
+        let _ = node.insert(
+            "publisher".to_string(),
+            json!({ "@id": site_config.organization_id() }),
+        );
+        if let Some(author) = meta_str(&metadata, "author") {
+            if !author.trim().is_empty() {
+                let _ = node.insert(
+                    "author".to_string(),
+                    json!({ "@type": "Person", "name": author.trim() }),
+                );
+            }
+        }
+    } else {
+        let _ = node.insert("@type".to_string(), json!("WebPage"));
+        let _ = node.insert(
+            "@id".to_string(),
+            json!(format!("{}#webpage", url)),
+        );
+        insert_text(&mut node, "name", title);
+    }
+    let _ = node.insert("url".to_string(), json!(url));
+    insert_text(
+        &mut node,
+        "description",
+        meta_str(&metadata, "description").unwrap_or_default(),
+    );
+    insert_text(
+        &mut node,
+        "inLanguage",
+        meta_str(&metadata, "language")
+            .unwrap_or(&site_config.language),
+    );
+    if let Some(published) = ["date", "pub_date"]
+        .iter()
+        .find_map(|key| meta_date(&metadata, key).ok().flatten())
+        .map(|date| to_rfc3339(&date))
+    {
+        let _ =
+            node.insert("datePublished".to_string(), json!(published));
+    }
+    let _ = node.insert(
+        "isPartOf".to_string(),
+        json!({ "@id": site_config.website_id() }),
+    );
+
+    Some((url, node.into()))
+}
+
+/// Inserts `value` under `key` unless it is blank.
+fn insert_text(node: &mut Map<String, Value>, key: &str, value: &str) {
+    if !value.trim().is_empty() {
+        let _ = node.insert(key.to_string(), json!(value.trim()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::page;
+    use crate::utilities::permalink::TrailingSlash;
+
+    fn site() -> SiteConfig {
+        SiteConfig {
+            name: "Example".to_string(),
+            url: "https://example.com".to_string(),
+            description: "An example site".to_string(),
+            language: "en-GB".to_string(),
+            organization: "Example Ltd".to_string(),
+            logo: "https://example.com/logo.svg".to_string(),
+            twitter: "example".to_string(),
+            social_profiles: vec![
+                "https://github.com/example".to_string(),
+                "not a url".to_string(),
+            ],
+            urls: UrlOptions {
+                pretty_urls: true,
+                ..UrlOptions::default()
+            },
+        }
+    }
+
+    fn graph(files: &[FileData]) -> Vec<Value> {
+        let jsonld: Value =
+            serde_json::from_str(&generate_site_jsonld(files, &site()))
+                .unwrap();
+        assert_eq!(jsonld["@context"], "https://schema.org");
+        jsonld["@graph"].as_array().unwrap().clone()
+    }
+
+    #[test]
+    fn test_site_urls() {
+        let config = SiteConfig {
+            url: "https://example.com/docs".to_string(),
+            logo: "/logo.svg".to_string(),
+            ..site()
+        };
+        assert_eq!(config.base_url(), "https://example.com/docs/");
+        assert_eq!(
+            config.logo_url(),
+            "https://example.com/docs/logo.svg"
+        );
+        assert_eq!(site().logo_url(), "https://example.com/logo.svg");
+        assert_eq!(
+            SiteConfig {
+                logo: String::new(),
+                ..site()
+            }
+            .logo_url(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_site_nodes() {
+        let graph = graph(&[]);
+
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph[0]["@type"], "WebSite");
+        assert_eq!(graph[0]["@id"], "https://example.com/#website");
+        assert_eq!(
+            graph[0]["publisher"]["@id"],
+            "https://example.com/#organization"
+        );
+        assert_eq!(graph[1]["@type"], "Organization");
+        assert_eq!(graph[1]["name"], "Example Ltd");
+        assert_eq!(
+            graph[1]["sameAs"],
+            json!([
+                "https://twitter.com/example",
+                "https://github.com/example"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_page_nodes() {
+        let graph = graph(&[
+            page(
+                "2024-post.md",
+                "title: Post\nlayout: post\nauthor: Jane\ndate: 2024-01-15\npermalink: https://example.com/blog/post/",
+            ),
+            page("about.md", "title: About\ndescription: Who we are"),
+            page("index.md", "title: Home\npermalink: /"),
+        ]);
+
+        assert_eq!(graph.len(), 5);
+        let article = &graph[4];
+        assert_eq!(article["@type"], "Article");
+        assert_eq!(
+            article["@id"],
+            "https://example.com/blog/post/#article"
+        );
+        assert_eq!(article["headline"], "Post");
+        assert_eq!(article["author"]["name"], "Jane");
+        assert_eq!(article["datePublished"], "2024-01-15T00:00:00Z");
+        assert_eq!(
+            article["publisher"]["@id"],
+            "https://example.com/#organization"
+        );
+
+        let about = &graph[3];
+        assert_eq!(about["@type"], "WebPage");
+        assert_eq!(about["url"], "https://example.com/about/");
+        assert_eq!(about["inLanguage"], "en-GB");
+        assert_eq!(
+            about["isPartOf"]["@id"],
+            "https://example.com/#website"
+        );
+
+        assert_eq!(graph[2]["url"], "https://example.com/");
+    }
+
+    #[test]
+    fn test_page_urls_follow_url_options() {
+        let files = [page("about.md", "title: About")];
+        let site = SiteConfig {
+            urls: UrlOptions {
+                policy: TrailingSlash::Never,
+                ..UrlOptions::default()
+            },
+            ..site()
+        };
+        let jsonld: Value =
+            serde_json::from_str(&generate_site_jsonld(&files, &site))
+                .unwrap();
+
+        assert_eq!(
+            jsonld["@graph"][2]["url"],
+            "https://example.com/about.html"
+        );
+        assert_eq!(
+            jsonld["@graph"][2]["@id"],
+            "https://example.com/about.html#webpage"
+        );
+    }
+
+    #[test]
+    fn test_noindex_pages_are_skipped() {
+        let graph =
+            graph(&[page("draft.md", "title: Draft\nrobots: noindex")]);
+
+        assert_eq!(graph.len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_robots_directives_keep_pages() {
+        let graph = graph(&[page(
+            "post.md",
+            "title: Post\nrobots: max-snippet:-1, nositelinks",
+        )]);
+
+        assert_eq!(graph.len(), 3);
+    }
+}
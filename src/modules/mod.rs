@@ -1,9 +1,18 @@
 // Copyright © 2025 Static Data Gen. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+/// The `build` module derives build ids and the `build.json` content.
+pub mod build;
+
+/// The `excerpt` module splits rendered content into excerpt and body.
+pub mod excerpt;
+
 /// The `json` module generates the JSON content.
 pub mod json;
 
+/// The `jsonld` module generates the site-level JSON-LD graph.
+pub mod jsonld;
+
 /// The `navigation` module generates the navigation menu.
 pub mod navigation;
 
@@ -22,5 +31,17 @@
 /// The `robots` module generates the robots.txt content.
 pub mod robots;
 
+/// The `sections` module generates index pages for sections without one.
+pub mod sections;
+
 /// The `security` module generates the security.txt content.
 pub mod security;
+
+/// The `stats` module computes content statistics for a site.
+pub mod stats;
+
+/// The `template` module checks templates against their variables.
+pub mod template;
+
+/// The `urls` module lists the public page URLs of a site.
+pub mod urls;
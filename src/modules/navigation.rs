@@ -35,11 +35,16 @@
 //! assert!(nav.contains("About"));
 //! ```
 
+use metadata_gen::extract_and_prepare_metadata;
 use rayon::prelude::*;
 use std::path::{Component, Path};
 
 use crate::models::data::FileData;
 use crate::utilities::directory::to_title_case;
+use crate::utilities::html::html_escape;
+use crate::utilities::permalink::{
+    page_path, resolve_url, TrailingSlash, UrlOptions,
+};
 
 /// A set of supported file extensions for navigation.
 const SUPPORTED_EXTENSIONS: [&str; 3] = ["md", "toml", "json"];
@@ -112,13 +117,96 @@ impl NavigationGenerator {
     /// assert!(nav.contains("about/index.html"));
     /// ```
     pub fn generate_navigation(files: &[FileData]) -> String {
+        Self::generate_navigation_with_policy(
+            files,
+            TrailingSlash::Always,
+        )
+    }
+
+    /// Generates a navigation menu whose link targets follow the given
+    /// [`TrailingSlash`] policy.
+    ///
+    /// This is [`NavigationGenerator::generate_navigation_with_options`]
+    /// with the default [`UrlOptions`] and the given policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - A slice of [`FileData`] structures representing the content files.
+    /// * `policy` - The trailing-slash policy used to build link targets.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use staticdatagen::models::data::FileData;
+    /// use staticdatagen::modules::navigation::NavigationGenerator;
+    /// use staticdatagen::utilities::permalink::TrailingSlash;
+    ///
+    /// let files = vec![FileData {
+    ///     name: "about.md".to_string(),
+    ///     content: "About page".to_string(),
+    ///     ..Default::default()
+    /// }];
+    ///
+    /// let nav = NavigationGenerator::generate_navigation_with_policy(
+    ///     &files,
+    ///     TrailingSlash::Never,
+    /// );
+    /// assert!(nav.contains(r#"href="/about.html""#));
+    /// ```
+    pub fn generate_navigation_with_policy(
+        files: &[FileData],
+        policy: TrailingSlash,
+    ) -> String {
+        Self::generate_navigation_with_options(
+            files,
+            &UrlOptions {
+                policy,
+                ..UrlOptions::default()
+            },
+        )
+    }
+
+    /// Generates a navigation menu whose link targets are written with
+    /// the given [`UrlOptions`].
+    ///
+    /// Each file is a directory-style permalink (`/about/`) unless, under
+    /// [`TrailingSlash::Preserve`], its front matter `permalink` has no
+    /// trailing slash (see [`page_path`]). It is then resolved with
+    /// [`resolve_url`], so links match the files the compiler writes and
+    /// the URLs emitted in the sitemap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use staticdatagen::models::data::FileData;
+    /// use staticdatagen::modules::navigation::NavigationGenerator;
+    /// use staticdatagen::utilities::permalink::UrlOptions;
+    ///
+    /// let files = vec![FileData {
+    ///     name: "about.md".to_string(),
+    ///     content: "About page".to_string(),
+    ///     ..Default::default()
+    /// }];
+    ///
+    /// let nav = NavigationGenerator::generate_navigation_with_options(
+    ///     &files,
+    ///     &UrlOptions { pretty_urls: true, ..UrlOptions::default() },
+    /// );
+    /// assert!(nav.contains(r#"href="/about/""#));
+    /// ```
+    pub fn generate_navigation_with_options(
+        files: &[FileData],
+        options: &UrlOptions,
+    ) -> String {
         if files.is_empty() {
             return String::new();
         }
 
         // Collect and process valid items in parallel
-        let mut nav_items: Vec<_> =
-            files.par_iter().filter_map(Self::process_file).collect();
+        let mut nav_items: Vec<_> = files
+            .par_iter()
+            .filter_map(|file| Self::process_file(file, options))
+            .collect();
 
         // Sort navigation items alphabetically by display name
         nav_items.par_sort_by(|a, b| a.0.cmp(&b.0));
@@ -170,12 +258,16 @@ fn build_item_html(name: &str, url: &str) -> String {
     /// # Arguments
     ///
     /// * `file` - A reference to a [`FileData`] structure.
+    /// * `options` - How the URL is written.
     ///
     /// # Returns
     ///
     /// An [`Option`] containing `(display_name, url)` if the file is valid
     /// for navigation, or [`None`] otherwise.
-    fn process_file(file: &FileData) -> Option<(String, String)> {
+    fn process_file(
+        file: &FileData,
+        options: &UrlOptions,
+    ) -> Option<(String, String)> {
         // First, sanitize the entire file name to remove null bytes or other control characters.
         let sanitized_name = remove_control_chars(&file.name);
         if sanitized_name.is_empty() {
@@ -202,10 +294,22 @@ fn process_file(file: &FileData) -> Option<(String, String)> {
             return None;
         }
 
-        // Build final URL: strip extension + add /index.html
-        let url = format!(
-            "/{}/index.html",
-            path.with_extension("").display()
+        // Only `Preserve` needs the page's own permalink.
+        let permalink = if options.policy == TrailingSlash::Preserve {
+            extract_and_prepare_metadata(&file.content).ok().and_then(
+                |(metadata, _, _)| metadata.get("permalink").cloned(),
+            )
+        } else {
+            None
+        };
+
+        // Build final URL: strip extension + resolve with the options
+        let url = resolve_url(
+            &page_path(
+                &path.with_extension("").to_string_lossy(),
+                permalink.as_deref(),
+            ),
+            options,
         );
 
         // Generate a sanitized, title-cased display name
@@ -288,20 +392,6 @@ fn sanitize_and_titlecase(file_stem: &str) -> String {
     display_name
 }
 
-/// Escapes `<`, `>`, and `&` in a string to avoid HTML injection.
-fn html_escape(input: &str) -> String {
-    let mut escaped = String::with_capacity(input.len());
-    for c in input.chars() {
-        match c {
-            '<' => escaped.push_str("&lt;"),
-            '>' => escaped.push_str("&gt;"),
-            '&' => escaped.push_str("&amp;"),
-            _ => escaped.push(c),
-        }
-    }
-    escaped
-}
-
 #[cfg(test)]
 #[allow(clippy::pedantic, clippy::nursery)]
 mod tests {
@@ -695,4 +785,62 @@ fn extremely_long_names() {
             "Should handle long filenames efficiently"
         );
     }
+
+    // ---------------------------------------------------------------------
+    // Trailing-slash policy tests
+    // ---------------------------------------------------------------------
+    #[test]
+    fn trailing_slash_policies() {
+        let files = vec![create_test_file("blog/post.md", "Post")];
+
+        let always =
+            NavigationGenerator::generate_navigation_with_policy(
+                &files,
+                TrailingSlash::Always,
+            );
+        assert!(always.contains(r#"href="/blog/post/index.html""#));
+
+        let never =
+            NavigationGenerator::generate_navigation_with_policy(
+                &files,
+                TrailingSlash::Never,
+            );
+        assert!(never.contains(r#"href="/blog/post.html""#));
+
+        let preserve =
+            NavigationGenerator::generate_navigation_with_policy(
+                &files,
+                TrailingSlash::Preserve,
+            );
+        assert_eq!(preserve, always);
+
+        let files = vec![create_test_file(
+            "blog/post.md",
+            "---\npermalink: https://example.com/blog/post\n---\nPost",
+        )];
+        let preserve =
+            NavigationGenerator::generate_navigation_with_policy(
+                &files,
+                TrailingSlash::Preserve,
+            );
+        assert!(preserve.contains(r#"href="/blog/post.html""#));
+    }
+
+    #[test]
+    fn navigation_matches_sitemap_urls() {
+        let files = vec![create_test_file("blog/post.md", "Post")];
+        let options = UrlOptions {
+            pretty_urls: true,
+            ..UrlOptions::default()
+        };
+
+        let nav = NavigationGenerator::generate_navigation_with_options(
+            &files, &options,
+        );
+        assert!(nav.contains(&format!(
+            r#"href="{}""#,
+            resolve_url("blog/post/", &options)
+        )));
+        assert!(nav.contains(r#"href="/blog/post/""#));
+    }
 }
@@ -41,7 +41,7 @@
 //! - Unicode character validation
 
 use anyhow::Result;
-use log::{debug, error, info};
+use log::{debug, info};
 use pulldown_cmark::{Event, Parser, Tag, TagEnd};
 use std::collections::HashMap;
 use thiserror::Error;
@@ -166,6 +166,28 @@ pub fn generate_plain_text(
     ))
 }
 
+/// Counts the words in HTML/Markdown content.
+///
+/// The content is converted to plain text first, so markup, list bullets
+/// and link targets are not counted.
+///
+/// # Example
+///
+/// ```
+/// use staticdatagen::modules::plaintext::word_count;
+///
+/// let count = word_count("# Hello\n\nThis is **bold** text.").unwrap();
+/// assert_eq!(count, 5);
+/// ```
+pub fn word_count(content: &str) -> Result<usize> {
+    let plain_content = convert_to_plain_text(content)?;
+
+    Ok(plain_content
+        .split_whitespace()
+        .filter(|word| *word != "•")
+        .count())
+}
+
 /// Converts formatted content to plain text.
 fn convert_to_plain_text(content: &str) -> Result<String> {
     let mut plain_text = String::new();
@@ -188,7 +210,7 @@ fn convert_to_plain_text(content: &str) -> Result<String> {
             }
             Event::Start(Tag::Paragraph)
             | Event::Start(Tag::Heading { .. }) => {
-                if !plain_text.is_empty() && !buffer.trim().is_empty() {
+                if !plain_text.is_empty() {
                     plain_text.push_str("\n\n");
                 }
                 buffer.clear();
@@ -270,6 +292,18 @@ fn test_basic_conversion() -> Result<()> {
         Ok(())
     }
 
+    #[test]
+    fn test_word_count() -> Result<()> {
+        assert_eq!(word_count("")?, 0);
+        assert_eq!(
+            word_count(
+                "- One item\n- Two [linked](https://example.com)"
+            )?,
+            4
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_empty_input() -> Result<()> {
         let (content, title, description, author, creator, keywords) =
@@ -1,8 +1,10 @@
 // Copyright © 2025 Static Data Gen. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use crate::models::data::validation::validate_url;
 use regex::{Captures, Regex};
 use std::error::Error;
+use url::Url;
 
 /// Post-processes HTML content by performing various transformations.
 ///
@@ -116,3 +118,195 @@ pub fn post_process_html(
 
     Ok(processed_html)
 }
+
+/// Returns the absolute URL of the first usable `<img src>` in `html`.
+///
+/// Relative sources are resolved against `base_url`. Images embedded as
+/// `data:` URIs, sources that do not resolve to an HTTP(S) URL and URLs
+/// rejected by [`validate_url`] are skipped in favour of the next image.
+///
+/// # Arguments
+///
+/// * `html` - The rendered HTML content.
+/// * `base_url` - The absolute URL relative sources are resolved against.
+///
+/// # Returns
+///
+/// The resolved image URL, or `None` if the page has no usable image.
+pub fn first_image(html: &str, base_url: &str) -> Option<String> {
+    let src_regex =
+        Regex::new(r#"(?i)<img\b[^>]*?\ssrc\s*=\s*["']([^"']+)["']"#)
+            .ok()?;
+    let base = Url::parse(base_url.trim()).ok();
+
+    let image = src_regex.captures_iter(html).find_map(|caps| {
+        let src = caps[1].trim().replace("&amp;", "&");
+        if src.is_empty() || src.starts_with("data:") {
+            return None;
+        }
+        let url = match &base {
+            Some(base) => base.join(&src).ok()?,
+            None => Url::parse(&src).ok()?,
+        };
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return None;
+        }
+        let url = String::from(url);
+        validate_url(&url).ok()?;
+        Some(url)
+    });
+    image
+}
+
+/// Inserts `head` before the closing `</head>` tag of `html`, and
+/// `footer` before its closing `</body>` tag.
+///
+/// Empty snippets and snippets already present in `html` (placed by the
+/// template through `{{extra_head}}` or `{{extra_footer}}`) are skipped.
+/// Tags are matched case-insensitively, and a snippet whose closing tag
+/// is missing is left out.
+///
+/// # Example
+///
+/// ```
+/// use staticdatagen::modules::postprocessor::inject_snippets;
+///
+/// let html = inject_snippets(
+///     "<html><head></head><body><p>Hi</p></body></html>",
+///     r#"<meta name="verify" content="abc">"#,
+///     "<footer>Bye</footer>",
+/// );
+/// assert_eq!(
+///     html,
+///     r#"<html><head><meta name="verify" content="abc"></head><body><p>Hi</p><footer>Bye</footer></body></html>"#
+/// );
+/// ```
+pub fn inject_snippets(html: &str, head: &str, footer: &str) -> String {
+    let mut html = html.to_string();
+    for (snippet, closing_tag) in
+        [(head, "</head>"), (footer, "</body>")]
+    {
+        if snippet.trim().is_empty() || html.contains(snippet) {
+            continue;
+        }
+        match html.to_ascii_lowercase().rfind(closing_tag) {
+            Some(index) => html.insert_str(index, snippet),
+            None => log::warn!(
+                "No {} tag to insert the snippet before",
+                closing_tag
+            ),
+        }
+    }
+    html
+}
+
+/// Returns the first executable construct found in `snippet`: a
+/// `<script>`, `<iframe>`, `<object>`, `<embed>`, `<frame>` or `<base>`
+/// tag, an `on*` event handler attribute, or a `javascript:`,
+/// `vbscript:` or `data:text/html` URL.
+///
+/// # Example
+///
+/// ```
+/// use staticdatagen::modules::postprocessor::find_executable;
+///
+/// assert_eq!(find_executable(r#"<meta name="verify" content="abc">"#), None);
+/// assert_eq!(
+///     find_executable(r#"<img src="x" onerror="alert(1)">"#).as_deref(),
+///     Some(" onerror=")
+/// );
+/// ```
+pub fn find_executable(snippet: &str) -> Option<String> {
+    let executable = Regex::new(
+        r"(?i)<\s*(script|iframe|object|embed|frame|base)\b|\son[a-z]+\s*=|(javascript|vbscript)\s*:|data\s*:\s*text/html",
+    )
+    .ok()?;
+    let found =
+        executable.find(snippet).map(|m| m.as_str().to_string());
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_image_resolves_relative_sources() {
+        let html = r#"<p>Intro</p>
+<p><img alt="Logo" src="/images/logo.png"></p>
+<img src="second.png">"#;
+
+        assert_eq!(
+            first_image(html, "https://example.com/blog/post/"),
+            Some("https://example.com/images/logo.png".to_string())
+        );
+    }
+
+    #[test]
+    fn test_first_image_skips_unusable_sources() {
+        let html = r#"<img src="data:image/png;base64,AAAA">
+<img src="javascript:alert(1)">
+<IMG SRC='https://cdn.example.com/a.jpg?w=1&amp;h=2'>"#;
+
+        assert_eq!(
+            first_image(html, "https://example.com/"),
+            Some("https://cdn.example.com/a.jpg?w=1&h=2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_inject_snippets() {
+        let html =
+            "<HTML><HEAD><title>T</title></HEAD><BODY>Hi</BODY></HTML>";
+
+        assert_eq!(
+            inject_snippets(html, "<meta charset=\"utf-8\">", ""),
+            "<HTML><HEAD><title>T</title><meta charset=\"utf-8\"></HEAD><BODY>Hi</BODY></HTML>"
+        );
+        assert_eq!(inject_snippets(html, " ", ""), html);
+        assert_eq!(inject_snippets(html, "", "Hi"), html);
+        assert_eq!(
+            inject_snippets("<p>Fragment</p>", "<meta>", "<footer>"),
+            "<p>Fragment</p>"
+        );
+    }
+
+    #[test]
+    fn test_find_executable() {
+        for snippet in [
+            r#"<meta name="google-site-verification" content="abc">"#,
+            r#"<link rel="me" href="https://example.social/@me">"#,
+            "<footer><p>Made with care</p></footer>",
+        ] {
+            assert_eq!(find_executable(snippet), None, "{}", snippet);
+        }
+        for (snippet, found) in [
+            (r#"<script src="a.js"></script>"#, "<script"),
+            ("< IFRAME src=x>", "< IFRAME"),
+            (r#"<body onload="x()">"#, " onload="),
+            (r#"<a href="JavaScript:x()">"#, "JavaScript:"),
+            (r#"<a href="data:text/html,x">"#, "data:text/html"),
+        ] {
+            assert_eq!(
+                find_executable(snippet).as_deref(),
+                Some(found)
+            );
+        }
+    }
+
+    #[test]
+    fn test_first_image_without_images() {
+        assert_eq!(
+            first_image("<p>No images</p>", "https://example.com/"),
+            None
+        );
+        assert_eq!(first_image(r#"<img src="a.png">"#, ""), None);
+        assert_eq!(
+            first_image(
+                r#"<img data-src="a.png">"#,
+                "https://example.com/"
+            ),
+            None
+        );
+    }
+}
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::utilities::directory::update_class_attributes;
+use crate::utilities::frontmatter::find_frontmatter;
+use crate::Error as CrateError;
 use regex::Regex;
+use std::collections::HashMap;
 use std::error::Error;
 
 /// Preprocesses the Markdown content to update class attributes and image tags.
@@ -38,3 +41,226 @@ pub fn preprocess_content(
 
     Ok(result)
 }
+
+/// Expands `${NAME}` references in `content` using `vars`.
+///
+/// Variables are looked up only in the caller-supplied map, never in the
+/// process environment. A name starts with a letter or `_` and continues
+/// with letters, digits or `_`; `$${NAME}` produces a literal `${NAME}`.
+///
+/// # Arguments
+///
+/// * `content` - The text to expand.
+/// * `vars` - The variable values.
+/// * `strict` - Whether a missing variable is an error rather than an
+///   empty string.
+///
+/// # Returns
+///
+/// The expanded text, or an error naming the first missing variable in
+/// strict mode.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use staticdatagen::modules::preprocessor::interpolate;
+///
+/// let mut vars = HashMap::new();
+/// vars.insert("BUILD_DATE".to_string(), "2025-01-01".to_string());
+///
+/// let content = "last_build_date: ${BUILD_DATE}";
+/// assert_eq!(
+///     interpolate(content, &vars, true).unwrap(),
+///     "last_build_date: 2025-01-01"
+/// );
+/// assert!(interpolate("${MISSING}", &vars, true).is_err());
+/// assert_eq!(interpolate("${MISSING}", &vars, false).unwrap(), "");
+/// ```
+pub fn interpolate(
+    content: &str,
+    vars: &HashMap<String, String>,
+    strict: bool,
+) -> Result<String, CrateError> {
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("${") {
+        // `$${` escapes a reference.
+        if rest[..start].ends_with('$') {
+            result.push_str(&rest[..start - 1]);
+            result.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let name = after.find('}').map(|end| &after[..end]);
+
+        match name.filter(|name| is_variable_name(name)) {
+            Some(name) => {
+                match vars.get(name) {
+                    Some(value) => result.push_str(value),
+                    None if strict => {
+                        return Err(CrateError::content_processing(
+                            format!("Undefined variable '{}'", name),
+                            None,
+                        ))
+                    }
+                    None => {}
+                }
+                rest = &after[name.len() + 1..];
+            }
+            // Not a reference: keep the text as written.
+            None => {
+                result.push_str("${");
+                rest = after;
+            }
+        }
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Expands `${NAME}` references like [`interpolate`], but only inside the
+/// leading YAML, TOML or JSON front matter block (see
+/// [`find_frontmatter`]), leaving the fences, line endings and body
+/// untouched.
+///
+/// Content without front matter is returned unchanged.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use staticdatagen::modules::preprocessor::interpolate_front_matter;
+///
+/// let mut vars = HashMap::new();
+/// vars.insert("COMMIT".to_string(), "abc123".to_string());
+///
+/// let content = "---\ncommit: ${COMMIT}\n---\nBody ${COMMIT}";
+/// assert_eq!(
+///     interpolate_front_matter(content, &vars, true).unwrap(),
+///     "---\ncommit: abc123\n---\nBody ${COMMIT}"
+/// );
+/// ```
+pub fn interpolate_front_matter(
+    content: &str,
+    vars: &HashMap<String, String>,
+    strict: bool,
+) -> Result<String, CrateError> {
+    let inner = match find_frontmatter(content) {
+        Some(span) => span.inner,
+        None => return Ok(content.to_string()),
+    };
+
+    Ok(format!(
+        "{}{}{}",
+        &content[..inner.start],
+        interpolate(&content[inner.clone()], vars, strict)?,
+        &content[inner.end..]
+    ))
+}
+
+/// Returns `true` if `name` is a valid variable name.
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HashMap<String, String> {
+        let mut vars = HashMap::new();
+        let _ = vars.insert("COMMIT".to_string(), "abc123".to_string());
+        let _ = vars.insert("_DATE".to_string(), "2025".to_string());
+        vars
+    }
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(
+            interpolate("v${COMMIT} (${_DATE})", &vars(), true)
+                .unwrap(),
+            "vabc123 (2025)"
+        );
+        assert_eq!(
+            interpolate("no refs", &vars(), true).unwrap(),
+            "no refs"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_missing() {
+        let err = interpolate("${NOPE}", &vars(), true).unwrap_err();
+        assert!(err.to_string().contains("NOPE"));
+        assert_eq!(
+            interpolate("a${NOPE}b", &vars(), false).unwrap(),
+            "ab"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_literals() {
+        assert_eq!(
+            interpolate("$${COMMIT} ${1X} ${open $5", &vars(), true)
+                .unwrap(),
+            "${COMMIT} ${1X} ${open $5"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_front_matter() {
+        let content = "---\ncommit: ${COMMIT}\n---\nBody ${NOPE}";
+        assert_eq!(
+            interpolate_front_matter(content, &vars(), true).unwrap(),
+            "---\ncommit: abc123\n---\nBody ${NOPE}"
+        );
+        assert_eq!(
+            interpolate_front_matter("Body ${NOPE}", &vars(), true)
+                .unwrap(),
+            "Body ${NOPE}"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_front_matter_fences() {
+        for (content, expanded) in [
+            (
+                "---\r\ncommit: ${COMMIT}\r\n---\r\nBody ${NOPE}",
+                "---\r\ncommit: abc123\r\n---\r\nBody ${NOPE}",
+            ),
+            (
+                "+++\ncommit = \"${COMMIT}\"\n+++\nBody ${NOPE}",
+                "+++\ncommit = \"abc123\"\n+++\nBody ${NOPE}",
+            ),
+            (
+                "{\n  \"commit\": \"${COMMIT}\"\n}\nBody ${NOPE}",
+                "{\n  \"commit\": \"abc123\"\n}\nBody ${NOPE}",
+            ),
+        ] {
+            assert_eq!(
+                interpolate_front_matter(content, &vars(), true)
+                    .unwrap(),
+                expanded
+            );
+        }
+
+        // Strict mode reports references in every fence style.
+        for content in [
+            "---\r\nv: ${NOPE}\r\n---\r\nBody.",
+            "+++\nv = \"${NOPE}\"\n+++\nBody.",
+            "{\n  \"v\": \"${NOPE}\"\n}\nBody.",
+        ] {
+            assert!(interpolate_front_matter(content, &vars(), true)
+                .is_err());
+        }
+    }
+}
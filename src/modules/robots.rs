@@ -12,6 +12,7 @@
 //! - Validation of URLs and directives
 //! - Generation of standard-compliant robots.txt content
 //! - Secure handling of robot control directives
+//! - Per-page `<meta name="robots">` directives such as `noindex`
 //!
 //! # Example
 //! ```
@@ -28,10 +29,180 @@
 use crate::models::data::TxtData;
 use std::collections::HashMap;
 
+/// The documented `<meta name="robots">` directives without a value.
+pub const META_ROBOTS_DIRECTIVES: &[&str] = &[
+    "all",
+    "index",
+    "follow",
+    "noindex",
+    "nofollow",
+    "none",
+    "noarchive",
+    "nocache",
+    "nosnippet",
+    "noimageindex",
+    "notranslate",
+    "indexifembedded",
+    "nositelinkssearchbox",
+    "noodp",
+    "noydir",
+];
+
+/// The documented `name:value` directives, such as `max-snippet:50`.
+pub const META_ROBOTS_VALUE_DIRECTIVES: &[&str] = &[
+    "max-snippet",
+    "max-image-preview",
+    "max-video-preview",
+    "unavailable_after",
+];
+
+/// The `<meta name="robots">` directives of a single page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MetaRobots {
+    /// The directives in declaration order. Names are lower-cased and
+    /// `name:value` directives written without spaces around the colon.
+    pub directives: Vec<String>,
+}
+
+impl MetaRobots {
+    /// Reads the page directives from the `meta_robots` metadata key, or
+    /// `robots` if it is absent.
+    ///
+    /// Directives are separated by commas or whitespace; a YAML list
+    /// such as `[noindex, nofollow]` is accepted too. Besides
+    /// [`META_ROBOTS_DIRECTIVES`], the `name:value` directives of
+    /// [`META_ROBOTS_VALUE_DIRECTIVES`] are understood, such as
+    /// `max-snippet:50`, `max-image-preview:large` or
+    /// `unavailable_after: 2025-12-31`.
+    ///
+    /// A directive outside these sets, or with an invalid value, is kept
+    /// as written and logged as a warning, so the page still builds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use staticdatagen::modules::robots::MetaRobots;
+    ///
+    /// let mut metadata = HashMap::new();
+    /// metadata.insert("robots".to_string(), "noindex, max-snippet: 50".to_string());
+    ///
+    /// let robots = MetaRobots::from_metadata(&metadata);
+    /// assert!(robots.is_noindex());
+    /// assert_eq!(
+    ///     robots.to_meta_tag(),
+    ///     r#"<meta name="robots" content="noindex, max-snippet:50">"#
+    /// );
+    /// ```
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Self {
+        let value = metadata
+            .get("meta_robots")
+            .filter(|value| !value.trim().is_empty())
+            .or_else(|| metadata.get("robots"))
+            .map_or("", String::as_str);
+
+        let mut directives: Vec<String> = Vec::new();
+        for part in value
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(|part| {
+                part.trim().trim_matches(|c| c == '"' || c == '\'')
+            })
+            .filter(|part| !part.is_empty())
+        {
+            match part.split_once(':') {
+                Some((name, value)) => {
+                    let name = name.trim().to_ascii_lowercase();
+                    let value = value.trim();
+                    // An RFC 850 date holds a comma, which split the
+                    // `unavailable_after` value in two.
+                    match directives.last_mut() {
+                        Some(last)
+                            if last
+                                .starts_with("unavailable_after:")
+                                && !META_ROBOTS_VALUE_DIRECTIVES
+                                    .contains(&name.as_str()) =>
+                        {
+                            last.push_str(", ");
+                            last.push_str(part);
+                        }
+                        _ => directives
+                            .push(format!("{}:{}", name, value)),
+                    }
+                }
+                None => directives.extend(
+                    part.split_whitespace()
+                        .map(str::to_ascii_lowercase),
+                ),
+            }
+        }
+
+        for directive in &directives {
+            if !is_known_directive(directive) {
+                log::warn!(
+                    "Unknown or invalid robots directive '{}'",
+                    directive
+                );
+            }
+        }
+
+        MetaRobots { directives }
+    }
+
+    /// Returns `true` if no directives are set.
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    /// Returns `true` if the page must be kept out of indexes
+    /// (`noindex` or `none`).
+    pub fn is_noindex(&self) -> bool {
+        self.directives.iter().any(|directive| {
+            directive == "noindex" || directive == "none"
+        })
+    }
+
+    /// Returns the `<meta name="robots">` tag, or an empty string if no
+    /// directives are set.
+    pub fn to_meta_tag(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        format!(
+            "<meta name=\"robots\" content=\"{}\">",
+            self.directives.join(", ")
+        )
+    }
+}
+
+/// Returns whether `directive` is documented, with a valid value for a
+/// `name:value` directive.
+fn is_known_directive(directive: &str) -> bool {
+    let preview_length = |value: &str| {
+        value.parse::<i64>().map_or(false, |length| length >= -1)
+    };
+    match directive.split_once(':') {
+        None => META_ROBOTS_DIRECTIVES.contains(&directive),
+        Some(("max-snippet" | "max-video-preview", value)) => {
+            preview_length(value)
+        }
+        Some(("max-image-preview", value)) => {
+            ["none", "standard", "large"]
+                .contains(&value.to_ascii_lowercase().as_str())
+        }
+        Some(("unavailable_after", value)) => !value.is_empty(),
+        Some(_) => false,
+    }
+}
+
 /// Creates a TxtData object from metadata.
 ///
 /// This function processes metadata to create a robots.txt configuration.
-/// It validates the permalink URL and ensures it's properly formatted.
+/// It validates the permalink URL and ensures it's properly formatted, and
+/// reads crawl rules with [`TxtData::from_metadata`]. Invalid rules are
+/// logged and ignored, so a bad front-matter entry never blocks the build.
 ///
 /// # Arguments
 /// * `metadata` - A reference to a HashMap containing metadata key-value pairs
@@ -47,21 +218,31 @@
 /// let mut metadata = HashMap::new();
 /// metadata.insert("permalink".to_string(), "https://example.com".to_string());
 ///
+/// metadata.insert("robots_disallow".to_string(), "/drafts/".to_string());
+///
 /// let txt_data = create_txt_data(&metadata);
 /// assert_eq!(txt_data.permalink, "https://example.com");
+/// assert_eq!(txt_data.disallow, vec!["/drafts/"]);
 /// ```
 pub fn create_txt_data(metadata: &HashMap<String, String>) -> TxtData {
+    let data = TxtData::from_metadata(metadata).unwrap_or_else(|e| {
+        log::warn!("Ignoring robots.txt rules: {}", e);
+        TxtData::default()
+    });
+
     TxtData {
         permalink: sanitize_url(
             metadata.get("permalink").unwrap_or(&String::new()),
         ),
+        ..data
     }
 }
 
 /// Generates robots.txt content.
 ///
 /// This function takes a TxtData object and generates properly formatted
-/// robots.txt content following the standard protocol.
+/// robots.txt content following the standard protocol, including any
+/// `Allow`, `Disallow` and `Crawl-delay` rules.
 ///
 /// # Arguments
 /// * `data` - A reference to a TxtData object containing the configuration
@@ -76,10 +257,13 @@ pub fn create_txt_data(metadata: &HashMap<String, String>) -> TxtData {
 ///
 /// let data = TxtData {
 ///     permalink: "https://example.com".to_string(),
+///     disallow: vec!["/drafts/".to_string()],
+///     ..TxtData::default()
 /// };
 ///
 /// let content = generate_txt_content(&data);
 /// assert!(content.contains("User-agent: *"));
+/// assert!(content.contains("Disallow: /drafts/"));
 /// assert!(content.contains("Sitemap:"));
 /// ```
 pub fn generate_txt_content(data: &TxtData) -> String {
@@ -87,10 +271,7 @@ pub fn generate_txt_content(data: &TxtData) -> String {
         return String::new();
     }
 
-    format!(
-        "User-agent: *\nAllow: /\nSitemap: {}/sitemap.xml",
-        data.permalink.trim_end_matches('/')
-    )
+    data.generate_content()
 }
 
 /// Sanitizes and validates a URL.
@@ -156,9 +337,7 @@ fn test_create_txt_data() {
 
     #[test]
     fn test_generate_txt_content() {
-        let data = TxtData {
-            permalink: "https://example.com".to_string(),
-        };
+        let data = TxtData::new("https://example.com".to_string());
 
         let content = generate_txt_content(&data);
         assert_eq!(
@@ -167,6 +346,118 @@ fn test_generate_txt_content() {
         );
     }
 
+    #[test]
+    fn test_create_txt_data_with_rules() {
+        let mut metadata = HashMap::new();
+        let _ = metadata.insert(
+            "permalink".to_string(),
+            "https://example.com/".to_string(),
+        );
+        let _ = metadata
+            .insert("robots_allow".to_string(), "/blog/".to_string());
+        let _ = metadata.insert(
+            "robots_disallow".to_string(),
+            "/drafts/, /search/".to_string(),
+        );
+
+        let data = create_txt_data(&metadata);
+        assert_eq!(
+            generate_txt_content(&data),
+            "User-agent: *\nAllow: /blog/\nDisallow: /drafts/\nDisallow: /search/\nSitemap: https://example.com/sitemap.xml"
+        );
+    }
+
+    #[test]
+    fn test_create_txt_data_ignores_invalid_rules() {
+        let mut metadata = HashMap::new();
+        let _ = metadata.insert(
+            "permalink".to_string(),
+            "https://example.com".to_string(),
+        );
+        let _ = metadata.insert(
+            "robots_disallow".to_string(),
+            "drafts".to_string(),
+        );
+
+        let data = create_txt_data(&metadata);
+        assert_eq!(data.permalink, "https://example.com");
+        assert!(data.disallow.is_empty());
+    }
+
+    #[test]
+    fn test_meta_robots_from_metadata() {
+        let mut metadata = HashMap::new();
+        assert!(MetaRobots::from_metadata(&metadata).is_empty());
+
+        let _ = metadata
+            .insert("robots".to_string(), "index, follow".to_string());
+        let robots = MetaRobots::from_metadata(&metadata);
+        assert!(!robots.is_noindex());
+
+        let _ = metadata.insert(
+            "meta_robots".to_string(),
+            "[NoIndex, noarchive]".to_string(),
+        );
+        let robots = MetaRobots::from_metadata(&metadata);
+        assert_eq!(robots.directives, vec!["noindex", "noarchive"]);
+        assert!(robots.is_noindex());
+        assert_eq!(
+            robots.to_meta_tag(),
+            r#"<meta name="robots" content="noindex, noarchive">"#
+        );
+    }
+
+    #[test]
+    fn test_meta_robots_value_directives() {
+        let mut metadata = HashMap::new();
+        let _ = metadata.insert(
+            "robots".to_string(),
+            "max-snippet:-1, Max-Image-Preview: large, max-video-preview:30, \
+             indexifembedded noodp, unavailable_after: Sunday, 01-Jan-2034 00:00:00 GMT"
+                .to_string(),
+        );
+        let robots = MetaRobots::from_metadata(&metadata);
+        assert_eq!(
+            robots.directives,
+            [
+                "max-snippet:-1",
+                "max-image-preview:large",
+                "max-video-preview:30",
+                "indexifembedded",
+                "noodp",
+                "unavailable_after:Sunday, 01-Jan-2034 00:00:00 GMT",
+            ]
+        );
+        assert!(robots
+            .directives
+            .iter()
+            .all(|d| is_known_directive(d)));
+        assert!(!robots.is_noindex());
+    }
+
+    #[test]
+    fn test_meta_robots_none_and_unknown() {
+        let mut metadata = HashMap::new();
+        let _ =
+            metadata.insert("robots".to_string(), "none".to_string());
+        assert!(MetaRobots::from_metadata(&metadata).is_noindex());
+
+        // Unknown directives are kept, and only warned about.
+        let _ = metadata.insert(
+            "robots".to_string(),
+            "noindex, hide, max-snippet:many".to_string(),
+        );
+        let robots = MetaRobots::from_metadata(&metadata);
+        assert_eq!(
+            robots.directives,
+            ["noindex", "hide", "max-snippet:many"]
+        );
+        assert!(robots.is_noindex());
+        assert!(!is_known_directive("hide"));
+        assert!(!is_known_directive("max-snippet:many"));
+        assert!(!is_known_directive("max-image-preview:huge"));
+    }
+
     #[test]
     fn test_sanitize_url_valid() {
         assert_eq!(
@@ -194,9 +485,7 @@ fn test_sanitize_url_invalid() {
 
     #[test]
     fn test_generate_txt_content_empty() {
-        let data = TxtData {
-            permalink: String::new(),
-        };
+        let data = TxtData::new(String::new());
 
         let content = generate_txt_content(&data);
         assert!(content.is_empty());
@@ -204,9 +493,7 @@ fn test_generate_txt_content_empty() {
 
     #[test]
     fn test_generate_txt_content_trailing_slash() {
-        let data = TxtData {
-            permalink: "https://example.com/".to_string(),
-        };
+        let data = TxtData::new("https://example.com/".to_string());
 
         let content = generate_txt_content(&data);
         assert_eq!(
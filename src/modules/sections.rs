@@ -0,0 +1,426 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Section index pages
+//!
+//! A page published at `/blog/post/` implies a `/blog/` section, but
+//! nothing is served there unless the content holds a page for it.
+//! [`generate_section_indexes`] fills those gaps with a landing page
+//! listing the pages of each section that has no explicit index.
+//!
+//! # Examples
+//!
+//! ```
+//! use staticdatagen::models::data::FileData;
+//! use staticdatagen::modules::sections::{
+//!     generate_section_indexes, DEFAULT_SECTION_TEMPLATE,
+//! };
+//!
+//! let files = vec![FileData::new(
+//!     "post.md".to_string(),
+//!     "---\ntitle: Post\npermalink: https://example.com/blog/post/\n---\nHello.".to_string(),
+//! )];
+//!
+//! let indexes = generate_section_indexes(&files, DEFAULT_SECTION_TEMPLATE);
+//! assert_eq!(indexes.len(), 1);
+//! assert_eq!(indexes[0].0, "blog/index.html");
+//! assert!(indexes[0].1.contains(r#"<a href="/blog/post/index.html">Post</a>"#));
+//! ```
+//!
+//! Links and index files follow the compile's
+//! [`UrlOptions`](crate::utilities::permalink::UrlOptions), like
+//! navigation, so they always point at written files.
+
+use crate::models::data::FileData;
+use crate::utilities::directory::to_title_case;
+use crate::utilities::html::html_escape;
+use crate::utilities::metadata::{meta_date, meta_str};
+use crate::utilities::permalink::{
+    page_output_path, resolve_url, UrlOptions,
+};
+use metadata_gen::extract_and_prepare_metadata;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// The template used for section index pages by default.
+///
+/// `{{title}}` is replaced with the section title, `{{section}}` with its
+/// path (e.g. `/blog/`) and `{{pages}}` with the list of its pages.
+pub const DEFAULT_SECTION_TEMPLATE: &str = "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{{title}}</title>
+</head>
+<body>
+<main>
+<h1>{{title}}</h1>
+{{pages}}
+</main>
+</body>
+</html>
+";
+
+/// Options for the generated section index pages.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SectionIndexOptions {
+    /// The page template (see [`DEFAULT_SECTION_TEMPLATE`]).
+    pub template: String,
+    /// Sections that get no generated index, such as `drafts` or
+    /// `/docs/api/`. Their subsections are excluded too.
+    pub excluded: Vec<String>,
+}
+
+impl Default for SectionIndexOptions {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_SECTION_TEMPLATE.to_string(),
+            excluded: Vec::new(),
+        }
+    }
+}
+
+/// A page listed on a section index.
+#[derive(Debug, Clone)]
+struct SectionPage {
+    /// The page path, such as `/blog/post/`.
+    path: String,
+    /// The permalink path as written, such as `blog/post` for a flat
+    /// page, resolved into the link by [`resolve_url`].
+    link: String,
+    /// The page title.
+    title: String,
+    /// The publication date, as `YYYY-MM-DD`.
+    date: Option<String>,
+    /// The page description.
+    excerpt: String,
+}
+
+/// Generates an index page for every section of `files` that has none,
+/// rendered with `template` and the default [`UrlOptions`].
+///
+/// See [`generate_section_indexes_with_options`].
+pub fn generate_section_indexes(
+    files: &[FileData],
+    template: &str,
+) -> Vec<(String, String)> {
+    generate_section_indexes_with_options(
+        files,
+        &SectionIndexOptions {
+            template: template.to_string(),
+            ..SectionIndexOptions::default()
+        },
+        &UrlOptions::default(),
+    )
+}
+
+/// Generates an index page for every section of `files` that has none.
+///
+/// A page's path is the path of its `permalink`, or `/<file stem>/`
+/// without one; every directory above it, except the site root, is a
+/// section. A section has an explicit index when a page is published at
+/// its path. Each generated index lists every page under its section,
+/// newest first, with its title, date and description.
+///
+/// Page links and the index files are resolved with `urls`, as the
+/// compiler writes pages, so `/blog/` is written to `blog/index.html`,
+/// or `blog.html` under [`TrailingSlash::Never`].
+///
+/// Returns `(file name, html)` pairs, such as `("blog/index.html", ..)`,
+/// with each section before its subsections.
+///
+/// [`TrailingSlash::Never`]: crate::utilities::permalink::TrailingSlash::Never
+pub fn generate_section_indexes_with_options(
+    files: &[FileData],
+    options: &SectionIndexOptions,
+    urls: &UrlOptions,
+) -> Vec<(String, String)> {
+    let mut pages: Vec<SectionPage> =
+        files.iter().filter_map(section_page).collect();
+    pages.sort_by(|a, b| {
+        b.date.cmp(&a.date).then_with(|| a.title.cmp(&b.title))
+    });
+
+    let published: HashSet<&str> =
+        pages.iter().map(|page| page.path.as_str()).collect();
+    let excluded: Vec<String> = options
+        .excluded
+        .iter()
+        .map(|section| normalize_path(section))
+        .filter(|section| section != "/")
+        .collect();
+
+    let mut sections: BTreeMap<String, Vec<&SectionPage>> =
+        BTreeMap::new();
+    for page in &pages {
+        for section in ancestors(&page.path) {
+            if !published.contains(section.as_str())
+                && !excluded
+                    .iter()
+                    .any(|e| section.starts_with(e.as_str()))
+            {
+                sections.entry(section).or_default().push(page);
+            }
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(section, pages)| {
+            let file_name =
+                page_output_path(section.trim_matches('/'), None, urls)
+                    .iter()
+                    .map(|segment| segment.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+            let html = render_section(
+                &options.template,
+                &section,
+                &pages,
+                urls,
+            );
+            (file_name, html)
+        })
+        .collect()
+}
+
+/// Reads the listing entry of a file, skipping files whose metadata
+/// cannot be extracted.
+fn section_page(file: &FileData) -> Option<SectionPage> {
+    let (metadata, _, _) =
+        extract_and_prepare_metadata(&file.content).ok()?;
+    let stem = Path::new(&file.name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let link = match meta_str(&metadata, "permalink") {
+        Some(link) if !link.trim().is_empty() => {
+            let link = link.trim();
+            url::Url::parse(link)
+                .map(|url| url.path().to_string())
+                .unwrap_or_else(|_| link.to_string())
+        }
+        _ if stem == "index" => String::new(),
+        _ => format!("{}/", stem),
+    };
+
+    Some(SectionPage {
+        path: normalize_path(&link),
+        link,
+        title: text(&metadata, "title").unwrap_or(stem),
+        date: page_date(&metadata),
+        excerpt: text(&metadata, "description").unwrap_or_default(),
+    })
+}
+
+/// Returns the trimmed, non-empty value of `key`.
+fn text(
+    metadata: &HashMap<String, String>,
+    key: &str,
+) -> Option<String> {
+    meta_str(metadata, key)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Returns the `date` or `pub_date` of a page, as `YYYY-MM-DD`.
+fn page_date(metadata: &HashMap<String, String>) -> Option<String> {
+    ["date", "pub_date"].iter().find_map(|key| {
+        meta_date(metadata, key)
+            .ok()
+            .flatten()
+            .map(|date| date.date().to_string())
+    })
+}
+
+/// Writes `path` with a single leading and trailing slash.
+fn normalize_path(path: &str) -> String {
+    let path = path.trim().trim_matches('/');
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{}/", path)
+    }
+}
+
+/// Returns the sections above `path`, outermost first, so `/a/b/c/`
+/// gives `/a/` and `/a/b/`.
+pub(crate) fn ancestors(path: &str) -> Vec<String> {
+    let segments: Vec<&str> =
+        path.split('/').filter(|s| !s.is_empty()).collect();
+    (1..segments.len())
+        .map(|depth| format!("/{}/", segments[..depth].join("/")))
+        .collect()
+}
+
+/// Renders the index page of `section`, linking its pages with `urls`.
+fn render_section(
+    template: &str,
+    section: &str,
+    pages: &[&SectionPage],
+    urls: &UrlOptions,
+) -> String {
+    let name = section
+        .trim_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .replace(['-', '_'], " ");
+
+    let mut list =
+        String::from("<ul role=\"list\" class=\"section-pages\">\n");
+    for page in pages {
+        list.push_str(&format!(
+            "<li class=\"section-page\"><a href=\"{}\">{}</a>",
+            html_escape(&resolve_url(&page.link, urls)),
+            html_escape(&page.title)
+        ));
+        if let Some(date) = &page.date {
+            list.push_str(&format!(
+                " <time datetime=\"{0}\">{0}</time>",
+                date
+            ));
+        }
+        if !page.excerpt.is_empty() {
+            list.push_str(&format!(
+                "<p>{}</p>",
+                html_escape(&page.excerpt)
+            ));
+        }
+        list.push_str("</li>\n");
+    }
+    list.push_str("</ul>\n");
+
+    template
+        .replace("{{title}}", &html_escape(&to_title_case(&name)))
+        .replace("{{section}}", &html_escape(section))
+        .replace("{{pages}}", &list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::page;
+    use crate::utilities::permalink::TrailingSlash;
+
+    #[test]
+    fn test_generate_section_indexes() {
+        let files = vec![
+            page("index.md", "title: Home"),
+            page(
+                "old.md",
+                "title: Old\ndate: 2024-01-01\ndescription: A <b>old</b> post\npermalink: https://example.com/blog/old/",
+            ),
+            page(
+                "new.md",
+                "title: New\ndate: 2024-06-01\npermalink: /blog/2024/new/",
+            ),
+            page(
+                "guide.md",
+                "title: Guide\npermalink: https://example.com/docs/guide/",
+            ),
+            page("docs.md", "title: Docs"),
+        ];
+
+        let indexes = generate_section_indexes(
+            &files,
+            "{{title}}|{{section}}|{{pages}}",
+        );
+        let names: Vec<&str> =
+            indexes.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["blog/index.html", "blog/2024/index.html"]);
+
+        let blog = &indexes[0].1;
+        assert!(blog.starts_with("Blog|/blog/|"));
+        let new = blog.find("/blog/2024/new/").unwrap();
+        let old = blog.find("/blog/old/").unwrap();
+        assert!(new < old, "{}", blog);
+        assert!(blog.contains(
+            r#"<time datetime="2024-01-01">2024-01-01</time><p>A &lt;b&gt;old&lt;/b&gt; post</p>"#
+        ));
+        assert!(indexes[1].1.starts_with("2024|/blog/2024/|"));
+    }
+
+    #[test]
+    fn test_generate_section_indexes_excluded() {
+        let files = vec![
+            page("a.md", "permalink: /blog/2024/a/"),
+            page("b.md", "permalink: /drafts/b/"),
+        ];
+        let options = SectionIndexOptions {
+            excluded: vec!["blog".to_string(), "/".to_string()],
+            ..SectionIndexOptions::default()
+        };
+
+        let indexes = generate_section_indexes_with_options(
+            &files,
+            &options,
+            &UrlOptions::default(),
+        );
+        assert_eq!(indexes.len(), 1);
+        assert_eq!(indexes[0].0, "drafts/index.html");
+        assert!(indexes[0].1.contains("<title>Drafts</title>"));
+        assert!(indexes[0]
+            .1
+            .contains(r#"<a href="/drafts/b/index.html">b</a>"#));
+    }
+
+    #[test]
+    fn test_generate_section_indexes_url_options() {
+        let files = vec![
+            page("a.md", "permalink: /blog/a/"),
+            page("b.md", "permalink: https://example.com/blog/b"),
+        ];
+        let options = SectionIndexOptions::default();
+
+        let pretty = UrlOptions {
+            pretty_urls: true,
+            ..UrlOptions::default()
+        };
+        let indexes = generate_section_indexes_with_options(
+            &files, &options, &pretty,
+        );
+        assert_eq!(indexes[0].0, "blog/index.html");
+        assert!(indexes[0].1.contains(r#"<a href="/blog/a/">a</a>"#));
+        assert!(indexes[0].1.contains(r#"<a href="/blog/b/">b</a>"#));
+
+        let never = UrlOptions {
+            policy: TrailingSlash::Never,
+            ..UrlOptions::default()
+        };
+        let indexes = generate_section_indexes_with_options(
+            &files, &options, &never,
+        );
+        assert_eq!(indexes[0].0, "blog.html");
+        assert!(indexes[0]
+            .1
+            .contains(r#"<a href="/blog/a.html">a</a>"#));
+
+        let preserve = UrlOptions {
+            policy: TrailingSlash::Preserve,
+            index_file: "default.htm".to_string(),
+            ..UrlOptions::default()
+        };
+        let indexes = generate_section_indexes_with_options(
+            &files, &options, &preserve,
+        );
+        assert_eq!(indexes[0].0, "blog/default.htm");
+        assert!(indexes[0]
+            .1
+            .contains(r#"<a href="/blog/a/default.htm">a</a>"#));
+        assert!(indexes[0]
+            .1
+            .contains(r#"<a href="/blog/b.html">b</a>"#));
+    }
+
+    #[test]
+    fn test_ancestors() {
+        assert!(ancestors("/").is_empty());
+        assert!(ancestors("/about/").is_empty());
+        assert_eq!(ancestors("/a/b/c/"), ["/a/", "/a/b/"]);
+        assert_eq!(normalize_path("blog"), "/blog/");
+        assert_eq!(normalize_path(" /blog// "), "/blog/");
+    }
+}
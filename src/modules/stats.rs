@@ -0,0 +1,230 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Content statistics
+//!
+//! Aggregates editorial statistics over a set of source files: page and
+//! word counts, pages per top-level section, tag frequency, average
+//! reading time and pages missing a meta description. The resulting
+//! [`SiteStats`] report is serializable so it can feed dashboards.
+
+use crate::generators::tags::{parse_tags, TagOptions};
+use crate::models::data::FileData;
+use crate::modules::plaintext::word_count;
+use crate::modules::sections::ancestors;
+use crate::utilities::directory::extract_front_matter;
+use metadata_gen::extract_and_prepare_metadata;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// The average reading speed used to estimate reading time.
+const WORDS_PER_MINUTE: f64 = 200.0;
+
+/// The section name used for pages at the root of the site.
+const ROOT_SECTION: &str = "/";
+
+/// Aggregate statistics for a site's content.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SiteStats {
+    /// The total number of pages.
+    pub total_pages: usize,
+    /// The number of pages in each top-level section (`/` for the root).
+    pub pages_per_section: BTreeMap<String, usize>,
+    /// The total number of words across all pages.
+    pub total_words: usize,
+    /// The number of pages each tag appears on.
+    pub tag_frequency: BTreeMap<String, usize>,
+    /// The average reading time per page, in minutes.
+    pub average_reading_time: f64,
+    /// The number of pages without a `description`.
+    pub pages_missing_description: usize,
+}
+
+/// Analyses the given source files and returns their [`SiteStats`].
+///
+/// Words are counted on the page body (front matter excluded) with
+/// [`word_count`], and tags are read with [`parse_tags`]. A page's
+/// section is the outermost directory above its `permalink` path, or
+/// above its file name without one, so pages at the top level, such as
+/// `/about/`, count under `/`, as for section indexes.
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::models::data::FileData;
+/// use staticdatagen::modules::stats::analyze;
+///
+/// let files = vec![FileData::new(
+///     "post.md".to_string(),
+///     "---\ntitle: Post\npermalink: https://example.com/blog/post/\n---\nHello world.".to_string(),
+/// )];
+///
+/// let stats = analyze(&files);
+/// assert_eq!(stats.total_pages, 1);
+/// assert_eq!(stats.total_words, 2);
+/// assert_eq!(stats.pages_per_section["blog"], 1);
+/// assert_eq!(stats.pages_missing_description, 1);
+/// ```
+pub fn analyze(files: &[FileData]) -> SiteStats {
+    let mut stats = SiteStats {
+        total_pages: files.len(),
+        ..SiteStats::default()
+    };
+
+    for file in files {
+        let metadata = extract_and_prepare_metadata(&file.content)
+            .map(|(metadata, _, _)| metadata)
+            .unwrap_or_default();
+
+        let body = extract_front_matter(&file.content);
+        stats.total_words += word_count(body).unwrap_or_else(|e| {
+            log::warn!("Failed to count words in {}: {}", file.name, e);
+            0
+        });
+
+        *stats
+            .pages_per_section
+            .entry(section(file, &metadata))
+            .or_default() += 1;
+
+        for tag in parse_tags(&metadata, &TagOptions::default()) {
+            *stats.tag_frequency.entry(tag).or_default() += 1;
+        }
+
+        if metadata
+            .get("description")
+            .map_or(true, |description| description.trim().is_empty())
+        {
+            stats.pages_missing_description += 1;
+        }
+    }
+
+    if stats.total_pages > 0 {
+        stats.average_reading_time = stats.total_words as f64
+            / WORDS_PER_MINUTE
+            / stats.total_pages as f64;
+    }
+
+    stats
+}
+
+/// Returns the top-level section of a page.
+fn section(
+    file: &FileData,
+    metadata: &HashMap<String, String>,
+) -> String {
+    let path = match metadata.get("permalink").map(|link| link.trim()) {
+        Some(link) if !link.is_empty() => url::Url::parse(link)
+            .map(|url| url.path().to_string())
+            .unwrap_or_else(|_| link.to_string()),
+        _ => Path::new(&file.name)
+            .with_extension("")
+            .to_string_lossy()
+            .replace('\\', "/"),
+    };
+
+    ancestors(&path)
+        .first()
+        .map(|section| section.trim_matches('/').to_string())
+        .unwrap_or_else(|| ROOT_SECTION.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::page_with_body;
+
+    #[test]
+    fn test_analyze_empty() {
+        let stats = analyze(&[]);
+        assert_eq!(stats, SiteStats::default());
+    }
+
+    #[test]
+    fn test_analyze_site() {
+        let files = vec![
+            page_with_body(
+                "index.md",
+                "title: Home\ndescription: Welcome\npermalink: https://example.com/",
+                "Welcome to the site.",
+            ),
+            page_with_body(
+                "first.md",
+                "title: First\ntags: rust, web\npermalink: https://example.com/blog/first/",
+                "Writing rust for the web.",
+            ),
+            page_with_body(
+                "second.md",
+                "title: Second\ndescription: More\ntags: rust\npermalink: https://example.com/blog/second/",
+                "More rust.",
+            ),
+        ];
+
+        let stats = analyze(&files);
+
+        assert_eq!(stats.total_pages, 3);
+        assert_eq!(stats.total_words, 11);
+        assert_eq!(stats.pages_per_section[ROOT_SECTION], 1);
+        assert_eq!(stats.pages_per_section["blog"], 2);
+        assert_eq!(stats.tag_frequency["rust"], 2);
+        assert_eq!(stats.tag_frequency["web"], 1);
+        assert_eq!(stats.pages_missing_description, 1);
+        assert!(
+            (stats.average_reading_time - 11.0 / 200.0 / 3.0).abs()
+                < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_section_falls_back_to_file_name() {
+        let file = FileData::new(
+            "docs/guide.md".to_string(),
+            "No front matter".to_string(),
+        );
+        assert_eq!(section(&file, &HashMap::new()), "docs");
+
+        let root = FileData::new("guide.md".to_string(), String::new());
+        assert_eq!(section(&root, &HashMap::new()), ROOT_SECTION);
+    }
+
+    #[test]
+    fn test_section_of_top_level_permalink() {
+        let files = vec![
+            page_with_body(
+                "about.md",
+                "title: About\npermalink: https://example.com/about/",
+                "About us.",
+            ),
+            page_with_body(
+                "contact.md",
+                "title: Contact\npermalink: /contact",
+                "",
+            ),
+            page_with_body(
+                "docs/guide.md",
+                "title: Guide\npermalink: /docs/2024/guide/",
+                "",
+            ),
+        ];
+
+        let stats = analyze(&files);
+
+        assert_eq!(stats.pages_per_section[ROOT_SECTION], 2);
+        assert_eq!(stats.pages_per_section["docs"], 1);
+        assert!(!stats.pages_per_section.contains_key("about"));
+    }
+
+    #[test]
+    fn test_stats_serialize() {
+        let stats = analyze(&[page_with_body(
+            "post.md",
+            "title: Post",
+            "One two three.",
+        )]);
+        let json = serde_json::to_string(&stats).unwrap();
+
+        assert!(json.contains("\"total_pages\":1"));
+        assert!(json.contains("\"total_words\":3"));
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Template variable checks
+//!
+//! The template engine stops at the first tag missing from the context,
+//! so a typo in a layout surfaces as a render error on whichever page
+//! happens to use it first. [`validate_template`] scans a template ahead
+//! of rendering and reports every variable it needs that the context
+//! lacks, with the line it appears on.
+//!
+//! # Examples
+//!
+//! ```
+//! use staticdatagen::modules::template::validate_template;
+//! use staticdatagen::Context;
+//!
+//! let mut context = Context::new();
+//! context.set("title".to_string(), "Home".to_string());
+//!
+//! let missing = validate_template("<h1>{{title}}</h1>\n{{tittle}}", &context);
+//! assert_eq!(missing.len(), 1);
+//! assert_eq!(missing[0].name, "tittle");
+//! assert_eq!(missing[0].line, 2);
+//! ```
+
+use staticweaver::{Context, Engine};
+use std::{fmt, fs, io, path::Path};
+
+/// A template variable missing from the render context.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MissingVar {
+    /// The variable name, exactly as written between the delimiters.
+    pub name: String,
+    /// The 1-based line of its first use in the template.
+    pub line: usize,
+}
+
+impl fmt::Display for MissingVar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' (line {})", self.name, self.line)
+    }
+}
+
+/// Returns the `{{ variables }}` used by `template` that `context` does
+/// not define, once each, in order of first use.
+///
+/// Tags are matched the way the engine renders them: the name is taken
+/// verbatim, so `{{ title }}` needs a ` title ` key. An unclosed tag is
+/// left to the engine to report.
+pub fn validate_template(
+    template: &str,
+    context: &Context,
+) -> Vec<MissingVar> {
+    missing_vars(template, context, "{{", "}}")
+}
+
+/// Checks the `layout` template of `engine` against `context`, like
+/// [`validate_template`] but with the engine's template directory and
+/// delimiters.
+///
+/// # Errors
+///
+/// Returns an error if `<layout>.html` cannot be read.
+pub fn validate_layout(
+    engine: &Engine,
+    layout: &str,
+    context: &Context,
+) -> io::Result<Vec<MissingVar>> {
+    let path = Path::new(&engine.template_path)
+        .join(format!("{}.html", layout));
+    let template = fs::read_to_string(path)?;
+    Ok(missing_vars(
+        &template,
+        context,
+        &engine.open_delim,
+        &engine.close_delim,
+    ))
+}
+
+/// Scans `template` for `open`…`close` tags missing from `context`.
+fn missing_vars(
+    template: &str,
+    context: &Context,
+    open: &str,
+    close: &str,
+) -> Vec<MissingVar> {
+    let mut missing: Vec<MissingVar> = Vec::new();
+    let mut offset = 0;
+
+    while let Some(start) = template[offset..].find(open) {
+        let tag_start = offset + start + open.len();
+        let end = match template[tag_start..].find(close) {
+            Some(end) => end,
+            None => break,
+        };
+        let name = &template[tag_start..tag_start + end];
+        if context.get(name).is_none()
+            && !missing.iter().any(|var| var.name == name)
+        {
+            missing.push(MissingVar {
+                name: name.to_string(),
+                line: template[..tag_start].matches('\n').count() + 1,
+            });
+        }
+        offset = tag_start + end + close.len();
+    }
+
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn context(keys: &[&str]) -> Context {
+        let mut context = Context::new();
+        for key in keys {
+            context.set(key.to_string(), String::new());
+        }
+        context
+    }
+
+    #[test]
+    fn test_validate_template() {
+        let template =
+            "{{title}}\n{{ body }}\n{{missing}} {{missing}}\n{{lang}}";
+        let missing =
+            validate_template(template, &context(&["title", "lang"]));
+
+        assert_eq!(
+            missing,
+            vec![
+                MissingVar {
+                    name: " body ".to_string(),
+                    line: 2
+                },
+                MissingVar {
+                    name: "missing".to_string(),
+                    line: 3
+                },
+            ]
+        );
+        assert_eq!(missing[1].to_string(), "'missing' (line 3)");
+        assert!(validate_template(
+            "<p>{{title}}</p>",
+            &context(&["title"])
+        )
+        .is_empty());
+        assert!(
+            validate_template("{{unclosed", &context(&[])).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_validate_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("page.html"), "<<title>> {{other}}")
+            .unwrap();
+        let mut engine = Engine::new(
+            dir.path().to_str().unwrap(),
+            Duration::from_secs(60),
+        );
+        engine.set_delimiters("<<", ">>");
+
+        assert!(validate_layout(&engine, "page", &context(&["title"]))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            validate_layout(&engine, "page", &context(&[])).unwrap(),
+            vec![MissingVar {
+                name: "title".to_string(),
+                line: 1
+            }]
+        );
+        assert!(
+            validate_layout(&engine, "missing", &context(&[])).is_err()
+        );
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Flat lists of the public pages of a site
+//!
+//! Link checkers, audits and LLM crawlers often only need the URLs of a
+//! site, not its sitemap. This module produces two plain-text listings:
+//!
+//! - `urls.txt`, one absolute page URL per line;
+//! - `llms.txt`, a Markdown index of the pages with their titles and
+//!   descriptions, following the [llms.txt](https://llmstxt.org)
+//!   convention.
+//!
+//! Pages marked `noindex` or `draft` are left out of both.
+//!
+//! # Examples
+//!
+//! ```
+//! use staticdatagen::models::data::FileData;
+//! use staticdatagen::modules::urls::generate_url_list;
+//! use staticdatagen::utilities::permalink::UrlOptions;
+//!
+//! let files = vec![
+//!     FileData::new(
+//!         "index.md".to_string(),
+//!         "---\ntitle: Home\n---\nWelcome.".to_string(),
+//!     ),
+//!     FileData::new(
+//!         "about.md".to_string(),
+//!         "---\ntitle: About\npermalink: /about-us/\n---\nAbout.".to_string(),
+//!     ),
+//!     FileData::new(
+//!         "wip.md".to_string(),
+//!         "---\ntitle: Soon\ndraft: true\n---\nNot yet.".to_string(),
+//!     ),
+//! ];
+//!
+//! let pretty = UrlOptions { pretty_urls: true, ..UrlOptions::default() };
+//! assert_eq!(
+//!     generate_url_list(&files, "https://example.com", &pretty),
+//!     "https://example.com/\nhttps://example.com/about-us/\n"
+//! );
+//! ```
+
+use crate::models::data::FileData;
+use crate::modules::robots::MetaRobots;
+use crate::utilities::metadata::{
+    meta_bool, meta_str, with_raw_scalars,
+};
+use crate::utilities::permalink::{resolve_url, UrlOptions};
+use crate::utilities::url::to_absolute;
+use metadata_gen::extract_and_prepare_metadata;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The file holding the list of page URLs.
+pub const URL_LIST_FILE: &str = "urls.txt";
+
+/// The file holding the llms.txt index.
+pub const LLMS_TXT_FILE: &str = "llms.txt";
+
+/// A public page of the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PublicPage {
+    /// The absolute URL of the page.
+    url: String,
+    /// The page title.
+    title: String,
+    /// The page description.
+    description: String,
+}
+
+/// Generates the newline-delimited list of public page URLs.
+///
+/// URLs come from each page's `permalink`, or from the file name when
+/// no permalink is set, written with `urls` (see [`page_url`]). The
+/// list is sorted, free of duplicates and ends with a newline; it is
+/// empty if `base_url` is not an absolute URL.
+pub fn generate_url_list(
+    files: &[FileData],
+    base_url: &str,
+    urls: &UrlOptions,
+) -> String {
+    public_pages(files, base_url, urls)
+        .iter()
+        .map(|page| format!("{}\n", page.url))
+        .collect()
+}
+
+/// Generates an llms.txt index of the public pages.
+///
+/// The index starts with `site_name` as its title and `description` as a
+/// summary, then lists every page as a Markdown link followed by its
+/// description, in URL order. Page URLs are written with `urls`, as in
+/// [`generate_url_list`].
+pub fn generate_llms_txt(
+    files: &[FileData],
+    base_url: &str,
+    urls: &UrlOptions,
+    site_name: &str,
+    description: &str,
+) -> String {
+    let mut content = format!("# {}\n", site_name.trim());
+    if !description.trim().is_empty() {
+        content.push_str(&format!("\n> {}\n", description.trim()));
+    }
+
+    let pages = public_pages(files, base_url, urls);
+    if !pages.is_empty() {
+        content.push_str("\n## Pages\n\n");
+    }
+    for page in pages {
+        let title = match page.title.trim() {
+            "" => page.url.as_str(),
+            title => title,
+        };
+        content.push_str(&format!("- [{}]({})", title, page.url));
+        if !page.description.trim().is_empty() {
+            content.push_str(&format!(": {}", page.description.trim()));
+        }
+        content.push('\n');
+    }
+    content
+}
+
+/// Resolves the absolute URL of `file` against `base_url`.
+///
+/// The path of the `permalink`, or without one the file name (`index`
+/// being the home page), is written with [`resolve_url`] under `urls`,
+/// as navigation and the sitemap write it. A relative result is then
+/// joined onto `base_url` with [`to_absolute`], while an absolute
+/// permalink keeps its origin; its query and fragment are kept either
+/// way.
+pub(crate) fn page_url(
+    file: &FileData,
+    metadata: &HashMap<String, String>,
+    base_url: &str,
+    urls: &UrlOptions,
+) -> Option<String> {
+    let permalink = meta_str(metadata, "permalink")
+        .map(str::trim)
+        .filter(|permalink| !permalink.is_empty());
+
+    let permalink = match permalink {
+        Some(permalink) => permalink,
+        None => {
+            let stem = Path::new(&file.name).with_extension("");
+            let path = match stem
+                .to_string_lossy()
+                .replace('\\', "/")
+                .as_str()
+            {
+                "index" => String::new(),
+                path => format!("{}/", path),
+            };
+            return to_absolute(base_url, &resolve_url(&path, urls))
+                .ok();
+        }
+    };
+
+    if let Ok(mut url) = url::Url::parse(permalink) {
+        let path = resolve_url(url.path(), urls);
+        url.set_path(&path);
+        return to_absolute(base_url, url.as_str()).ok();
+    }
+    let (path, suffix) = permalink
+        .find(['?', '#'])
+        .map_or((permalink, ""), |at| permalink.split_at(at));
+    to_absolute(
+        base_url,
+        &format!("{}{}", resolve_url(path, urls), suffix),
+    )
+    .ok()
+}
+
+/// Returns the pages that are neither `noindex` nor drafts, sorted by URL
+/// and without duplicate URLs.
+fn public_pages(
+    files: &[FileData],
+    base_url: &str,
+    urls: &UrlOptions,
+) -> Vec<PublicPage> {
+    let mut pages: Vec<PublicPage> = files
+        .iter()
+        .filter_map(|file| {
+            let (metadata, _, _) =
+                extract_and_prepare_metadata(&file.content).ok()?;
+            let metadata = with_raw_scalars(
+                &file.content,
+                &metadata,
+                &["draft", "robots"],
+            );
+            let is_draft = meta_bool(&metadata, "draft")
+                .unwrap_or_else(|e| {
+                    log::warn!("Listing '{}' anyway: {}", file.name, e);
+                    None
+                })
+                .unwrap_or(false);
+            if is_draft
+                || MetaRobots::from_metadata(&metadata).is_noindex()
+            {
+                return None;
+            }
+
+            Some(PublicPage {
+                url: page_url(file, &metadata, base_url, urls)?,
+                title: meta_str(&metadata, "title")
+                    .unwrap_or_default()
+                    .to_string(),
+                description: meta_str(&metadata, "description")
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        })
+        .collect();
+
+    pages.sort_by(|a, b| a.url.cmp(&b.url));
+    pages.dedup_by(|a, b| a.url == b.url);
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::page;
+    use crate::utilities::permalink::TrailingSlash;
+
+    fn pretty() -> UrlOptions {
+        UrlOptions {
+            pretty_urls: true,
+            ..UrlOptions::default()
+        }
+    }
+
+    fn files() -> Vec<FileData> {
+        vec![
+            page("index.md", "title: Home\ndescription: Welcome"),
+            page("blog/post.md", "title: Post"),
+            page(
+                "about.md",
+                "title: About\ndescription: Who we are\npermalink: https://example.com/about/",
+            ),
+            page("hidden.md", "title: Hidden\nrobots: noindex, nofollow"),
+            page("draft.md", "title: Draft\ndraft: true"),
+            page("quoted.md", "title: Quoted\ndraft: \"yes\""),
+            page("published.md", "title: Published\ndraft: false"),
+        ]
+    }
+
+    #[test]
+    fn test_generate_url_list() {
+        assert_eq!(
+            generate_url_list(
+                &files(),
+                "https://example.com/",
+                &pretty()
+            ),
+            "https://example.com/\n\
+             https://example.com/about/\n\
+             https://example.com/blog/post/\n\
+             https://example.com/published/\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_url_list_keeps_valued_robots_directives() {
+        let files = [page(
+            "snippet.md",
+            "title: Snippet\nrobots: max-snippet:50, max-image-preview:large",
+        )];
+        assert_eq!(
+            generate_url_list(
+                &files,
+                "https://example.com/",
+                &pretty()
+            ),
+            "https://example.com/snippet/\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_url_list_url_options() {
+        let files = [
+            page("index.md", "title: Home"),
+            page("blog/post.md", "title: Post"),
+            page(
+                "about.md",
+                "permalink: https://example.com/about-us/",
+            ),
+            page("flat.md", "permalink: /flat?v=2#top"),
+        ];
+        assert_eq!(
+            generate_url_list(
+                &files,
+                "https://example.com",
+                &UrlOptions::default()
+            ),
+            "https://example.com/about-us/index.html\n\
+             https://example.com/blog/post/index.html\n\
+             https://example.com/flat/index.html?v=2#top\n\
+             https://example.com/index.html\n"
+        );
+        assert_eq!(
+            generate_url_list(
+                &files,
+                "https://example.com",
+                &UrlOptions {
+                    policy: TrailingSlash::Preserve,
+                    ..pretty()
+                }
+            ),
+            "https://example.com/\n\
+             https://example.com/about-us/\n\
+             https://example.com/blog/post/\n\
+             https://example.com/flat.html?v=2#top\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_url_list_invalid_base() {
+        assert_eq!(
+            generate_url_list(&files(), "not a url", &pretty()),
+            ""
+        );
+        assert_eq!(
+            generate_url_list(&[], "https://example.com", &pretty()),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_generate_llms_txt() {
+        let llms = generate_llms_txt(
+            &files(),
+            "https://example.com",
+            &pretty(),
+            "Example",
+            "An example site.",
+        );
+
+        assert_eq!(
+            llms,
+            "# Example\n\n\
+             > An example site.\n\n\
+             ## Pages\n\n\
+             - [Home](https://example.com/): Welcome\n\
+             - [About](https://example.com/about/): Who we are\n\
+             - [Post](https://example.com/blog/post/)\n\
+             - [Published](https://example.com/published/)\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_llms_txt_without_pages() {
+        assert_eq!(
+            generate_llms_txt(
+                &[],
+                "https://example.com",
+                &pretty(),
+                "Example",
+                ""
+            ),
+            "# Example\n"
+        );
+    }
+}
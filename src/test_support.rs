@@ -0,0 +1,34 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Factories shared by the unit tests.
+
+use crate::models::data::FileData;
+use std::collections::HashMap;
+
+/// Builds a metadata map from `(key, value)` pairs.
+pub(crate) fn metadata(
+    pairs: &[(&str, &str)],
+) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Builds a source file with the given front matter and a `Body.` body.
+pub(crate) fn page(name: &str, front_matter: &str) -> FileData {
+    page_with_body(name, front_matter, "Body.")
+}
+
+/// Builds a source file with the given front matter and body.
+pub(crate) fn page_with_body(
+    name: &str,
+    front_matter: &str,
+    body: &str,
+) -> FileData {
+    FileData::new(
+        name.to_string(),
+        format!("---\n{}\n---\n{}", front_matter, body),
+    )
+}
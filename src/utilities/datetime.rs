@@ -0,0 +1,203 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Date parsing and formatting shared by the generators
+//!
+//! Front matter dates are written in several layouts, while each output
+//! expects its own: RSS uses RFC 2822, news sitemaps and JSON-LD use
+//! RFC 3339, and sitemaps and humans.txt use plain calendar dates. This
+//! module parses any accepted layout with [`parse_flexible`] and formats
+//! the result for each target, so a `date:` value accepted by one output
+//! is accepted by all of them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use staticdatagen::utilities::datetime::{
+//!     parse_flexible, to_calendar_date, to_rfc2822, to_rfc3339,
+//! };
+//!
+//! let date = parse_flexible("2024-02-20").unwrap();
+//! assert_eq!(to_rfc2822(&date), "Tue, 20 Feb 2024 00:00:00 +0000");
+//! assert_eq!(to_rfc3339(&date), "2024-02-20T00:00:00Z");
+//! assert_eq!(to_calendar_date(&date), "2024-02-20");
+//!
+//! assert!(parse_flexible("20/02/2024").is_err());
+//! ```
+
+use thiserror::Error;
+use time::{
+    format_description::well_known::{Rfc2822, Rfc3339},
+    Date, Month, OffsetDateTime,
+};
+
+/// Month names, as written in `July 12, 2023` style dates.
+const MONTHS: [(&str, Month); 12] = [
+    ("january", Month::January),
+    ("february", Month::February),
+    ("march", Month::March),
+    ("april", Month::April),
+    ("may", Month::May),
+    ("june", Month::June),
+    ("july", Month::July),
+    ("august", Month::August),
+    ("september", Month::September),
+    ("october", Month::October),
+    ("november", Month::November),
+    ("december", Month::December),
+];
+
+/// Errors returned when a date cannot be parsed.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DateTimeError {
+    /// The date string is empty.
+    #[error("Date is empty")]
+    Empty,
+
+    /// The date string is not in an accepted layout.
+    #[error("Invalid date '{0}': expected an RFC 3339, RFC 2822 or calendar date")]
+    Invalid(String),
+}
+
+/// Parses a date written in any of the accepted layouts.
+///
+/// Accepts RFC 3339, RFC 2822, `YYYY-MM-DD`, `July 12, 2023` and
+/// `12 July 2023`; calendar dates are taken as midnight UTC. Surrounding
+/// whitespace is ignored.
+///
+/// # Errors
+///
+/// Returns [`DateTimeError::Empty`] for an empty string and
+/// [`DateTimeError::Invalid`] for any other unrecognised value.
+pub fn parse_flexible(
+    date: &str,
+) -> Result<OffsetDateTime, DateTimeError> {
+    let date = date.trim();
+    if date.is_empty() {
+        return Err(DateTimeError::Empty);
+    }
+
+    OffsetDateTime::parse(date, &Rfc3339)
+        .or_else(|_| OffsetDateTime::parse(date, &Rfc2822))
+        .ok()
+        .or_else(|| {
+            parse_calendar_date(date)
+                .map(|day| day.midnight().assume_utc())
+        })
+        .ok_or_else(|| DateTimeError::Invalid(date.to_string()))
+}
+
+/// Formats `date` as RFC 2822, as used by RSS.
+pub fn to_rfc2822(date: &OffsetDateTime) -> String {
+    date.format(&Rfc2822).unwrap_or_default()
+}
+
+/// Formats `date` as RFC 3339, as used by news sitemaps and JSON-LD.
+pub fn to_rfc3339(date: &OffsetDateTime) -> String {
+    date.format(&Rfc3339).unwrap_or_default()
+}
+
+/// Formats `date` as a `YYYY-MM-DD` calendar date, as used by sitemaps
+/// and humans.txt.
+pub fn to_calendar_date(date: &OffsetDateTime) -> String {
+    date.date().to_string()
+}
+
+/// Parses `YYYY-MM-DD`, `July 12, 2023` and `12 July 2023` dates.
+fn parse_calendar_date(date: &str) -> Option<Date> {
+    let parts: Vec<&str> = date.split('-').collect();
+    if let [year, month, day] = parts.as_slice() {
+        let month = Month::try_from(month.parse::<u8>().ok()?).ok()?;
+        return Date::from_calendar_date(
+            year.parse().ok()?,
+            month,
+            day.parse().ok()?,
+        )
+        .ok();
+    }
+
+    let month_of = |name: &str| {
+        MONTHS
+            .iter()
+            .find(|(month, _)| name.eq_ignore_ascii_case(month))
+            .map(|(_, month)| *month)
+    };
+    let parts: Vec<&str> = date
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    let (month, day, year) = match parts.as_slice() {
+        [first, second, year] => match month_of(first) {
+            Some(month) => (month, second, year),
+            None => (month_of(second)?, first, year),
+        },
+        _ => return None,
+    };
+    Date::from_calendar_date(
+        year.parse().ok()?,
+        month,
+        day.parse().ok()?,
+    )
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flexible_layouts() {
+        for input in [
+            "2024-02-20T15:15:15Z",
+            "2024-02-20T16:15:15+01:00",
+            "Tue, 20 Feb 2024 15:15:15 GMT",
+            " Tue, 20 Feb 2024 15:15:15 +0000 ",
+        ] {
+            let date = parse_flexible(input).unwrap();
+            assert_eq!(
+                to_rfc3339(&date.to_offset(time::UtcOffset::UTC)),
+                "2024-02-20T15:15:15Z",
+                "{}",
+                input
+            );
+        }
+
+        for input in
+            ["2024-02-20", "February 20, 2024", "20 February 2024"]
+        {
+            let date = parse_flexible(input).unwrap();
+            assert_eq!(
+                to_calendar_date(&date),
+                "2024-02-20",
+                "{}",
+                input
+            );
+            assert_eq!(date.hour(), 0);
+        }
+    }
+
+    #[test]
+    fn test_parse_flexible_errors() {
+        assert_eq!(parse_flexible("  "), Err(DateTimeError::Empty));
+        for input in
+            ["2024-13-01", "20/02/2024", "Smarch 3, 2024", "soon"]
+        {
+            assert_eq!(
+                parse_flexible(input),
+                Err(DateTimeError::Invalid(input.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_formatters() {
+        let date = parse_flexible("2024-02-20T15:15:15Z").unwrap();
+
+        assert_eq!(
+            to_rfc2822(&date),
+            "Tue, 20 Feb 2024 15:15:15 +0000"
+        );
+        assert_eq!(to_rfc3339(&date), "2024-02-20T15:15:15Z");
+        assert_eq!(to_calendar_date(&date), "2024-02-20");
+    }
+}
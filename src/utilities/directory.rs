@@ -8,6 +8,7 @@
 
 use regex::Regex;
 use std::{
+    collections::HashSet,
     error::Error,
     fs, io,
     path::{Path, PathBuf},
@@ -142,16 +143,105 @@ pub fn move_output_directory(
 ///
 /// # Notes
 ///
-/// This function recursively searches all subdirectories.
+/// This function recursively searches all subdirectories; use
+/// [`find_html_files_with_depth`] to bound the search.
 pub fn find_html_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    find_html_files_with_depth(dir, None).map(|found| found.files)
+}
+
+/// The HTML files found by [`find_html_files_with_depth`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlFiles {
+    /// The paths of the HTML files, in directory order.
+    pub files: Vec<PathBuf>,
+    /// The number of subdirectories left unsearched because they are
+    /// deeper than the limit.
+    pub skipped_directories: usize,
+}
+
+/// Finds all HTML files in a directory, descending at most `max_depth`
+/// levels of subdirectories.
+///
+/// Files directly in `dir` are at depth `0`, so `Some(0)` searches `dir`
+/// alone. Subdirectories beyond the limit are skipped; their number is
+/// returned and logged as a warning. `None` searches the whole tree.
+///
+/// # Arguments
+///
+/// * `dir` - A reference to the directory `Path` to search.
+/// * `max_depth` - The deepest subdirectory level to search, if any.
+///
+/// # Returns
+///
+/// An `io::Result<HtmlFiles>` with the paths of the HTML files found and
+/// the number of subdirectories skipped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use staticdatagen::utilities::directory::find_html_files_with_depth;
+///
+/// let found = find_html_files_with_depth(Path::new("public"), Some(3))?;
+/// if found.skipped_directories > 0 {
+///     eprintln!("{} directories were too deep", found.skipped_directories);
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn find_html_files_with_depth(
+    dir: &Path,
+    max_depth: Option<usize>,
+) -> io::Result<HtmlFiles> {
     let mut html_files = Vec::new();
+    let mut skipped = 0;
+    collect_html_files(
+        dir,
+        0,
+        max_depth,
+        &mut html_files,
+        &mut skipped,
+    )?;
+
+    if skipped > 0 {
+        log::warn!(
+            "Skipped {} directories deeper than {} levels in {}",
+            skipped,
+            max_depth.unwrap_or_default(),
+            dir.display()
+        );
+    }
 
+    Ok(HtmlFiles {
+        files: html_files,
+        skipped_directories: skipped,
+    })
+}
+
+/// Collects the HTML files of `dir`, found `depth` levels below the search
+/// root, counting the subdirectories beyond `max_depth` in `skipped`.
+fn collect_html_files(
+    dir: &Path,
+    depth: usize,
+    max_depth: Option<usize>,
+    html_files: &mut Vec<PathBuf>,
+    skipped: &mut usize,
+) -> io::Result<()> {
     for entry in fs::read_dir(dir)? {
         let entry = entry?;
         let path = entry.path();
 
         if path.is_dir() {
-            html_files.extend(find_html_files(&path)?);
+            if max_depth.map_or(false, |max_depth| depth >= max_depth) {
+                *skipped += 1;
+            } else {
+                collect_html_files(
+                    &path,
+                    depth + 1,
+                    max_depth,
+                    html_files,
+                    skipped,
+                )?;
+            }
         } else if let Some(extension) = path.extension() {
             if extension.eq_ignore_ascii_case("html") {
                 html_files.push(path);
@@ -159,7 +249,7 @@ pub fn find_html_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
         }
     }
 
-    Ok(html_files)
+    Ok(())
 }
 
 /// Cleans up the specified directories.
@@ -267,9 +357,78 @@ pub fn to_title_case(s: &str) -> String {
 /// For other headers (like h2, h3, etc.), `itemprop="name"` is used instead of `headline`.
 ///
 /// Empty headers are handled gracefully, resulting in an empty `class` and `id` ending with a hyphen.
+///
+/// Each call starts from a fresh [`HeadingIds`] registry, so ids are only
+/// unique within `header_str`. Use [`format_header_with_id_class_in`] to
+/// keep ids unique across all the headers of a page.
 pub fn format_header_with_id_class(
     header_str: &str,
     id_regex: &Regex,
+) -> String {
+    format_header_with_id_class_in(
+        header_str,
+        id_regex,
+        &mut HeadingIds::default(),
+    )
+}
+
+/// Registry of the heading ids already assigned on a page.
+///
+/// The first heading keeps its derived id; later headings deriving the
+/// same id get a numeric suffix, so two "Introduction" headings become
+/// `h2-introduction` and `h2-introduction-1`.
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::utilities::directory::HeadingIds;
+///
+/// let mut ids = HeadingIds::default();
+/// assert_eq!(ids.unique("h2-introduction"), "h2-introduction");
+/// assert_eq!(ids.unique("h2-introduction"), "h2-introduction-1");
+/// assert_eq!(ids.unique("h2-introduction"), "h2-introduction-2");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeadingIds {
+    /// The ids assigned so far.
+    used: HashSet<String>,
+}
+
+impl HeadingIds {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `id`, or `id` with the first free `-N` suffix if it has
+    /// already been assigned, and records the result.
+    pub fn unique(&mut self, id: &str) -> String {
+        let mut candidate = id.to_string();
+        let mut suffix = 0;
+        while self.used.contains(&candidate) {
+            suffix += 1;
+            candidate = format!("{}-{}", id, suffix);
+        }
+        let _ = self.used.insert(candidate.clone());
+        candidate
+    }
+}
+
+/// Formats a header string like [`format_header_with_id_class`], making
+/// each `id` unique within `ids`.
+///
+/// Pass the same registry for every header of a page so repeated heading
+/// text does not produce duplicate ids.
+///
+/// # Arguments
+///
+/// * `header_str` - A reference to the header string.
+/// * `id_regex` - A reference to a `Regex` object for ID formatting.
+/// * `ids` - The ids already assigned on the page.
+pub fn format_header_with_id_class_in(
+    header_str: &str,
+    id_regex: &Regex,
+    ids: &mut HeadingIds,
 ) -> String {
     // Match HTML header tags with a named capture group for the tag name and allow empty content.
     let re = Regex::new(r"<(?P<tag>\w+)([^>]*)>(?P<content>.*?)</\w+>")
@@ -281,11 +440,11 @@ pub fn format_header_with_id_class(
         let content = caps.name("content").map_or("", |m| m.as_str());
 
         let binding = content.to_lowercase();
-        let id = id_regex.replace_all(&binding, "-");
-        let class = id.clone();
+        let class = id_regex.replace_all(&binding, "-");
+        let id = ids.unique(&format!("{}-{}", tag, class));
 
         format!(
-            r#"<{0}{1} id="{0}-{2}" class="{3}" tabindex="0" aria-label="{4} Heading" {5}>{6}</{0}>"#,
+            r#"<{0}{1} id="{2}" class="{3}" tabindex="0" aria-label="{4} Heading" {5}>{6}</{0}>"#,
             tag,
             attrs,
             id,
@@ -336,12 +495,17 @@ pub fn extract_front_matter(content: &str) -> &str {
 /// # Returns
 ///
 /// A `comrak::ComrakOptions` instance with non-standard Markdown features enabled.
+///
+/// Front matter handling is left off: the compiler strips `---`, `+++` and
+/// JSON front matter before rendering (see
+/// [`split_frontmatter_and_body`](crate::utilities::frontmatter::split_frontmatter_and_body)),
+/// so comrak only ever sees the body.
 pub fn create_comrak_options() -> comrak::ComrakOptions<'static> {
     let mut options = comrak::ComrakOptions::default();
     options.extension.autolink = true;
     options.extension.description_lists = true;
     options.extension.footnotes = true;
-    options.extension.front_matter_delimiter = Some("---".to_owned());
+    options.extension.front_matter_delimiter = None;
     options.extension.strikethrough = true;
     options.extension.superscript = true;
     options.extension.table = true;
@@ -657,10 +821,7 @@ fn test_create_comrak_options() {
         assert!(options.extension.autolink);
         assert!(options.extension.description_lists);
         assert!(options.extension.footnotes);
-        assert_eq!(
-            options.extension.front_matter_delimiter,
-            Some("---".to_owned())
-        );
+        assert_eq!(options.extension.front_matter_delimiter, None);
         assert!(options.extension.strikethrough);
         assert!(options.extension.superscript);
         assert!(options.extension.table);
@@ -725,6 +886,41 @@ fn test_move_output_directory_nonexistent() {
         assert!(result.is_err());
     }
 
+    /// Tests bounding the search for HTML files by depth.
+    #[test]
+    fn test_find_html_files_with_depth() -> io::Result<()> {
+        let base_dir = tempfile::tempdir()?;
+        let mut dir = base_dir.path().to_path_buf();
+        let mut pages = Vec::new();
+        for level in ["", "a", "b", "c"] {
+            dir = dir.join(level);
+            fs::create_dir_all(&dir)?;
+            pages.push(dir.join("index.html"));
+            fs::write(pages.last().unwrap(), "<html></html>")?;
+        }
+
+        for (max_depth, expected, skipped) in [
+            (Some(0), 1, 1),
+            (Some(2), 3, 1),
+            (Some(3), 4, 0),
+            (None, 4, 0),
+        ] {
+            let found =
+                find_html_files_with_depth(base_dir.path(), max_depth)?;
+            let mut files = found.files;
+            files.sort();
+            let mut expected = pages[..expected].to_vec();
+            expected.sort();
+            assert_eq!(files, expected, "{:?}", max_depth);
+            assert_eq!(
+                found.skipped_directories, skipped,
+                "{:?}",
+                max_depth
+            );
+        }
+        Ok(())
+    }
+
     /// Tests finding HTML files in an empty directory.
     #[test]
     fn test_find_html_files_empty() -> io::Result<()> {
@@ -854,6 +1050,34 @@ fn test_format_header_with_id_class_h2() {
         assert!(formatted.contains("itemprop=\"name\""));
     }
 
+    /// Tests that repeated headings on a page get distinct ids.
+    #[test]
+    fn test_format_header_with_id_class_duplicates() {
+        let id_regex = Regex::new(r"[^a-z0-9]+").unwrap();
+        let mut ids = HeadingIds::new();
+
+        let first = format_header_with_id_class_in(
+            "<h2>Introduction</h2>",
+            &id_regex,
+            &mut ids,
+        );
+        let second = format_header_with_id_class_in(
+            "<h2>Introduction</h2>",
+            &id_regex,
+            &mut ids,
+        );
+        let literal = format_header_with_id_class_in(
+            "<h2>Introduction 1</h2>",
+            &id_regex,
+            &mut ids,
+        );
+
+        assert!(first.contains("id=\"h2-introduction\""));
+        assert!(second.contains("id=\"h2-introduction-1\""));
+        assert!(literal.contains("id=\"h2-introduction-1-1\""));
+        assert!(second.contains("class=\"introduction\""));
+    }
+
     /// Tests formatting a header with punctuation.
     #[test]
     fn test_format_header_with_id_class_punctuation() {
@@ -21,10 +21,10 @@
 /// # Returns
 ///
 /// * `Result<(), Box<dyn std::error::Error>>` - A result indicating success or
-///    failure.
-///    - `Ok(())` if the element was written successfully.
-///    - `Err(Box<dyn std::error::Error>)` if an error occurred during the
-///       writing process.
+///   failure.
+///   - `Ok(())` if the element was written successfully.
+///   - `Err(Box<dyn std::error::Error>)` if an error occurred during the
+///     writing process.
 ///
 pub fn write_element(
     writer: &mut Writer<Cursor<Vec<u8>>>,
@@ -1,13 +1,19 @@
 // Copyright © 2025 Static Data Gen. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::models::data::FileData;
+use crate::{models::data::FileData, Error, Result};
 use quick_xml::escape::escape;
-use std::{fs, io, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 /// Reads all files in a directory specified by the given path and returns a vector of FileData.
 ///
 /// Each file is represented as a `FileData` struct containing the name and content of the file.
+/// Hidden files (such as `.DS_Store` or editor swap files) and zero-byte
+/// files are skipped; use [`add_with_options`] to keep empty files or to
+/// get the list of skipped files.
 ///
 /// # Arguments
 ///
@@ -16,32 +22,125 @@
 /// # Returns
 ///
 /// A `Result` containing a vector of `FileData` structs representing all files in the directory,
-/// or an `io::Error` if the directory cannot be read.
-pub fn add(path: &Path) -> io::Result<Vec<FileData>> {
-    let files = fs::read_dir(path)?
-        .filter_map(|entry| {
-            let entry = entry.ok()?;
-            let path = entry.path();
-            if path.is_file() {
-                let file_name =
-                    path.file_name()?.to_string_lossy().to_string();
-                if file_name == ".DS_Store" {
-                    return None;
-                }
-                let content = fs::read_to_string(&path)
-                    .map_err(|e| {
-                        eprintln!(
-                            "Error reading file {:?}: {}",
-                            path, e
-                        );
-                        e
-                    })
-                    .ok()?;
-                Some((file_name, content))
+/// or an error if the directory cannot be read.
+///
+/// # Errors
+///
+/// Returns [`Error::ContentProcessing`] naming the file and the byte
+/// offset of the first invalid sequence if a file is not valid UTF-8,
+/// such as a binary file left in the content directory.
+pub fn add(path: &Path) -> Result<Vec<FileData>> {
+    Ok(add_with_options(path, &AddOptions::default())?.files)
+}
+
+/// Options controlling which files [`add_with_options`] loads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AddOptions {
+    /// Load zero-byte files, logging a warning for each, instead of
+    /// skipping them.
+    pub keep_empty: bool,
+}
+
+/// Why a file was left out of the loaded content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkipReason {
+    /// The file name starts with a `.`.
+    Hidden,
+    /// The file holds no bytes.
+    Empty,
+}
+
+/// A file left out of the loaded content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// Why it was skipped.
+    pub reason: SkipReason,
+}
+
+/// The files of a content directory, and those left out.
+#[derive(Debug, Clone, Default)]
+pub struct LoadedContent {
+    /// The loaded files, in directory order.
+    pub files: Vec<FileData>,
+    /// The skipped files, in directory order.
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Reads the files of a directory like [`add`], applying the given
+/// [`AddOptions`] and reporting the files it skips.
+///
+/// Hidden files are always skipped. Zero-byte files are skipped unless
+/// [`AddOptions::keep_empty`] is set, as they would otherwise become
+/// blank pages listed in the sitemap and feeds.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read or holds a file that
+/// is not valid UTF-8 (see [`add`]).
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use staticdatagen::utilities::file::{add_with_options, AddOptions};
+///
+/// let loaded = add_with_options(Path::new("content"), &AddOptions::default())?;
+/// for skipped in &loaded.skipped {
+///     println!("Skipped {:?} ({:?})", skipped.path, skipped.reason);
+/// }
+/// # Ok::<(), staticdatagen::Error>(())
+/// ```
+pub fn add_with_options(
+    path: &Path,
+    options: &AddOptions,
+) -> Result<LoadedContent> {
+    let mut sources = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(_) => continue,
+        };
+        let file_name = match path.file_name() {
+            Some(name) if path.is_file() => {
+                name.to_string_lossy().to_string()
+            }
+            _ => continue,
+        };
+        if file_name.starts_with('.') {
+            log::debug!("Skipping hidden file {:?}", path);
+            skipped.push(SkippedFile {
+                path,
+                reason: SkipReason::Hidden,
+            });
+            continue;
+        }
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                eprintln!("Error reading file {:?}: {}", path, e);
+                continue;
+            }
+        };
+        if bytes.is_empty() {
+            if options.keep_empty {
+                log::warn!("Loading empty file {:?}", path);
             } else {
-                None
+                log::warn!("Skipping empty file {:?}", path);
+                skipped.push(SkippedFile {
+                    path,
+                    reason: SkipReason::Empty,
+                });
+                continue;
             }
-        })
+        }
+        sources.push((file_name, utf8_content(&path, bytes)?));
+    }
+
+    let files = sources
+        .into_iter()
         .map(|(file_name, content)| {
             let rss = escape(&content).to_string();
             let cname = escape(&content).to_string();
@@ -66,24 +165,125 @@ pub fn add(path: &Path) -> io::Result<Vec<FileData>> {
                 sitemap_news,
                 // tags,
                 txt,
+                json: String::new(),
+                alternates: Vec::new(),
+                output_path: String::new(),
             }
         })
         .collect::<Vec<FileData>>();
 
-    Ok(files)
+    Ok(LoadedContent { files, skipped })
+}
+
+/// Decodes the content of the source file at `path` as UTF-8.
+fn utf8_content(path: &Path, bytes: Vec<u8>) -> Result<String> {
+    String::from_utf8(bytes).map_err(|e| {
+        let offset = e.utf8_error().valid_up_to();
+        Error::content_processing_builder()
+            .message(format!(
+                "Source file {:?} is not valid UTF-8: invalid byte sequence at offset {}",
+                path, offset
+            ))
+            .source(e.utf8_error())
+            .build()
+    })
+}
+
+/// A file from one content directory replaced by a file with the same name
+/// from a later directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentOverride {
+    /// The name of the file, relative to its content directory.
+    pub name: String,
+    /// The content directory whose file was replaced.
+    pub overridden: PathBuf,
+    /// The content directory whose file is used.
+    pub by: PathBuf,
+}
+
+/// The files of several content directories merged into a single tree.
+#[derive(Debug, Clone, Default)]
+pub struct MergedContent {
+    /// The merged files, in the order they were first seen.
+    pub files: Vec<FileData>,
+    /// The files replaced by a later directory, in merge order.
+    pub overrides: Vec<ContentOverride>,
+    /// The files skipped by [`add`], from every directory.
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Reads the files of several content directories with [`add`] and merges
+/// them into a single logical tree.
+///
+/// When two directories contain a file with the same name, the later
+/// directory wins: its file takes the place of the earlier one and the
+/// replacement is recorded in [`MergedContent::overrides`].
+///
+/// # Arguments
+///
+/// * `paths` - The content directories, from lowest to highest priority.
+///
+/// # Returns
+///
+/// The merged files and overrides, or an error if any directory cannot
+/// be read or holds a file that is not valid UTF-8.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use staticdatagen::utilities::file::add_all;
+///
+/// let merged = add_all(&[Path::new("shared/docs"), Path::new("content")])?;
+/// for o in &merged.overrides {
+///     println!("{} from {:?} overrides {:?}", o.name, o.by, o.overridden);
+/// }
+/// # Ok::<(), staticdatagen::Error>(())
+/// ```
+pub fn add_all(paths: &[&Path]) -> Result<MergedContent> {
+    let mut merged = MergedContent::default();
+    let mut sources: Vec<&Path> = Vec::new();
+
+    for path in paths {
+        let loaded = add_with_options(path, &AddOptions::default())?;
+        merged.skipped.extend(loaded.skipped);
+        for file in loaded.files {
+            match merged.files.iter().position(|f| f.name == file.name)
+            {
+                Some(index) => {
+                    merged.overrides.push(ContentOverride {
+                        name: file.name.clone(),
+                        overridden: sources[index].to_path_buf(),
+                        by: path.to_path_buf(),
+                    });
+                    merged.files[index] = file;
+                    sources[index] = path;
+                }
+                None => {
+                    merged.files.push(file);
+                    sources.push(path);
+                }
+            }
+        }
+    }
+
+    Ok(merged)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::add;
+    use super::{
+        add, add_all, add_with_options, AddOptions, SkipReason,
+    };
+    use crate::{Error, Result};
     use std::fs::{self, File};
-    use std::io::{self, Write};
+    use std::io::Write;
     use std::path::Path;
     use tempfile::tempdir;
 
     /// Tests that `add` reads all valid files in the directory and excludes invalid files.
     #[test]
-    fn test_add_valid_files() -> io::Result<()> {
+    fn test_add_valid_files() -> Result<()> {
         let dir = tempdir()?;
         let file1_path = dir.path().join("test1.txt");
         let file2_path = dir.path().join("test2.txt");
@@ -107,7 +307,7 @@ fn test_add_valid_files() -> io::Result<()> {
 
     /// Tests that `add` handles an empty directory correctly.
     #[test]
-    fn test_add_empty_directory() -> io::Result<()> {
+    fn test_add_empty_directory() -> Result<()> {
         let dir = tempdir()?;
 
         // Run the `add` function on an empty directory
@@ -133,7 +333,7 @@ fn test_add_nonexistent_directory() {
 
     /// Tests that `add` correctly escapes special characters in file content.
     #[test]
-    fn test_add_escapes_special_characters() -> io::Result<()> {
+    fn test_add_escapes_special_characters() -> Result<()> {
         let dir = tempdir()?;
         let special_chars_file_path =
             dir.path().join("special_chars.txt");
@@ -160,7 +360,7 @@ fn test_add_escapes_special_characters() -> io::Result<()> {
 
     /// Tests that `add` correctly serializes file content to JSON.
     #[test]
-    fn test_add_serializes_to_json() -> io::Result<()> {
+    fn test_add_serializes_to_json() -> Result<()> {
         let dir = tempdir()?;
         let json_file_path = dir.path().join("manifest_file.txt");
 
@@ -183,7 +383,7 @@ fn test_add_serializes_to_json() -> io::Result<()> {
 
     /// Tests that `add` skips over non-file entries (e.g., subdirectories).
     #[test]
-    fn test_add_skips_directories() -> io::Result<()> {
+    fn test_add_skips_directories() -> Result<()> {
         let dir = tempdir()?;
         let subdir_path = dir.path().join("subdir");
         let file_path = dir.path().join("test_file.txt");
@@ -201,4 +401,111 @@ fn test_add_skips_directories() -> io::Result<()> {
 
         Ok(())
     }
+
+    /// Tests that `add` reports a file that is not valid UTF-8.
+    #[test]
+    fn test_add_invalid_utf8() -> Result<()> {
+        let dir = tempdir()?;
+        fs::write(dir.path().join("index.md"), "# Valid")?;
+        fs::write(dir.path().join("logo.png"), b"\x89PNG\r\n\xff")?;
+
+        let err = add(dir.path()).unwrap_err();
+
+        assert!(matches!(err, Error::ContentProcessing { .. }));
+        let message = err.to_string();
+        assert!(message.contains("logo.png"), "{}", message);
+        assert!(message.contains("offset 0"), "{}", message);
+
+        fs::write(dir.path().join("logo.png"), b"caf\xc3 au lait")?;
+        let message = add(dir.path()).unwrap_err().to_string();
+        assert!(message.contains("offset 3"), "{}", message);
+
+        Ok(())
+    }
+
+    /// Tests that `add_all` merges directories, letting later ones win.
+    #[test]
+    fn test_add_all_merges_with_overrides() -> Result<()> {
+        let shared = tempdir()?;
+        let site = tempdir()?;
+
+        fs::write(shared.path().join("index.md"), "shared index")?;
+        fs::write(shared.path().join("docs.md"), "shared docs")?;
+        fs::write(site.path().join("index.md"), "site index")?;
+        fs::write(site.path().join("about.md"), "site about")?;
+
+        let merged = add_all(&[shared.path(), site.path()])?;
+
+        assert_eq!(merged.files.len(), 3);
+        let index =
+            merged.files.iter().find(|f| f.name == "index.md").unwrap();
+        assert_eq!(index.content, "site index");
+
+        assert_eq!(merged.overrides.len(), 1);
+        assert_eq!(merged.overrides[0].name, "index.md");
+        assert_eq!(merged.overrides[0].overridden, shared.path());
+        assert_eq!(merged.overrides[0].by, site.path());
+
+        Ok(())
+    }
+
+    /// Tests that `add_all` fails if any directory is missing.
+    #[test]
+    fn test_add_all_missing_directory() -> Result<()> {
+        let dir = tempdir()?;
+        let missing = dir.path().join("missing");
+
+        assert!(add_all(&[dir.path(), &missing]).is_err());
+        assert!(add_all(&[]).map(|m| m.files.is_empty())?);
+
+        Ok(())
+    }
+
+    /// Tests that `add` skips hidden and zero-byte files and reports them.
+    #[test]
+    fn test_add_skips_hidden_and_empty_files() -> Result<()> {
+        let dir = tempdir()?;
+        fs::write(dir.path().join("index.md"), "# Home")?;
+        fs::write(dir.path().join(".post.md.swp"), "swap")?;
+        fs::write(dir.path().join("blank.md"), "")?;
+
+        let files = add(dir.path())?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "index.md");
+
+        let loaded =
+            add_with_options(dir.path(), &AddOptions::default())?;
+        let mut skipped: Vec<(String, SkipReason)> = loaded
+            .skipped
+            .iter()
+            .map(|s| {
+                (
+                    s.path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into(),
+                    s.reason,
+                )
+            })
+            .collect();
+        skipped.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            skipped,
+            [
+                (".post.md.swp".to_string(), SkipReason::Hidden),
+                ("blank.md".to_string(), SkipReason::Empty),
+            ]
+        );
+
+        let loaded = add_with_options(
+            dir.path(),
+            &AddOptions { keep_empty: true },
+        )?;
+        assert_eq!(loaded.files.len(), 2);
+        assert_eq!(loaded.skipped.len(), 1);
+        assert_eq!(add_all(&[dir.path()])?.skipped.len(), 2);
+
+        Ok(())
+    }
 }
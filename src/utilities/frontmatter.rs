@@ -0,0 +1,309 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Front matter splitting
+//!
+//! Pages open with YAML (`---`), TOML (`+++`) or JSON (`{`) front matter.
+//! [`split_frontmatter_and_body`] separates it from the Markdown body for
+//! every part of the build that reads one or the other.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use staticdatagen::utilities::frontmatter::split_frontmatter_and_body;
+//!
+//! let (frontmatter, body) =
+//!     split_frontmatter_and_body("+++\ntitle = \"Hi\"\n+++\nBody.");
+//! assert_eq!(frontmatter, "title = \"Hi\"");
+//! assert_eq!(body, "Body.");
+//! ```
+//!
+//! [`find_frontmatter`] locates the block in place instead, for passes
+//! that rewrite it without disturbing its fences or line endings.
+
+use std::ops::Range;
+
+/// Splits a Markdown content string into frontmatter and body parts.
+///
+/// The function uses the `---` separator to divide the content into two parts:
+/// the frontmatter (metadata) and the body (main content). Content opening
+/// with a `+++` (TOML) or `{` (JSON) line is split at the matching `+++` or
+/// `}` line instead, so no fence is left in the body.
+///
+/// # Parameters
+///
+/// * `content` - A reference to a string containing the Markdown content.
+///
+/// # Returns
+///
+/// A tuple containing two strings:
+/// - The first string represents the frontmatter part of the content.
+/// - The second string represents the body part of the content.
+///
+/// If the `---` separator is not found in the content, both strings will be empty.
+pub fn split_frontmatter_and_body(content: &str) -> (String, String) {
+    if let Some(split) = split_fenced_frontmatter(content) {
+        return split;
+    }
+
+    let mut lines = content.lines();
+    let mut frontmatter = String::new();
+    let mut body = String::new();
+    let mut in_frontmatter = false;
+
+    for line in &mut lines {
+        if line.trim() == "---" {
+            if in_frontmatter {
+                // Ending the frontmatter
+                break;
+            } else {
+                // Starting the frontmatter
+                in_frontmatter = true;
+                continue;
+            }
+        }
+
+        if in_frontmatter {
+            frontmatter.push_str(line);
+            frontmatter.push('\n');
+        } else {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+
+    // Append the rest of the lines to the body
+    for line in lines {
+        body.push_str(line);
+        body.push('\n');
+    }
+
+    (frontmatter.trim().to_string(), body.trim().to_string())
+}
+
+/// The opening and closing lines of TOML and JSON front matter.
+const FRONTMATTER_FENCES: [(&str, &str); 2] =
+    [("+++", "+++"), ("{", "}")];
+
+/// Splits content whose first line opens TOML or JSON front matter, or
+/// returns `None` for other content.
+///
+/// The closing line must match exactly, so the indented braces of nested
+/// JSON objects do not end the block.
+fn split_fenced_frontmatter(content: &str) -> Option<(String, String)> {
+    let mut lines = content.lines().map(str::trim_end);
+    let first = lines.next()?;
+    let (_, close) =
+        FRONTMATTER_FENCES.iter().find(|(open, _)| first == *open)?;
+
+    let lines: Vec<&str> = lines.collect();
+    let end = lines.iter().position(|line| line == close)?;
+    Some((
+        lines[..end].join("\n").trim().to_string(),
+        lines[end + 1..].join("\n").trim().to_string(),
+    ))
+}
+
+/// The syntax of a front matter block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterFormat {
+    /// YAML between `---` lines.
+    Yaml,
+    /// TOML between `+++` lines.
+    Toml,
+    /// A JSON object between `{` and `}` lines.
+    Json,
+}
+
+impl FrontmatterFormat {
+    /// Returns `key` set to the string `value` in this syntax, without a
+    /// separator or line ending.
+    fn entry(self, key: &str, value: &str) -> String {
+        match self {
+            FrontmatterFormat::Yaml => {
+                format!("{}: \"{}\"", key, value)
+            }
+            FrontmatterFormat::Toml => {
+                format!("{} = \"{}\"", key, value)
+            }
+            FrontmatterFormat::Json => {
+                format!("  \"{}\": \"{}\"", key, value)
+            }
+        }
+    }
+}
+
+/// The location of the front matter block opening a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontmatterSpan {
+    /// The block's syntax.
+    pub format: FrontmatterFormat,
+    /// The byte range of the lines between the opening and closing
+    /// fences.
+    pub inner: Range<usize>,
+    /// The line ending of the opening fence, `"\n"` or `"\r\n"`.
+    pub newline: &'static str,
+}
+
+/// Locates the YAML, TOML or JSON front matter block opening `content`.
+///
+/// The first line must be a fence (`---`, `+++` or `{`) and a later line
+/// the matching closing fence; otherwise `None` is returned. Both LF and
+/// CRLF line endings are recognised.
+///
+/// # Example
+///
+/// ```rust
+/// use staticdatagen::utilities::frontmatter::{find_frontmatter, FrontmatterFormat};
+///
+/// let content = "+++\r\ntitle = \"Hi\"\r\n+++\r\nBody.";
+/// let span = find_frontmatter(content).unwrap();
+/// assert_eq!(span.format, FrontmatterFormat::Toml);
+/// assert_eq!(&content[span.inner], "title = \"Hi\"\r\n");
+/// assert_eq!(span.newline, "\r\n");
+/// ```
+pub fn find_frontmatter(content: &str) -> Option<FrontmatterSpan> {
+    let mut lines = content.split_inclusive('\n');
+    let first = lines.next()?;
+    let newline = if first.ends_with("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let (format, close) = match first.trim_end() {
+        "---" => (FrontmatterFormat::Yaml, "---"),
+        open => {
+            let (_, close) = FRONTMATTER_FENCES
+                .iter()
+                .find(|(fence, _)| open == *fence)?;
+            let format = if *close == "+++" {
+                FrontmatterFormat::Toml
+            } else {
+                FrontmatterFormat::Json
+            };
+            (format, *close)
+        }
+    };
+    if !first.ends_with('\n') {
+        return None;
+    }
+
+    let start = first.len();
+    let mut offset = start;
+    for line in lines {
+        if line.trim_end() == close {
+            return Some(FrontmatterSpan {
+                format,
+                inner: start..offset,
+                newline,
+            });
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Adds string `entries` at the top of the front matter of `content`,
+/// written in the block's own syntax and line ending.
+///
+/// Content without front matter gets a new YAML block holding them.
+///
+/// # Example
+///
+/// ```rust
+/// use staticdatagen::utilities::frontmatter::insert_entries;
+///
+/// assert_eq!(
+///     insert_entries("+++\ntitle = \"Hi\"\n+++\nBody.", &[("date", "2024-01-15")]),
+///     "+++\ndate = \"2024-01-15\"\ntitle = \"Hi\"\n+++\nBody."
+/// );
+/// assert_eq!(
+///     insert_entries("Body.", &[("date", "2024-01-15")]),
+///     "---\ndate: \"2024-01-15\"\n---\nBody."
+/// );
+/// ```
+pub fn insert_entries(
+    content: &str,
+    entries: &[(&str, &str)],
+) -> String {
+    if entries.is_empty() {
+        return content.to_string();
+    }
+    let span = match find_frontmatter(content) {
+        Some(span) => span,
+        None => {
+            let lines: String = entries
+                .iter()
+                .map(|(key, value)| {
+                    FrontmatterFormat::Yaml.entry(key, value) + "\n"
+                })
+                .collect();
+            return format!("---\n{}---\n{}", lines, content);
+        }
+    };
+
+    // JSON members are comma-separated, so every inserted member but the
+    // last of an empty object needs a trailing comma.
+    let existing = !content[span.inner.clone()].trim().is_empty();
+    let lines: String = entries
+        .iter()
+        .enumerate()
+        .map(|(i, (key, value))| {
+            let comma = span.format == FrontmatterFormat::Json
+                && (existing || i + 1 < entries.len());
+            format!(
+                "{}{}{}",
+                span.format.entry(key, value),
+                if comma { "," } else { "" },
+                span.newline
+            )
+        })
+        .collect();
+
+    format!(
+        "{}{}{}",
+        &content[..span.inner.start],
+        lines,
+        &content[span.inner.start..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_frontmatter() {
+        let content = "---\r\ntitle: Hi\r\n---\r\nBody.";
+        let span = find_frontmatter(content).unwrap();
+        assert_eq!(span.format, FrontmatterFormat::Yaml);
+        assert_eq!(&content[span.inner], "title: Hi\r\n");
+
+        let content = "{\n  \"a\": {\n  }\n}\nBody.";
+        let span = find_frontmatter(content).unwrap();
+        assert_eq!(span.format, FrontmatterFormat::Json);
+        assert_eq!(&content[span.inner], "  \"a\": {\n  }\n");
+
+        assert!(find_frontmatter("Body.").is_none());
+        assert!(find_frontmatter("+++\nBody.").is_none());
+        assert!(find_frontmatter("---").is_none());
+    }
+
+    #[test]
+    fn test_insert_entries() {
+        let entries = [("a", "1"), ("b", "2")];
+        assert_eq!(
+            insert_entries("---\r\nt: x\r\n---\r\nBody.", &entries),
+            "---\r\na: \"1\"\r\nb: \"2\"\r\nt: x\r\n---\r\nBody."
+        );
+        assert_eq!(
+            insert_entries("{\n  \"t\": \"x\"\n}\nBody.", &entries),
+            "{\n  \"a\": \"1\",\n  \"b\": \"2\",\n  \"t\": \"x\"\n}\nBody."
+        );
+        assert_eq!(
+            insert_entries("{\n}\nBody.", &entries),
+            "{\n  \"a\": \"1\",\n  \"b\": \"2\"\n}\nBody."
+        );
+        assert_eq!(insert_entries("Body.", &[]), "Body.");
+    }
+}
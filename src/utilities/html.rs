@@ -0,0 +1,35 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! HTML escaping for generated markup
+//!
+//! Navigation menus, tag listings and section indexes interpolate page
+//! titles and URLs into HTML text and double-quoted attributes, and all
+//! escape them with [`html_escape`].
+//!
+//! # Examples
+//!
+//! ```
+//! use staticdatagen::utilities::html::html_escape;
+//!
+//! assert_eq!(
+//!     html_escape(r#"<a href="/?a=1&b=2">"#),
+//!     "&lt;a href=&quot;/?a=1&amp;b=2&quot;&gt;"
+//! );
+//! ```
+
+/// Escapes `&`, `<`, `>` and `"` for HTML text and double-quoted
+/// attribute values.
+pub fn html_escape(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
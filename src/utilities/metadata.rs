@@ -0,0 +1,262 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Typed front matter access
+//!
+//! Front matter is parsed into a `HashMap<String, String>`, so every value
+//! arrives as text. The helpers in this module read a key and parse it into
+//! the type the caller expects, keeping three cases apart:
+//!
+//! - the key is absent (`Ok(None)`),
+//! - the key is present but empty ([`MetadataError::Empty`]),
+//! - the key is present but cannot be parsed ([`MetadataError::Invalid`]).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use staticdatagen::utilities::metadata::{meta_bool, meta_date, meta_str};
+//!
+//! let mut metadata = HashMap::new();
+//! metadata.insert("title".to_string(), "Hello".to_string());
+//! metadata.insert("draft".to_string(), "yes".to_string());
+//! metadata.insert("date".to_string(), "2024-01-15".to_string());
+//!
+//! assert_eq!(meta_str(&metadata, "title"), Some("Hello"));
+//! assert_eq!(meta_bool(&metadata, "draft").unwrap(), Some(true));
+//! assert_eq!(meta_date(&metadata, "date").unwrap().unwrap().year(), 2024);
+//! assert!(meta_date(&metadata, "updated").unwrap().is_none());
+//! ```
+
+use crate::models::data::validation::validate_url;
+use crate::utilities::datetime::parse_flexible;
+use crate::utilities::frontmatter::split_frontmatter_and_body;
+use std::collections::HashMap;
+use thiserror::Error;
+use time::OffsetDateTime;
+use url::Url;
+
+/// Errors returned when a metadata value is present but unusable.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MetadataError {
+    /// The key is present but its value is empty.
+    #[error("Metadata key '{0}' is empty")]
+    Empty(String),
+
+    /// The key is present but its value cannot be parsed.
+    #[error("Metadata key '{key}' has invalid value '{value}': expected {expected}")]
+    Invalid {
+        /// The metadata key.
+        key: String,
+        /// The value found.
+        value: String,
+        /// A description of the expected value.
+        expected: &'static str,
+    },
+}
+
+impl MetadataError {
+    /// Returns `true` if the value was present but empty.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, MetadataError::Empty(_))
+    }
+}
+
+/// Returns the value of `key`, or `None` if it is absent.
+///
+/// Unlike `macro_metadata_option!`, an empty value is returned as
+/// `Some("")` rather than being indistinguishable from a missing key.
+pub fn meta_str<'a>(
+    metadata: &'a HashMap<String, String>,
+    key: &str,
+) -> Option<&'a str> {
+    metadata.get(key).map(String::as_str)
+}
+
+/// Parses `key` as a date.
+///
+/// Accepts RFC 3339, RFC 2822, `YYYY-MM-DD`, `July 12, 2023` and
+/// `12 July 2023`; calendar dates are taken as midnight UTC.
+pub fn meta_date(
+    metadata: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<OffsetDateTime>, MetadataError> {
+    parse_with(
+        metadata,
+        key,
+        "an RFC 3339, RFC 2822 or calendar date",
+        parse_date,
+    )
+}
+
+/// Parses `key` as a boolean.
+///
+/// Accepts `true`/`false`, `yes`/`no`, `on`/`off` and `1`/`0`, ignoring
+/// case.
+pub fn meta_bool(
+    metadata: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<bool>, MetadataError> {
+    parse_with(metadata, key, "a boolean", |value| {
+        match value.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Some(true),
+            "false" | "no" | "off" | "0" => Some(false),
+            _ => None,
+        }
+    })
+}
+
+/// Parses `key` as an absolute URL.
+pub fn meta_url(
+    metadata: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<Url>, MetadataError> {
+    parse_with(metadata, key, "an absolute URL", |value| {
+        validate_url(value).ok()?;
+        Url::parse(value).ok()
+    })
+}
+
+/// Parses a date in any of the layouts accepted by [`meta_date`].
+///
+/// This is [`parse_flexible`] without the error detail.
+pub fn parse_date(date: &str) -> Option<OffsetDateTime> {
+    parse_flexible(date).ok()
+}
+
+/// Restores unquoted front-matter values (booleans, numbers, inline
+/// lists) for `keys` whose metadata value was emptied by the metadata
+/// parser, reading the raw value from the front matter.
+pub(crate) fn with_raw_scalars(
+    content: &str,
+    metadata: &HashMap<String, String>,
+    keys: &[&str],
+) -> HashMap<String, String> {
+    let mut metadata = metadata.clone();
+    let (frontmatter, _) = split_frontmatter_and_body(content);
+
+    for key in keys {
+        if metadata.get(*key).map_or(true, |value| !value.is_empty()) {
+            continue;
+        }
+        let raw = frontmatter
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim() == *key)
+            .map(|(_, value)| value.trim().to_string())
+            .unwrap_or_default();
+        let _ = metadata.insert(key.to_string(), raw);
+    }
+
+    metadata
+}
+
+/// Reads `key` and parses its trimmed value with `parse`.
+fn parse_with<T>(
+    metadata: &HashMap<String, String>,
+    key: &str,
+    expected: &'static str,
+    parse: impl FnOnce(&str) -> Option<T>,
+) -> Result<Option<T>, MetadataError> {
+    let value = match meta_str(metadata, key) {
+        Some(value) => value.trim(),
+        None => return Ok(None),
+    };
+    if value.is_empty() {
+        return Err(MetadataError::Empty(key.to_string()));
+    }
+
+    parse(value)
+        .map(Some)
+        .ok_or_else(|| MetadataError::Invalid {
+            key: key.to_string(),
+            value: value.to_string(),
+            expected,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::metadata;
+
+    #[test]
+    fn test_meta_str_distinguishes_absent_and_empty() {
+        let metadata = metadata(&[("title", "")]);
+
+        assert_eq!(meta_str(&metadata, "title"), Some(""));
+        assert_eq!(meta_str(&metadata, "author"), None);
+    }
+
+    #[test]
+    fn test_meta_date_layouts() {
+        let metadata = metadata(&[
+            ("rfc3339", "2024-01-15T10:00:00Z"),
+            ("rfc2822", "Mon, 15 Jan 2024 10:00:00 GMT"),
+            ("iso", "2024-01-15"),
+            ("long", "January 15, 2024"),
+            ("day_first", "15 January 2024"),
+        ]);
+
+        for key in ["rfc3339", "rfc2822", "iso", "long", "day_first"] {
+            let date = meta_date(&metadata, key).unwrap().unwrap();
+            assert_eq!(
+                date.date().to_string(),
+                "2024-01-15",
+                "{}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_meta_date_errors() {
+        let metadata =
+            metadata(&[("empty", "  "), ("bad", "2024-13-45")]);
+
+        assert!(meta_date(&metadata, "missing").unwrap().is_none());
+        assert_eq!(
+            meta_date(&metadata, "empty"),
+            Err(MetadataError::Empty("empty".to_string()))
+        );
+        assert!(matches!(
+            meta_date(&metadata, "bad"),
+            Err(MetadataError::Invalid { ref value, .. })
+                if value == "2024-13-45"
+        ));
+    }
+
+    #[test]
+    fn test_meta_bool() {
+        let metadata = metadata(&[
+            ("a", "TRUE"),
+            ("b", "off"),
+            ("c", "0"),
+            ("d", "maybe"),
+        ]);
+
+        assert_eq!(meta_bool(&metadata, "a"), Ok(Some(true)));
+        assert_eq!(meta_bool(&metadata, "b"), Ok(Some(false)));
+        assert_eq!(meta_bool(&metadata, "c"), Ok(Some(false)));
+        assert!(meta_bool(&metadata, "d").is_err());
+        assert_eq!(meta_bool(&metadata, "e"), Ok(None));
+    }
+
+    #[test]
+    fn test_meta_url() {
+        let metadata = metadata(&[
+            ("good", "https://example.com/feed.xml"),
+            ("relative", "/feed.xml"),
+            ("unsafe", "https://example.com/\"onload"),
+            ("empty", ""),
+        ]);
+
+        assert_eq!(
+            meta_url(&metadata, "good").unwrap().unwrap().as_str(),
+            "https://example.com/feed.xml"
+        );
+        assert!(meta_url(&metadata, "relative").is_err());
+        assert!(meta_url(&metadata, "unsafe").is_err());
+        assert!(meta_url(&metadata, "empty").unwrap_err().is_empty());
+    }
+}
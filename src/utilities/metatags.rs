@@ -0,0 +1,248 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Meta tag bundles
+//!
+//! `metadata_gen` hands over each group of meta tags (`apple`, `ms`, `og`,
+//! `primary`, `twitter`) as pre-rendered HTML. [`parse_meta_tags`] reads
+//! such a bundle back into `(attribute, name, content)` triples so single
+//! tags can be inspected, deduplicated or replaced, and
+//! [`render_meta_tags`] writes them out again.
+//!
+//! # Examples
+//!
+//! ```
+//! use staticdatagen::utilities::metatags::{
+//!     parse_meta_tags, render_meta_tags,
+//! };
+//!
+//! let bundle = r#"<meta name="og:title" content="Say &quot;hi&quot;">
+//! <meta property="og:type" content="article">"#;
+//!
+//! let tags = parse_meta_tags(bundle);
+//! assert_eq!(
+//!     tags[0],
+//!     ("name".to_string(), "og:title".to_string(), "Say \"hi\"".to_string())
+//! );
+//! assert_eq!(tags[1].0, "property");
+//! assert_eq!(render_meta_tags(&tags), bundle);
+//! ```
+
+/// The attributes that name a meta tag, in order of preference.
+const NAME_ATTRIBUTES: [&str; 4] =
+    ["name", "property", "http-equiv", "itemprop"];
+
+/// Parses the `<meta>` tags of `bundle` into `(attribute, name, content)`
+/// triples, in document order.
+///
+/// `attribute` is the attribute naming the tag (`name`, `property`,
+/// `http-equiv` or `itemprop`), lowercased. Entity references in values
+/// are decoded, a missing `content` reads as empty, and tags with no
+/// naming attribute (such as `<meta charset>`) are skipped. Text outside
+/// the tags is ignored.
+pub fn parse_meta_tags(bundle: &str) -> Vec<(String, String, String)> {
+    let lower = bundle.to_ascii_lowercase();
+    let mut tags = Vec::new();
+    let mut offset = 0;
+
+    while let Some(start) = lower[offset..].find("<meta") {
+        let attrs_start = offset + start + "<meta".len();
+        let (attributes, end) = parse_attributes(bundle, attrs_start);
+        offset = end;
+
+        // `<metadata>` and the like are other elements.
+        if bundle[attrs_start..]
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            continue;
+        }
+
+        let value = |key: &str| {
+            attributes
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.clone())
+        };
+        if let Some((attribute, name)) = NAME_ATTRIBUTES
+            .iter()
+            .find_map(|key| value(key).map(|name| (*key, name)))
+        {
+            tags.push((
+                attribute.to_string(),
+                name,
+                value("content").unwrap_or_default(),
+            ));
+        }
+    }
+
+    tags
+}
+
+/// Renders `(attribute, name, content)` triples as `<meta>` tags, one
+/// per line, escaping `&` and `"` in values.
+pub fn render_meta_tags(tags: &[(String, String, String)]) -> String {
+    tags.iter()
+        .map(|(attribute, name, content)| {
+            format!(
+                r#"<meta {}="{}" content="{}">"#,
+                attribute,
+                escape_attribute(name),
+                escape_attribute(content)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reads the attributes of a tag starting at `start`, up to its closing
+/// `>`. Returns them with lowercased names and decoded values, and the
+/// offset just past the tag.
+fn parse_attributes(
+    input: &str,
+    start: usize,
+) -> (Vec<(String, String)>, usize) {
+    let bytes = input.as_bytes();
+    let mut attributes = Vec::new();
+    let mut i = start;
+
+    loop {
+        while i < bytes.len()
+            && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/')
+        {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return (attributes, i);
+        }
+        if bytes[i] == b'>' {
+            return (attributes, i + 1);
+        }
+
+        let name_start = i;
+        while i < bytes.len()
+            && !bytes[i].is_ascii_whitespace()
+            && !matches!(bytes[i], b'=' | b'>' | b'/')
+        {
+            i += 1;
+        }
+        let name = input[name_start..i].to_ascii_lowercase();
+
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let mut value = String::new();
+        if i < bytes.len() && bytes[i] == b'=' {
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            let value_start;
+            let value_end;
+            match bytes.get(i) {
+                Some(&quote) if quote == b'"' || quote == b'\'' => {
+                    value_start = i + 1;
+                    value_end = input[value_start..]
+                        .find(quote as char)
+                        .map_or(input.len(), |end| value_start + end);
+                    i = (value_end + 1).min(input.len());
+                }
+                _ => {
+                    value_start = i;
+                    while i < bytes.len()
+                        && !bytes[i].is_ascii_whitespace()
+                        && bytes[i] != b'>'
+                    {
+                        i += 1;
+                    }
+                    value_end = i;
+                }
+            }
+            value = decode_entities(&input[value_start..value_end]);
+        }
+
+        if !name.is_empty() {
+            attributes.push((name, value));
+        }
+    }
+}
+
+/// Decodes the entity references `metadata_gen` and common HTML
+/// escaping produce.
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#34;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Escapes `&` and `"` for a double-quoted attribute value.
+fn escape_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metadata_gen::generate_metatags;
+    use std::collections::HashMap;
+
+    fn triple(
+        attribute: &str,
+        name: &str,
+        content: &str,
+    ) -> (String, String, String) {
+        (attribute.to_string(), name.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn test_parse_meta_tags() {
+        let bundle = "<!-- og -->\n<META Property='og:url' content=https://example.com/>\n<meta charset=\"utf-8\">\n<metadata name=\"x\">\n<meta name=\"robots\">\n<meta http-equiv=\"refresh\" content=\"5; url=/a?b=1&amp;c=2\" />";
+
+        assert_eq!(
+            parse_meta_tags(bundle),
+            [
+                triple("property", "og:url", "https://example.com/"),
+                triple("name", "robots", ""),
+                triple("http-equiv", "refresh", "5; url=/a?b=1&c=2"),
+            ]
+        );
+        assert!(parse_meta_tags("").is_empty());
+        assert_eq!(parse_meta_tags("<meta name=\"unclosed").len(), 1);
+    }
+
+    #[test]
+    fn test_round_trip_generated_bundle() {
+        let mut metadata = HashMap::new();
+        for (key, value) in [
+            ("og:title", "A \"quoted\" title"),
+            ("og:description", "Tips <b>&</b> tricks"),
+            ("twitter:card", "summary"),
+        ] {
+            let _ = metadata.insert(key.to_string(), value.to_string());
+        }
+        let groups = generate_metatags(&metadata);
+
+        let og = parse_meta_tags(&groups.og);
+        assert!(og.contains(&triple(
+            "name",
+            "og:title",
+            "A \"quoted\" title"
+        )));
+        assert!(og.contains(&triple(
+            "name",
+            "og:description",
+            "Tips <b>&</b> tricks"
+        )));
+        assert_eq!(parse_meta_tags(&render_meta_tags(&og)), og);
+        assert_eq!(
+            parse_meta_tags(&groups.twitter),
+            [triple("name", "twitter:card", "summary")]
+        );
+    }
+}
@@ -4,6 +4,9 @@
 /// The `backup` module contains functions for creating backups of files.
 pub mod backup;
 
+/// The `datetime` module parses and formats dates for every output.
+pub mod datetime;
+
 /// The `directory` module contains functions for creating directories.
 pub mod directory;
 
@@ -13,9 +16,32 @@
 /// The `file` module handles file reading and writing operations.
 pub mod file;
 
+/// The `frontmatter` module splits front matter from page bodies.
+pub mod frontmatter;
+
+/// The `html` module escapes text for generated HTML.
+pub mod html;
+/// The `metadata` module provides typed access to front matter values.
+pub mod metadata;
+
+/// The `metatags` module parses pre-rendered meta tag bundles.
+pub mod metatags;
+
+/// The `permalink` module maps permalinks to output paths.
+pub mod permalink;
+
+/// The `schema` module checks front matter against per-type schemas.
+pub mod schema;
+
 /// The `security` module contains functions for security-related operations.
 pub mod security;
 
+/// The `server` module contains the development server.
+pub mod server;
+
+/// The `url` module resolves permalinks to absolute URLs.
+pub mod url;
+
 /// The `write` module contains functions for writing files.
 pub mod write;
 
@@ -0,0 +1,542 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Permalink resolution utilities
+//!
+//! This module maps site permalinks (such as `/about` or `/about/`) to the
+//! output file that serves them. Hosts differ in how they treat a trailing
+//! slash, so the mapping is driven by an explicit [`TrailingSlash`] policy
+//! that the writer, navigation and sitemap generation share through
+//! [`UrlOptions`], [`page_output_path`] and [`resolve_url`].
+//! It also recognises the Jekyll-style `YYYY-MM-DD-` date prefix in
+//! content file names.
+
+use std::path::{Component, Path, PathBuf};
+use time::{Date, Month};
+
+/// The file name used for directory-style permalinks.
+const INDEX_FILE: &str = "index.html";
+
+/// The extension appended to file-style permalinks.
+const HTML_EXTENSION: &str = "html";
+
+/// Controls how a permalink is mapped to an output file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrailingSlash {
+    /// Every permalink is served from a directory index
+    /// (`/about` → `about/index.html`).
+    Always,
+    /// Every permalink is served from a flat HTML file
+    /// (`/about/` → `about.html`).
+    Never,
+    /// The permalink is mapped as written: a trailing slash produces a
+    /// directory index, otherwise a flat HTML file is used.
+    Preserve,
+}
+
+impl Default for TrailingSlash {
+    /// Defaults to [`TrailingSlash::Always`], matching the
+    /// `/path/index.html` layout written by the compiler.
+    fn default() -> Self {
+        TrailingSlash::Always
+    }
+}
+
+/// Resolves a permalink to the relative output path that serves it.
+///
+/// Leading slashes, empty segments, `.` and `..` components are discarded,
+/// so the returned path is always relative and stays inside the output
+/// directory. The site root (`""` or `"/"`) always resolves to
+/// `index.html`, and permalinks that already name an `.html` file are
+/// returned unchanged regardless of the policy.
+///
+/// # Arguments
+///
+/// * `permalink` - The permalink to resolve (e.g. `/blog/post/`).
+/// * `policy` - The [`TrailingSlash`] policy to apply.
+///
+/// # Returns
+///
+/// A relative `PathBuf` pointing to the output file.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use staticdatagen::utilities::permalink::{resolve_output_path, TrailingSlash};
+///
+/// assert_eq!(
+///     resolve_output_path("/about", TrailingSlash::Always),
+///     PathBuf::from("about/index.html")
+/// );
+/// assert_eq!(
+///     resolve_output_path("/about/", TrailingSlash::Never),
+///     PathBuf::from("about.html")
+/// );
+/// assert_eq!(
+///     resolve_output_path("/about", TrailingSlash::Preserve),
+///     PathBuf::from("about.html")
+/// );
+/// ```
+pub fn resolve_output_path(
+    permalink: &str,
+    policy: TrailingSlash,
+) -> PathBuf {
+    output_path(permalink, policy, INDEX_FILE)
+}
+
+/// Resolves `permalink` like [`resolve_output_path`], serving
+/// directory-style permalinks from `index_file`, which, like an `.html`
+/// file, is kept when named.
+fn output_path(
+    permalink: &str,
+    policy: TrailingSlash,
+    index_file: &str,
+) -> PathBuf {
+    let trimmed = permalink.trim();
+    let has_trailing_slash = trimmed.ends_with('/');
+
+    let mut path: PathBuf = Path::new(trimmed)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(segment) => Some(segment),
+            _ => None,
+        })
+        .collect();
+
+    if path.as_os_str().is_empty() {
+        return PathBuf::from(index_file);
+    }
+
+    let is_html_file = path
+        .extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case(HTML_EXTENSION))
+        || path.file_name().map_or(false, |name| name == index_file);
+    if is_html_file && !has_trailing_slash {
+        return path;
+    }
+
+    let use_index = match policy {
+        TrailingSlash::Always => true,
+        TrailingSlash::Never => false,
+        TrailingSlash::Preserve => has_trailing_slash,
+    };
+
+    if use_index {
+        path.push(index_file);
+    } else {
+        let file_name = format!(
+            "{}.{}",
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            HTML_EXTENSION
+        );
+        path.set_file_name(file_name);
+    }
+
+    path
+}
+
+/// Resolves a permalink to the site-relative URL of its output file.
+///
+/// This is a convenience wrapper around [`resolve_output_path`] that joins
+/// the path components with `/` and prefixes a leading slash, making the
+/// result suitable for `href` attributes and sitemap locations.
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::utilities::permalink::{resolve_output_url, TrailingSlash};
+///
+/// assert_eq!(
+///     resolve_output_url("blog/post", TrailingSlash::Always),
+///     "/blog/post/index.html"
+/// );
+/// ```
+pub fn resolve_output_url(
+    permalink: &str,
+    policy: TrailingSlash,
+) -> String {
+    path_to_url(&resolve_output_path(permalink, policy))
+}
+
+/// Controls how page URLs are written, so navigation links and sitemap
+/// locations always agree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UrlOptions {
+    /// The [`TrailingSlash`] policy mapping pages to output files.
+    pub policy: TrailingSlash,
+    /// Whether URLs served from a directory index end at the directory
+    /// (`/about/`) rather than naming the index file
+    /// (`/about/index.html`).
+    pub pretty_urls: bool,
+    /// The file name serving directory-style URLs.
+    pub index_file: String,
+}
+
+impl Default for UrlOptions {
+    fn default() -> Self {
+        Self {
+            policy: TrailingSlash::default(),
+            pretty_urls: false,
+            index_file: INDEX_FILE.to_string(),
+        }
+    }
+}
+
+/// Resolves a permalink path to the site-relative URL of the file that
+/// serves it.
+///
+/// `path` is resolved with the [`TrailingSlash`] policy and index file of
+/// `options`, exactly like the output path the compiler writes (e.g.
+/// `blog/post/` or, under [`TrailingSlash::Preserve`], `blog/post` for a
+/// flat page), or names a written `.html` file, which is kept. With
+/// `pretty_urls`, a trailing index file name is dropped. Navigation and
+/// sitemap generation both use this, so their URLs cannot drift.
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::utilities::permalink::{resolve_url, UrlOptions};
+///
+/// let options = UrlOptions::default();
+/// assert_eq!(resolve_url("about/", &options), "/about/index.html");
+///
+/// let pretty = UrlOptions { pretty_urls: true, ..UrlOptions::default() };
+/// assert_eq!(resolve_url("about/", &pretty), "/about/");
+/// assert_eq!(resolve_url("about/index.html", &pretty), "/about/");
+/// assert_eq!(resolve_url("", &pretty), "/");
+/// ```
+pub fn resolve_url(path: &str, options: &UrlOptions) -> String {
+    let path = output_path(path, options.policy, &options.index_file);
+    let url = path_to_url(&path);
+    if !options.pretty_urls
+        || path.file_name().map_or(true, |name| {
+            name.to_string_lossy() != options.index_file.as_str()
+        })
+    {
+        return url;
+    }
+    match url.strip_suffix(options.index_file.as_str()) {
+        Some(directory) => directory.to_string(),
+        None => url,
+    }
+}
+
+/// Returns the permalink path of the page whose path without extension
+/// is `file_stem` (e.g. `blog/post`, or `""` for the home page).
+///
+/// Pages are directory-style (`blog/post/`) unless their front matter
+/// `permalink` is given without a trailing slash, which only
+/// [`TrailingSlash::Preserve`] then keeps as a flat page.
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::utilities::permalink::page_path;
+///
+/// assert_eq!(page_path("about", None), "about/");
+/// assert_eq!(page_path("about", Some("https://example.com/about/")), "about/");
+/// assert_eq!(page_path("about", Some("https://example.com/about")), "about");
+/// assert_eq!(page_path("", Some("https://example.com")), "");
+/// ```
+pub fn page_path(file_stem: &str, permalink: Option<&str>) -> String {
+    let file_stem = file_stem.trim_matches('/');
+    let flat = permalink.map_or(false, |permalink| {
+        !permalink.trim().is_empty() && !permalink.trim().ends_with('/')
+    });
+    if file_stem.is_empty() || flat {
+        file_stem.to_string()
+    } else {
+        format!("{}/", file_stem)
+    }
+}
+
+/// Resolves the output path of the page whose path without extension is
+/// `file_stem`, given its front matter `permalink` (see [`page_path`]).
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use staticdatagen::utilities::permalink::{page_output_path, TrailingSlash, UrlOptions};
+///
+/// let never = UrlOptions { policy: TrailingSlash::Never, ..UrlOptions::default() };
+/// assert_eq!(page_output_path("about", None, &never), PathBuf::from("about.html"));
+/// assert_eq!(page_output_path("", None, &never), PathBuf::from("index.html"));
+/// ```
+pub fn page_output_path(
+    file_stem: &str,
+    permalink: Option<&str>,
+    options: &UrlOptions,
+) -> PathBuf {
+    output_path(
+        &page_path(file_stem, permalink),
+        options.policy,
+        &options.index_file,
+    )
+}
+
+/// Joins the components of a relative output path into a URL path with
+/// a leading slash.
+fn path_to_url(path: &Path) -> String {
+    let segments: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Splits a leading `YYYY-MM-DD-` date from a file name, as in
+/// `2024-01-15-my-post.md`.
+///
+/// Returns the date and the remaining name, or `None` if the name has no
+/// valid date prefix or nothing follows it.
+///
+/// # Examples
+///
+/// ```
+/// use staticdatagen::utilities::permalink::split_date_prefix;
+///
+/// let (date, name) = split_date_prefix("2024-01-15-my-post.md").unwrap();
+/// assert_eq!(date.to_string(), "2024-01-15");
+/// assert_eq!(name, "my-post.md");
+///
+/// assert!(split_date_prefix("my-post.md").is_none());
+/// ```
+pub fn split_date_prefix(name: &str) -> Option<(Date, &str)> {
+    let prefix = name.get(..11)?;
+    let rest = &name[11..];
+    let bytes = prefix.as_bytes();
+    let well_formed = bytes.iter().enumerate().all(|(i, b)| match i {
+        4 | 7 | 10 => *b == b'-',
+        _ => b.is_ascii_digit(),
+    });
+    if rest.is_empty() || !well_formed {
+        return None;
+    }
+
+    let month =
+        Month::try_from(prefix[5..7].parse::<u8>().ok()?).ok()?;
+    let date = Date::from_calendar_date(
+        prefix[..4].parse().ok()?,
+        month,
+        prefix[8..10].parse().ok()?,
+    )
+    .ok()?;
+    Some((date, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_always_policy() {
+        for permalink in ["/about", "/about/", "about", "about/"] {
+            assert_eq!(
+                resolve_output_path(permalink, TrailingSlash::Always),
+                PathBuf::from("about/index.html"),
+                "Unexpected path for '{}'",
+                permalink
+            );
+        }
+    }
+
+    #[test]
+    fn test_never_policy() {
+        for permalink in ["/about", "/about/", "about", "about/"] {
+            assert_eq!(
+                resolve_output_path(permalink, TrailingSlash::Never),
+                PathBuf::from("about.html"),
+                "Unexpected path for '{}'",
+                permalink
+            );
+        }
+    }
+
+    #[test]
+    fn test_preserve_policy() {
+        assert_eq!(
+            resolve_output_path("/about/", TrailingSlash::Preserve),
+            PathBuf::from("about/index.html")
+        );
+        assert_eq!(
+            resolve_output_path("/about", TrailingSlash::Preserve),
+            PathBuf::from("about.html")
+        );
+    }
+
+    #[test]
+    fn test_nested_permalinks() {
+        assert_eq!(
+            resolve_output_path(
+                "/blog/2025/post",
+                TrailingSlash::Always
+            ),
+            PathBuf::from("blog/2025/post/index.html")
+        );
+        assert_eq!(
+            resolve_output_path(
+                "/blog/2025/post/",
+                TrailingSlash::Never
+            ),
+            PathBuf::from("blog/2025/post.html")
+        );
+    }
+
+    #[test]
+    fn test_site_root() {
+        for policy in [
+            TrailingSlash::Always,
+            TrailingSlash::Never,
+            TrailingSlash::Preserve,
+        ] {
+            assert_eq!(
+                resolve_output_path("/", policy),
+                PathBuf::from("index.html")
+            );
+            assert_eq!(
+                resolve_output_path("", policy),
+                PathBuf::from("index.html")
+            );
+        }
+    }
+
+    #[test]
+    fn test_existing_html_file_is_kept() {
+        for policy in [
+            TrailingSlash::Always,
+            TrailingSlash::Never,
+            TrailingSlash::Preserve,
+        ] {
+            assert_eq!(
+                resolve_output_path("/about/index.html", policy),
+                PathBuf::from("about/index.html")
+            );
+            assert_eq!(
+                resolve_output_path("/contact.html", policy),
+                PathBuf::from("contact.html")
+            );
+        }
+    }
+
+    #[test]
+    fn test_traversal_components_are_dropped() {
+        assert_eq!(
+            resolve_output_path(
+                "/../../etc/./passwd",
+                TrailingSlash::Never
+            ),
+            PathBuf::from("etc/passwd.html")
+        );
+        assert_eq!(
+            resolve_output_path(
+                "//blog//post//",
+                TrailingSlash::Always
+            ),
+            PathBuf::from("blog/post/index.html")
+        );
+    }
+
+    #[test]
+    fn test_resolve_output_url() {
+        assert_eq!(
+            resolve_output_url("/about", TrailingSlash::Always),
+            "/about/index.html"
+        );
+        assert_eq!(
+            resolve_output_url("/about/", TrailingSlash::Never),
+            "/about.html"
+        );
+        assert_eq!(
+            resolve_output_url("/", TrailingSlash::Never),
+            "/index.html"
+        );
+    }
+
+    #[test]
+    fn test_default_policy_is_always() {
+        assert_eq!(TrailingSlash::default(), TrailingSlash::Always);
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        let options = UrlOptions::default();
+        assert_eq!(
+            resolve_url("blog/post/", &options),
+            "/blog/post/index.html"
+        );
+        assert_eq!(resolve_url("", &options), "/index.html");
+
+        let pretty = UrlOptions {
+            pretty_urls: true,
+            index_file: "index.htm".to_string(),
+            ..UrlOptions::default()
+        };
+        assert_eq!(resolve_url("blog/post/", &pretty), "/blog/post/");
+        assert_eq!(resolve_url("", &pretty), "/");
+
+        let flat = UrlOptions {
+            policy: TrailingSlash::Never,
+            pretty_urls: true,
+            ..UrlOptions::default()
+        };
+        assert_eq!(resolve_url("about/", &flat), "/about.html");
+        assert_eq!(resolve_url("", &flat), "/");
+
+        let preserve = UrlOptions {
+            policy: TrailingSlash::Preserve,
+            ..UrlOptions::default()
+        };
+        assert_eq!(
+            resolve_url("about/", &preserve),
+            "/about/index.html"
+        );
+        assert_eq!(resolve_url("about", &preserve), "/about.html");
+    }
+
+    #[test]
+    fn test_page_output_path() {
+        let preserve = UrlOptions {
+            policy: TrailingSlash::Preserve,
+            index_file: "default.htm".to_string(),
+            ..UrlOptions::default()
+        };
+        assert_eq!(
+            page_output_path("about", None, &preserve),
+            PathBuf::from("about/default.htm")
+        );
+        assert_eq!(
+            page_output_path("about", Some("/about"), &preserve),
+            PathBuf::from("about.html")
+        );
+        assert_eq!(
+            page_output_path("", Some("/"), &preserve),
+            PathBuf::from("default.htm")
+        );
+    }
+
+    #[test]
+    fn test_split_date_prefix() {
+        let (date, name) =
+            split_date_prefix("2024-01-15-my-post.md").unwrap();
+        assert_eq!(
+            date,
+            Date::from_calendar_date(2024, Month::January, 15).unwrap()
+        );
+        assert_eq!(name, "my-post.md");
+
+        for name in [
+            "my-post.md",
+            "2024-01-15-",
+            "2024-13-15-post.md",
+            "2024-02-30-post.md",
+            "2024-01-15post.md",
+            "20x4-01-15-post.md",
+            "+024-01-15-post.md",
+            "2024-01-1",
+        ] {
+            assert!(split_date_prefix(name).is_none(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Front matter schemas
+//!
+//! A [`MetadataSchema`] states which front matter fields a kind of page
+//! must or may set, and what each holds. [`validate_metadata`] checks a
+//! page against it and returns every [`MetadataIssue`], so a post missing
+//! its date is reported instead of shipping with an empty one.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use staticdatagen::utilities::schema::{
+//!     validate_metadata, FieldType, MetadataIssue, MetadataSchema,
+//! };
+//!
+//! let post = MetadataSchema::new()
+//!     .required("title", FieldType::Text)
+//!     .required("date", FieldType::Date)
+//!     .required("author", FieldType::Text)
+//!     .optional("draft", FieldType::Bool);
+//!
+//! let mut metadata = HashMap::new();
+//! metadata.insert("title".to_string(), "Hello".to_string());
+//! metadata.insert("date".to_string(), "someday".to_string());
+//!
+//! let issues = validate_metadata(&metadata, &post);
+//! assert_eq!(issues.len(), 2);
+//! assert_eq!(issues[0], MetadataIssue::Missing("author".to_string()));
+//! assert!(matches!(issues[1], MetadataIssue::Invalid(_)));
+//! ```
+
+use crate::utilities::metadata::{
+    meta_bool, meta_date, meta_url, MetadataError,
+};
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+
+/// The kind of value a front matter field holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    /// Any text.
+    Text,
+    /// A date, in any layout accepted by [`meta_date`].
+    Date,
+    /// A boolean, as accepted by [`meta_bool`].
+    Bool,
+    /// An absolute URL, as accepted by [`meta_url`].
+    Url,
+}
+
+/// Whether a page must set a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    /// The field must be present and non-empty.
+    Required,
+    /// The field may be absent or empty.
+    Optional,
+}
+
+/// The rule for one front matter field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldRule {
+    /// Whether the field must be set.
+    pub requirement: Requirement,
+    /// The kind of value it holds.
+    pub field_type: FieldType,
+}
+
+/// The front matter fields of one kind of page.
+///
+/// Fields the schema does not name are allowed and not checked.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MetadataSchema {
+    /// The rules, keyed by field name.
+    pub fields: BTreeMap<String, FieldRule>,
+}
+
+impl MetadataSchema {
+    /// Creates a schema with no fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a field the page must set.
+    pub fn required(
+        self,
+        name: impl Into<String>,
+        field_type: FieldType,
+    ) -> Self {
+        self.field(name, Requirement::Required, field_type)
+    }
+
+    /// Adds a field the page may set.
+    pub fn optional(
+        self,
+        name: impl Into<String>,
+        field_type: FieldType,
+    ) -> Self {
+        self.field(name, Requirement::Optional, field_type)
+    }
+
+    /// Adds a field with the given rule, replacing any earlier one.
+    pub fn field(
+        mut self,
+        name: impl Into<String>,
+        requirement: Requirement,
+        field_type: FieldType,
+    ) -> Self {
+        let _ = self.fields.insert(
+            name.into(),
+            FieldRule {
+                requirement,
+                field_type,
+            },
+        );
+        self
+    }
+}
+
+/// A way in which front matter breaks its schema.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MetadataIssue {
+    /// A required field is absent or empty.
+    #[error("Required metadata key '{0}' is missing")]
+    Missing(String),
+
+    /// A field does not hold the expected kind of value.
+    #[error(transparent)]
+    Invalid(MetadataError),
+}
+
+/// Checks `metadata` against `schema`, returning every issue in field
+/// name order.
+///
+/// An empty value counts as absent, so it fails a required field and
+/// passes an optional one.
+pub fn validate_metadata(
+    metadata: &HashMap<String, String>,
+    schema: &MetadataSchema,
+) -> Vec<MetadataIssue> {
+    schema
+        .fields
+        .iter()
+        .filter_map(|(name, rule)| {
+            let checked = match rule.field_type {
+                FieldType::Text => match metadata.get(name) {
+                    Some(value) if value.trim().is_empty() => {
+                        Err(MetadataError::Empty(name.clone()))
+                    }
+                    Some(_) => Ok(Some(())),
+                    None => Ok(None),
+                },
+                FieldType::Date => {
+                    meta_date(metadata, name).map(drop_value)
+                }
+                FieldType::Bool => {
+                    meta_bool(metadata, name).map(drop_value)
+                }
+                FieldType::Url => {
+                    meta_url(metadata, name).map(drop_value)
+                }
+            };
+            match (checked, rule.requirement) {
+                (Ok(Some(())), _) => None,
+                (Ok(None), Requirement::Optional) => None,
+                (Err(e), Requirement::Optional) if e.is_empty() => None,
+                (Ok(None), Requirement::Required) => {
+                    Some(MetadataIssue::Missing(name.clone()))
+                }
+                (Err(e), Requirement::Required) if e.is_empty() => {
+                    Some(MetadataIssue::Missing(name.clone()))
+                }
+                (Err(e), _) => Some(MetadataIssue::Invalid(e)),
+            }
+        })
+        .collect()
+}
+
+/// Keeps whether a value was present, dropping the value.
+fn drop_value<T>(value: Option<T>) -> Option<()> {
+    value.map(drop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::metadata;
+
+    #[test]
+    fn test_validate_metadata_required() {
+        let schema = MetadataSchema::new()
+            .required("title", FieldType::Text)
+            .required("date", FieldType::Date)
+            .required("canonical", FieldType::Url);
+
+        assert!(validate_metadata(
+            &metadata(&[
+                ("title", "Post"),
+                ("date", "2024-01-15"),
+                ("canonical", "https://example.com/post/"),
+            ]),
+            &schema
+        )
+        .is_empty());
+
+        assert_eq!(
+            validate_metadata(
+                &metadata(&[("title", "  "), ("date", "")]),
+                &schema
+            ),
+            [
+                MetadataIssue::Missing("canonical".to_string()),
+                MetadataIssue::Missing("date".to_string()),
+                MetadataIssue::Missing("title".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_metadata_types() {
+        let schema = MetadataSchema::new()
+            .optional("draft", FieldType::Bool)
+            .optional("date", FieldType::Date)
+            .optional("canonical", FieldType::Url)
+            .optional("summary", FieldType::Text);
+
+        assert!(validate_metadata(&metadata(&[]), &schema).is_empty());
+        assert!(validate_metadata(
+            &metadata(&[("draft", ""), ("summary", "")]),
+            &schema
+        )
+        .is_empty());
+
+        let issues = validate_metadata(
+            &metadata(&[
+                ("draft", "maybe"),
+                ("date", "soon"),
+                ("canonical", "/post/"),
+            ]),
+            &schema,
+        );
+        let keys: Vec<&str> = issues
+            .iter()
+            .map(|issue| match issue {
+                MetadataIssue::Invalid(MetadataError::Invalid {
+                    key,
+                    ..
+                }) => key.as_str(),
+                other => panic!("unexpected issue {:?}", other),
+            })
+            .collect();
+        assert_eq!(keys, ["canonical", "date", "draft"]);
+        assert!(issues[2].to_string().contains("expected a boolean"));
+    }
+
+    #[test]
+    fn test_schema_field_replaces_rule() {
+        let schema = MetadataSchema::new()
+            .required("title", FieldType::Text)
+            .optional("title", FieldType::Text);
+
+        assert_eq!(schema.fields.len(), 1);
+        assert!(validate_metadata(&metadata(&[]), &schema).is_empty());
+    }
+}
@@ -0,0 +1,667 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Development server
+//!
+//! [`DevServer`] serves a generated site like the re-exported
+//! `http_handle::Server`, and adds what automated environments need: a
+//! [`HEALTH_CHECK_PATH`] endpoint that always answers `200 OK`, and a
+//! [`ShutdownHandle`] that stops the server cleanly from another thread.
+//! Generated well-known files are served with the content types browsers
+//! and validators expect (see [`DEFAULT_CONTENT_TYPES`]).
+//!
+//! `http_handle::Server::start` binds its own listener, never returns and
+//! builds every response internally, so neither a health check, a
+//! shutdown nor content-type overrides can be added around it. The accept
+//! loop is therefore kept here, on top of the `http_handle` request and
+//! response types: connections are answered by a fixed pool of
+//! [`DEFAULT_WORKERS`] threads, request paths are percent-decoded before
+//! they are confined to the document root, and a server bound to an
+//! unspecified address such as `0.0.0.0` is woken for shutdown through
+//! the loopback address.
+
+use http_handle::request::Request;
+use http_handle::response::Response;
+use http_handle::ServerError;
+use percent_encoding::percent_decode_str;
+use std::{
+    collections::HashMap,
+    fs, io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream},
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// The path of the health-check endpoint.
+pub const HEALTH_CHECK_PATH: &str = "/healthz";
+
+/// The number of threads answering requests by default. Connections
+/// beyond those being answered wait in a queue of the same length.
+pub const DEFAULT_WORKERS: usize = 4;
+
+/// Content types for generated files that cannot be inferred from their
+/// extension, keyed by file name.
+pub const DEFAULT_CONTENT_TYPES: &[(&str, &str)] = &[
+    ("apple-app-site-association", "application/json"),
+    ("manifest.json", "application/manifest+json"),
+    ("security.txt", "text/plain; charset=utf-8"),
+    ("site.webmanifest", "application/manifest+json"),
+];
+
+/// A static file server with a health-check endpoint and graceful
+/// shutdown.
+///
+/// # Examples
+///
+/// ```no_run
+/// use staticdatagen::utilities::server::DevServer;
+///
+/// let running = DevServer::new("127.0.0.1:0", "public").spawn().unwrap();
+/// println!("Serving on http://{}", running.local_addr());
+///
+/// // ... run checks against the server ...
+///
+/// running.shutdown().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevServer {
+    address: String,
+    workers: usize,
+    site: Site,
+}
+
+/// The files served by a [`DevServer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Site {
+    document_root: PathBuf,
+    content_types: HashMap<String, String>,
+}
+
+impl DevServer {
+    /// Creates a new `DevServer` for `address` (e.g. `127.0.0.1:3000`)
+    /// serving files from `document_root`, with the
+    /// [`DEFAULT_CONTENT_TYPES`] overrides.
+    pub fn new(
+        address: &str,
+        document_root: impl Into<PathBuf>,
+    ) -> Self {
+        DevServer {
+            address: address.to_string(),
+            workers: DEFAULT_WORKERS,
+            site: Site {
+                document_root: document_root.into(),
+                content_types: DEFAULT_CONTENT_TYPES
+                    .iter()
+                    .map(|(name, mime)| {
+                        (name.to_string(), mime.to_string())
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    /// Answers requests on `workers` threads instead of
+    /// [`DEFAULT_WORKERS`]. At least one thread is always used.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Serves files matching `name` with `content_type`, replacing any
+    /// existing override.
+    ///
+    /// `name` is either a path relative to the document root (e.g.
+    /// `.well-known/security.txt`), which takes precedence, or a bare
+    /// file name matched in any directory. Overrides apply to every file
+    /// the server sends, after the request path is decoded, including
+    /// directory indexes and the `404/index.html` page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use staticdatagen::utilities::server::DevServer;
+    ///
+    /// let server = DevServer::new("127.0.0.1:0", "public")
+    ///     .with_content_type("feed", "application/rss+xml");
+    /// assert_eq!(
+    ///     server.content_type_for("blog/feed"),
+    ///     "application/rss+xml"
+    /// );
+    /// assert_eq!(
+    ///     server.content_type_for(".well-known/apple-app-site-association"),
+    ///     "application/json"
+    /// );
+    /// ```
+    pub fn with_content_type(
+        mut self,
+        name: &str,
+        content_type: &str,
+    ) -> Self {
+        let _ = self.site.content_types.insert(
+            name.trim_start_matches('/').to_string(),
+            content_type.to_string(),
+        );
+        self
+    }
+
+    /// Returns the content type used to serve `path`, relative to the
+    /// document root.
+    pub fn content_type_for(&self, path: &str) -> &str {
+        self.site
+            .content_type(Path::new(path.trim_start_matches('/')))
+    }
+
+    /// Binds the server and runs it on a background thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound.
+    pub fn spawn(&self) -> io::Result<RunningServer> {
+        let listener = TcpListener::bind(&self.address)?;
+        let local_addr = listener.local_addr()?;
+        let handle = ShutdownHandle {
+            stop: Arc::new(AtomicBool::new(false)),
+            wake_addr: wake_addr(local_addr),
+        };
+
+        let site = Arc::new(self.site.clone());
+        let stop = Arc::clone(&handle.stop);
+        let workers = self.workers;
+        let thread = thread::spawn(move || {
+            serve(&listener, &site, &stop, workers)
+        });
+
+        Ok(RunningServer {
+            local_addr,
+            handle,
+            thread,
+        })
+    }
+
+    /// Runs the server on the current thread, blocking until it stops.
+    /// Use [`DevServer::spawn`] to obtain a [`ShutdownHandle`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound.
+    pub fn start(&self) -> io::Result<()> {
+        let running = self.spawn()?;
+        log::info!(
+            "Server is now running at http://{}",
+            running.local_addr()
+        );
+        log::info!(
+            "Document root: {}",
+            self.site.document_root.display()
+        );
+        log::info!("Health check: {}", HEALTH_CHECK_PATH);
+        running.join()
+    }
+}
+
+/// A handle that stops a [`DevServer`] from any thread.
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    stop: Arc<AtomicBool>,
+    /// The address connected to to wake the accept loop.
+    wake_addr: SocketAddr,
+}
+
+impl ShutdownHandle {
+    /// Asks the server to stop accepting connections. In-flight requests
+    /// are allowed to complete.
+    pub fn shutdown(&self) {
+        if !self.stop.swap(true, Ordering::SeqCst) {
+            // Wake the blocking `accept` so the loop observes the flag.
+            let _ = TcpStream::connect(self.wake_addr);
+        }
+    }
+
+    /// Returns `true` once shutdown has been requested.
+    pub fn is_shutdown(&self) -> bool {
+        self.stop.load(Ordering::SeqCst)
+    }
+}
+
+/// A [`DevServer`] running on a background thread.
+#[derive(Debug)]
+pub struct RunningServer {
+    local_addr: SocketAddr,
+    handle: ShutdownHandle,
+    thread: JoinHandle<io::Result<()>>,
+}
+
+impl RunningServer {
+    /// Returns the address the server is bound to, which resolves port
+    /// `0` to the port actually assigned.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns a handle that can stop the server from another thread.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.handle.clone()
+    }
+
+    /// Stops the server and waits for its accept loop to exit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the accept loop failed.
+    pub fn shutdown(self) -> io::Result<()> {
+        self.handle.shutdown();
+        self.join()
+    }
+
+    /// Waits for the server to stop, e.g. after a [`ShutdownHandle`] was
+    /// triggered elsewhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the accept loop failed or panicked.
+    pub fn join(self) -> io::Result<()> {
+        self.thread.join().unwrap_or_else(|_| {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Server thread panicked",
+            ))
+        })
+    }
+}
+
+/// Returns the address that reaches a listener bound to `local_addr`:
+/// the loopback address in place of an unspecified one, which cannot be
+/// connected to on every platform.
+fn wake_addr(local_addr: SocketAddr) -> SocketAddr {
+    let mut addr = local_addr;
+    if addr.ip().is_unspecified() {
+        addr.set_ip(match addr {
+            SocketAddr::V4(_) => Ipv4Addr::LOCALHOST.into(),
+            SocketAddr::V6(_) => Ipv6Addr::LOCALHOST.into(),
+        });
+    }
+    addr
+}
+
+/// Accepts connections until `stop` is set, handing them to `workers`
+/// threads, then waits for the requests in flight.
+fn serve(
+    listener: &TcpListener,
+    site: &Arc<Site>,
+    stop: &AtomicBool,
+    workers: usize,
+) -> io::Result<()> {
+    let (sender, receiver) = mpsc::sync_channel(workers);
+    let receiver = Arc::new(Mutex::new(receiver));
+    let pool: Vec<JoinHandle<()>> = (0..workers)
+        .map(|_| {
+            let receiver = Arc::clone(&receiver);
+            let site = Arc::clone(site);
+            thread::spawn(move || work(&receiver, &site))
+        })
+        .collect();
+
+    for stream in listener.incoming() {
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                if sender.send(stream).is_err() {
+                    break;
+                }
+            }
+            Err(e) => log::warn!("Connection error: {}", e),
+        }
+    }
+
+    drop(sender);
+    for worker in pool {
+        if worker.join().is_err() {
+            log::warn!("A server worker thread panicked");
+        }
+    }
+    Ok(())
+}
+
+/// Answers the connections received on `receiver` until it is closed.
+fn work(receiver: &Mutex<Receiver<TcpStream>>, site: &Site) {
+    loop {
+        // The lock is released before the connection is handled.
+        let stream = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => return,
+        };
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = handle_connection(stream, site) {
+                    log::warn!("Error handling connection: {}", e);
+                }
+            }
+            Err(_) => return,
+        }
+    }
+}
+
+/// Reads a request from `stream` and writes the response.
+fn handle_connection(
+    mut stream: TcpStream,
+    site: &Site,
+) -> Result<(), ServerError> {
+    let request = Request::from_stream(&stream)?;
+    respond(&request, site)?.send(&mut stream)
+}
+
+/// Builds the response for `request`.
+fn respond(
+    request: &Request,
+    site: &Site,
+) -> Result<Response, ServerError> {
+    let document_root = &site.document_root;
+    let path = request.path().split(['?', '#']).next().unwrap_or("/");
+    let path = match percent_decode_str(path).decode_utf8() {
+        Ok(path) => path,
+        Err(_) => {
+            let mut response = Response::new(
+                400,
+                "BAD REQUEST",
+                b"400 Bad Request".to_vec(),
+            );
+            response.add_header("Content-Type", "text/plain");
+            return Ok(response);
+        }
+    };
+
+    if path == HEALTH_CHECK_PATH {
+        let mut response = Response::new(200, "OK", b"OK".to_vec());
+        response.add_header("Content-Type", "text/plain");
+        return Ok(response);
+    }
+
+    let mut file = document_root.to_path_buf();
+    for component in
+        Path::new(path.trim_start_matches('/')).components()
+    {
+        match component {
+            Component::Normal(part) => file.push(part),
+            Component::CurDir => {}
+            _ => {
+                let mut response = Response::new(
+                    403,
+                    "FORBIDDEN",
+                    b"403 Forbidden".to_vec(),
+                );
+                response.add_header("Content-Type", "text/plain");
+                return Ok(response);
+            }
+        }
+    }
+    if file.is_dir() {
+        file.push("index.html");
+    }
+
+    let (status, text, file) = if file.is_file() {
+        (200, "OK", file)
+    } else {
+        (404, "NOT FOUND", document_root.join("404/index.html"))
+    };
+
+    let (body, content_type) = if file.is_file() {
+        (
+            fs::read(&file)?,
+            site.content_type(
+                file.strip_prefix(document_root).unwrap_or(&file),
+            ),
+        )
+    } else {
+        (b"404 Not Found".to_vec(), "text/html")
+    };
+
+    let mut response = Response::new(status, text, body);
+    response.add_header("Content-Type", content_type);
+    Ok(response)
+}
+
+impl Site {
+    /// Returns the content type for `relative`, a path relative to the
+    /// document root: an override for the full path, then one for the
+    /// file name, then the type implied by the extension.
+    fn content_type(&self, relative: &Path) -> &str {
+        let key = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let file_name = relative
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+
+        self.content_types
+            .get(&key)
+            .or_else(|| self.content_types.get(file_name.as_ref()))
+            .map_or_else(|| content_type(relative), String::as_str)
+    }
+}
+
+/// Returns the content type for `path` based on its extension.
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html",
+        Some("css") => "text/css",
+        Some("js") => "application/javascript",
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("txt") => "text/plain",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use tempfile::tempdir;
+
+    fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n",
+            path
+        )
+        .unwrap();
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_health_check_and_shutdown() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("index.html"), "<h1>Home</h1>")
+            .unwrap();
+
+        let running =
+            DevServer::new("127.0.0.1:0", dir.path()).spawn().unwrap();
+        let addr = running.local_addr();
+
+        let health = get(addr, HEALTH_CHECK_PATH);
+        assert!(health.starts_with("HTTP/1.1 200 OK"));
+        assert!(health.ends_with("OK"));
+
+        let home = get(addr, "/");
+        assert!(home.starts_with("HTTP/1.1 200 OK"));
+        assert!(home.contains("<h1>Home</h1>"));
+
+        assert!(get(addr, "/missing").starts_with("HTTP/1.1 404"));
+        assert!(get(addr, "/../secret").starts_with("HTTP/1.1 403"));
+
+        let handle = running.shutdown_handle();
+        thread::spawn(move || handle.shutdown()).join().unwrap();
+        running.join().unwrap();
+
+        assert!(TcpStream::connect(addr).is_err());
+    }
+
+    #[test]
+    fn test_percent_encoded_paths() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("my docs")).unwrap();
+        fs::write(dir.path().join("my docs/a b.html"), "<p>Spaced</p>")
+            .unwrap();
+
+        let running = DevServer::new("127.0.0.1:0", dir.path())
+            .with_workers(1)
+            .spawn()
+            .unwrap();
+        let addr = running.local_addr();
+
+        let spaced = get(addr, "/my%20docs/a%20b.html?v=1");
+        assert!(spaced.starts_with("HTTP/1.1 200 OK"), "{}", spaced);
+        assert!(spaced.contains("<p>Spaced</p>"));
+        assert!(get(addr, "/%2e%2e/secret").starts_with("HTTP/1.1 403"));
+        assert!(get(addr, "/my%20docs%2F..%2F..%2Fsecret")
+            .starts_with("HTTP/1.1 403"));
+        assert!(get(addr, "/%ff").starts_with("HTTP/1.1 400"));
+
+        running.shutdown().unwrap();
+    }
+
+    #[test]
+    fn test_shutdown_unspecified_address() {
+        let dir = tempdir().unwrap();
+        let running =
+            DevServer::new("0.0.0.0:0", dir.path()).spawn().unwrap();
+        assert!(running.local_addr().ip().is_unspecified());
+
+        running.shutdown().unwrap();
+    }
+
+    #[test]
+    fn test_wake_addr() {
+        let any: SocketAddr = "0.0.0.0:8080".parse().unwrap();
+        assert_eq!(
+            wake_addr(any),
+            "127.0.0.1:8080".parse::<SocketAddr>().unwrap()
+        );
+        let any: SocketAddr = "[::]:8080".parse().unwrap();
+        assert_eq!(
+            wake_addr(any),
+            "[::1]:8080".parse::<SocketAddr>().unwrap()
+        );
+        let local: SocketAddr = "192.0.2.1:80".parse().unwrap();
+        assert_eq!(wake_addr(local), local);
+    }
+
+    #[test]
+    fn test_shutdown_is_idempotent() {
+        let dir = tempdir().unwrap();
+        let running =
+            DevServer::new("127.0.0.1:0", dir.path()).spawn().unwrap();
+        let handle = running.shutdown_handle();
+
+        handle.shutdown();
+        handle.shutdown();
+        assert!(handle.is_shutdown());
+        running.shutdown().unwrap();
+    }
+
+    #[test]
+    fn test_well_known_content_types() {
+        let dir = tempdir().unwrap();
+        let well_known = dir.path().join(".well-known");
+        fs::create_dir(&well_known).unwrap();
+        fs::write(well_known.join("security.txt"), "Contact: x")
+            .unwrap();
+        fs::write(well_known.join("apple-app-site-association"), "{}")
+            .unwrap();
+        fs::write(dir.path().join("manifest.json"), "{}").unwrap();
+
+        let running =
+            DevServer::new("127.0.0.1:0", dir.path()).spawn().unwrap();
+        let addr = running.local_addr();
+
+        assert!(get(addr, "/.well-known/security.txt")
+            .contains("Content-Type: text/plain; charset=utf-8\r\n"));
+        assert!(get(addr, "/.well-known/apple-app-site-association")
+            .contains("Content-Type: application/json\r\n"));
+        assert!(get(addr, "/manifest.json")
+            .contains("Content-Type: application/manifest+json\r\n"));
+
+        running.shutdown().unwrap();
+    }
+
+    #[test]
+    fn test_content_type_overrides_are_served() {
+        let dir = tempdir().unwrap();
+        let data = dir.path().join("my data");
+        fs::create_dir(&data).unwrap();
+        fs::write(data.join("feed"), "<rss/>").unwrap();
+        fs::create_dir(dir.path().join("404")).unwrap();
+        fs::write(dir.path().join("404/index.html"), "Gone").unwrap();
+
+        let running = DevServer::new("127.0.0.1:0", dir.path())
+            .with_content_type("feed", "application/rss+xml")
+            .with_content_type(
+                "404/index.html",
+                "text/html; charset=utf-8",
+            )
+            .spawn()
+            .unwrap();
+        let addr = running.local_addr();
+
+        assert!(get(addr, "/my%20data/feed")
+            .contains("Content-Type: application/rss+xml\r\n"));
+        let missing = get(addr, "/missing");
+        assert!(missing.starts_with("HTTP/1.1 404"));
+        assert!(missing
+            .contains("Content-Type: text/html; charset=utf-8\r\n"));
+
+        running.shutdown().unwrap();
+    }
+
+    #[test]
+    fn test_content_type_overrides() {
+        let server = DevServer::new("127.0.0.1:0", "public")
+            .with_content_type(
+                "/data/manifest.json",
+                "application/json",
+            );
+
+        assert_eq!(
+            server.content_type_for("data/manifest.json"),
+            "application/json"
+        );
+        assert_eq!(
+            server.content_type_for("manifest.json"),
+            "application/manifest+json"
+        );
+        assert_eq!(
+            server.content_type_for("other.json"),
+            "application/json"
+        );
+        assert_eq!(server.content_type_for("style.css"), "text/css");
+        assert_eq!(
+            server.content_type_for("unknown"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn test_bind_error() {
+        let dir = tempdir().unwrap();
+        assert!(DevServer::new("invalid-address", dir.path())
+            .spawn()
+            .is_err());
+    }
+}
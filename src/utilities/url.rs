@@ -0,0 +1,309 @@
+// Copyright © 2025 Static Data Gen. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Absolute URLs from permalinks
+//!
+//! Sitemaps, feeds, canonical links and JSON-LD all need absolute URLs,
+//! while front matter permalinks may be relative. [`to_absolute`] joins a
+//! permalink onto the site URL the same way for every output: the site
+//! URL's path is kept, slashes between the two are not doubled, and the
+//! permalink's query and fragment survive.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use staticdatagen::utilities::url::to_absolute;
+//!
+//! assert_eq!(
+//!     to_absolute("https://example.com/docs", "/guide/?v=2#setup").unwrap(),
+//!     "https://example.com/docs/guide/?v=2#setup"
+//! );
+//! assert_eq!(
+//!     to_absolute("https://example.com", "https://cdn.example.com/a.png")
+//!         .unwrap(),
+//!     "https://cdn.example.com/a.png"
+//! );
+//! assert!(to_absolute("example.com", "/about/").is_err());
+//! ```
+//!
+//! When the production domain is not known yet, [`UrlMode::Relative`]
+//! produces root-relative URLs instead. Navigation links are always
+//! root-relative and follow the URL options of
+//! [`resolve_url`](crate::utilities::permalink::resolve_url) instead.
+
+use crate::models::data::validation::validate_url;
+use thiserror::Error;
+use url::Url;
+
+/// Errors returned when an absolute URL cannot be built.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// The base URL is not an absolute HTTP(S) URL.
+    #[error(
+        "Invalid base URL '{0}': expected an absolute http(s) URL"
+    )]
+    InvalidBase(String),
+
+    /// The permalink does not resolve to a valid HTTP(S) URL.
+    #[error("Invalid permalink '{0}': {1}")]
+    InvalidPermalink(String, String),
+}
+
+/// Resolves `permalink` to an absolute URL under `base_url`.
+///
+/// - An absolute or protocol-relative (`//host/path`) permalink is kept
+///   as is.
+/// - A relative permalink is appended to the path of `base_url`, with or
+///   without a leading slash, so `/about/` under
+///   `https://example.com/docs/` gives `https://example.com/docs/about/`.
+/// - An empty permalink gives `base_url` itself, with a trailing slash.
+///
+/// The permalink's query string and fragment are preserved; those of
+/// `base_url` are dropped.
+///
+/// # Errors
+///
+/// Returns [`UrlError::InvalidBase`] if `base_url` is not an absolute
+/// HTTP(S) URL, and [`UrlError::InvalidPermalink`] if the result is not a
+/// valid HTTP(S) URL.
+pub fn to_absolute(
+    base_url: &str,
+    permalink: &str,
+) -> Result<String, UrlError> {
+    let mut base = Url::parse(base_url.trim())
+        .ok()
+        .filter(is_http)
+        .ok_or_else(|| UrlError::InvalidBase(base_url.to_string()))?;
+    base.set_query(None);
+    base.set_fragment(None);
+    if !base.path().ends_with('/') {
+        let path = format!("{}/", base.path());
+        base.set_path(&path);
+    }
+
+    let permalink = permalink.trim();
+    let invalid = |reason: String| {
+        UrlError::InvalidPermalink(permalink.to_string(), reason)
+    };
+    let relative = if permalink.starts_with("//") {
+        permalink
+    } else {
+        permalink.trim_start_matches('/')
+    };
+
+    let url =
+        base.join(relative).map_err(|e| invalid(e.to_string()))?;
+    if !is_http(&url) {
+        return Err(invalid("expected an http(s) URL".to_string()));
+    }
+    let url = String::from(url);
+    validate_url(&url).map_err(|e| invalid(e.to_string()))?;
+    Ok(url)
+}
+
+/// How URLs are written in generated files such as sitemaps and feeds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UrlMode {
+    /// Absolute URLs under the given site URL (see [`to_absolute`]).
+    Absolute(String),
+    /// Root-relative URLs such as `/about/`, for previews built before
+    /// the site's domain is known.
+    Relative,
+}
+
+impl UrlMode {
+    /// Checks that the base URL of [`UrlMode::Absolute`] is an absolute
+    /// HTTP(S) URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UrlError::InvalidBase`] if it is not.
+    pub fn validate(&self) -> Result<(), UrlError> {
+        match self {
+            UrlMode::Absolute(base_url) => {
+                to_absolute(base_url, "").map(drop)
+            }
+            UrlMode::Relative => Ok(()),
+        }
+    }
+
+    /// Writes `permalink` as a URL in this mode.
+    ///
+    /// In relative mode, the path, query and fragment of an absolute
+    /// permalink are kept and its origin dropped, so
+    /// `https://example.com/about/` and `about/` both give `/about/`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`to_absolute`] in absolute mode, and
+    /// [`UrlError::InvalidPermalink`] for an unsafe relative URL.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use staticdatagen::utilities::url::UrlMode;
+    ///
+    /// let absolute = UrlMode::Absolute("https://example.com".to_string());
+    /// assert_eq!(absolute.resolve("/about/").unwrap(), "https://example.com/about/");
+    /// assert_eq!(UrlMode::Relative.resolve("https://example.com/about/").unwrap(), "/about/");
+    /// assert_eq!(UrlMode::Relative.resolve("about/?v=2").unwrap(), "/about/?v=2");
+    /// ```
+    pub fn resolve(&self, permalink: &str) -> Result<String, UrlError> {
+        let base_url = match self {
+            UrlMode::Absolute(base_url) => {
+                return to_absolute(base_url, permalink)
+            }
+            UrlMode::Relative => "http://localhost",
+        };
+
+        // Resolve against a placeholder origin, then drop it.
+        let url = Url::parse(&to_absolute(base_url, permalink)?)
+            .map_err(|e| {
+                UrlError::InvalidPermalink(
+                    permalink.to_string(),
+                    e.to_string(),
+                )
+            })?;
+        let mut relative = url.path().to_string();
+        if let Some(query) = url.query() {
+            relative.push('?');
+            relative.push_str(query);
+        }
+        if let Some(fragment) = url.fragment() {
+            relative.push('#');
+            relative.push_str(fragment);
+        }
+        Ok(relative)
+    }
+}
+
+/// Returns `true` for `http` and `https` URLs with a host.
+fn is_http(url: &Url) -> bool {
+    matches!(url.scheme(), "http" | "https") && url.has_host()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_absolute_relative() {
+        for (base, permalink, expected) in [
+            (
+                "https://example.com",
+                "/about/",
+                "https://example.com/about/",
+            ),
+            (
+                "https://example.com/",
+                "about/",
+                "https://example.com/about/",
+            ),
+            (
+                "https://example.com/docs",
+                "/guide/",
+                "https://example.com/docs/guide/",
+            ),
+            (
+                "https://example.com/docs/?page=1#top",
+                "guide",
+                "https://example.com/docs/guide",
+            ),
+            ("https://example.com", "", "https://example.com/"),
+            (
+                "https://example.com/docs",
+                "/",
+                "https://example.com/docs/",
+            ),
+        ] {
+            assert_eq!(
+                to_absolute(base, permalink).unwrap(),
+                expected,
+                "{} + {}",
+                base,
+                permalink
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_absolute_keeps_query_and_fragment() {
+        assert_eq!(
+            to_absolute(
+                "https://example.com",
+                "/search/?q=rust&p=2#results"
+            )
+            .unwrap(),
+            "https://example.com/search/?q=rust&p=2#results"
+        );
+    }
+
+    #[test]
+    fn test_to_absolute_absolute_permalink() {
+        assert_eq!(
+            to_absolute("https://example.com", " http://other.org/a ")
+                .unwrap(),
+            "http://other.org/a"
+        );
+        assert_eq!(
+            to_absolute(
+                "https://example.com",
+                "//cdn.example.com/a.png"
+            )
+            .unwrap(),
+            "https://cdn.example.com/a.png"
+        );
+    }
+
+    #[test]
+    fn test_url_mode() {
+        let absolute =
+            UrlMode::Absolute("https://example.com/docs".into());
+        assert_eq!(
+            absolute.resolve("/guide/").unwrap(),
+            "https://example.com/docs/guide/"
+        );
+        assert!(absolute.validate().is_ok());
+        assert!(UrlMode::Absolute("http:///".into())
+            .validate()
+            .is_err());
+
+        for (permalink, expected) in [
+            ("", "/"),
+            ("/about/", "/about/"),
+            ("about", "/about"),
+            ("https://example.com/about/?a=1#b", "/about/?a=1#b"),
+        ] {
+            assert_eq!(
+                UrlMode::Relative.resolve(permalink).unwrap(),
+                expected,
+                "{}",
+                permalink
+            );
+        }
+        assert!(UrlMode::Relative.validate().is_ok());
+        assert!(UrlMode::Relative
+            .resolve("javascript:alert(1)")
+            .is_err());
+    }
+
+    #[test]
+    fn test_to_absolute_errors() {
+        for base in
+            ["", "example.com", "ftp://example.com", "file:///tmp"]
+        {
+            assert_eq!(
+                to_absolute(base, "/about/"),
+                Err(UrlError::InvalidBase(base.to_string()))
+            );
+        }
+        for permalink in
+            ["mailto:me@example.com", "javascript:alert(1)", "http://"]
+        {
+            assert!(matches!(
+                to_absolute("https://example.com", permalink),
+                Err(UrlError::InvalidPermalink(..))
+            ));
+        }
+    }
+}
@@ -19,13 +19,14 @@
 //! Functions use `anyhow::Result` for error propagation and leverage `log`
 //! statements for debugging and informational messages.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use log::{debug, info};
 use std::fs::{self, copy, read_dir};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::time::Instant;
 
-use crate::models::data::FileData;
+use crate::models::data::{validation::sanitize_path, FileData};
+use crate::utilities::permalink::{page_output_path, UrlOptions};
 use html_generator::performance::minify_html;
 
 /// Constants for auxiliary files that should be copied to the build directory.
@@ -44,6 +45,83 @@
     "news-sitemap.xml",
 ];
 
+/// The per-page metadata sidecar, written only when it has content.
+const PAGE_JSON_FILE: &str = "page.json";
+
+/// Text files whose line endings are normalised according to
+/// [`WriteOptions`].
+const TEXT_FILES: [&str; 4] =
+    ["CNAME", "humans.txt", "robots.txt", "security.txt"];
+
+/// The line ending used when writing generated text files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// Unix line endings (`\n`).
+    Lf,
+    /// Windows line endings (`\r\n`).
+    CrLf,
+}
+
+impl Default for LineEnding {
+    fn default() -> Self {
+        LineEnding::Lf
+    }
+}
+
+impl LineEnding {
+    /// Returns the line terminator as a string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Options applied to generated text files (`CNAME`, `humans.txt`,
+/// `robots.txt` and `security.txt`) during the write phase.
+///
+/// The default keeps the content as generated: `\n` line endings and no
+/// added trailing newline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct WriteOptions {
+    /// The line ending every line is normalised to.
+    pub line_ending: LineEnding,
+    /// Whether non-empty files must end with a line ending.
+    pub trailing_newline: bool,
+}
+
+impl WriteOptions {
+    /// Normalises the line endings of `content` and, if requested,
+    /// ensures it ends with a newline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use staticdatagen::utilities::write::{LineEnding, WriteOptions};
+    ///
+    /// let options = WriteOptions {
+    ///     line_ending: LineEnding::CrLf,
+    ///     trailing_newline: true,
+    /// };
+    /// assert_eq!(options.apply("a\nb\r\nc"), "a\r\nb\r\nc\r\n");
+    /// ```
+    pub fn apply(&self, content: &str) -> String {
+        let mut normalised = content
+            .replace("\r\n", "\n")
+            .replace('\n', self.line_ending.as_str());
+
+        if self.trailing_newline
+            && !normalised.is_empty()
+            && !normalised.ends_with(self.line_ending.as_str())
+        {
+            normalised.push_str(self.line_ending.as_str());
+        }
+
+        normalised
+    }
+}
+
 /// Writes the files to the build directory.
 ///
 /// This function orchestrates writing either the index files (if the current file
@@ -58,7 +136,14 @@
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` if successful, or an error if any operation fails.
+/// Returns `Ok(())` if successful, or an error if any operation fails,
+/// including a `file.name` that would write outside `build_dir_path`.
+///
+/// # Empty files
+///
+/// A generated file whose `FileData` field is empty is not written,
+/// whether its generator was disabled or had nothing to emit. Only the
+/// page's `index.html` is written even when empty.
 ///
 /// # Example
 ///
@@ -78,6 +163,36 @@ pub fn write_files_to_build_directory(
     build_dir_path: &Path,
     file: &FileData,
     template_path: &Path,
+) -> Result<()> {
+    write_files_to_build_directory_with_options(
+        build_dir_path,
+        file,
+        template_path,
+        &WriteOptions::default(),
+    )
+}
+
+/// Writes the files to the build directory, applying `options` to the
+/// generated text files.
+///
+/// This behaves like [`write_files_to_build_directory`], which uses
+/// [`WriteOptions::default`].
+///
+/// # Arguments
+///
+/// * `build_dir_path` - The path to the build directory
+/// * `file` - The `FileData` object containing file name, content, and related metadata
+/// * `template_path` - The path to the template directory containing auxiliary files
+/// * `options` - The line-ending and trailing-newline options
+///
+/// # Returns
+///
+/// Returns `Ok(())` if successful, or an error if any operation fails.
+pub fn write_files_to_build_directory_with_options(
+    build_dir_path: &Path,
+    file: &FileData,
+    template_path: &Path,
+    options: &WriteOptions,
 ) -> Result<()> {
     info!(
         "Starting file write to build directory: {}",
@@ -85,25 +200,40 @@ pub fn write_files_to_build_directory(
     );
 
     let start_time = Instant::now();
+    let _ = confined_path(build_dir_path, &file.name)?;
     let file_name = get_processed_file_name(&file.name);
     let index_html_minified = file_name == "index";
-    let dir_name = build_dir_path.join(&file_name);
+    let dir_name = confined_path(build_dir_path, &file_name)?;
 
     debug!("Processed file name: '{}'", file_name);
     debug!("Index HTML minification: {}", index_html_minified);
 
+    if !file.output_path.is_empty() {
+        write_page_html(build_dir_path, file, index_html_minified)?;
+    }
+
     if file_name == "index" {
         info!("Writing index files...");
-        write_index_files(build_dir_path, file, index_html_minified)
-            .context("Failed to write index files")?;
+        write_index_files(
+            build_dir_path,
+            file,
+            index_html_minified,
+            options,
+        )
+        .context("Failed to write index files")?;
 
         info!("Copying auxiliary files...");
         copy_auxiliary_files(template_path, build_dir_path)
             .context("Failed to copy auxiliary files")?;
     } else {
         info!("Writing content files to '{}'", dir_name.display());
-        write_content_files(&dir_name, file, index_html_minified)
-            .context("Failed to write content files")?;
+        write_content_files(
+            &dir_name,
+            file,
+            index_html_minified,
+            options,
+        )
+        .context("Failed to write content files")?;
 
         info!("Printing section headers...");
         print_section_headers(&dir_name, start_time)
@@ -117,6 +247,119 @@ pub fn write_files_to_build_directory(
     Ok(())
 }
 
+/// Returns the paths, relative to the build directory, that
+/// [`write_files_to_build_directory`] writes for `file`, without writing
+/// anything.
+///
+/// Auxiliary files copied from the template directory are not included.
+///
+/// # Arguments
+///
+/// * `file` - The `FileData` object whose non-empty fields are written
+///
+/// # Returns
+///
+/// The paths in write order, or an error for a `file.name` that would
+/// write outside the build directory.
+pub(crate) fn output_paths(file: &FileData) -> Result<Vec<PathBuf>> {
+    let root = Path::new("");
+    let _ = confined_path(root, &file.name)?;
+    let file_name = get_processed_file_name(&file.name);
+
+    let (dir_name, files) = if file_name == "index" {
+        let files = INDEX_FILES
+            .iter()
+            .map(|name| (*name, get_file_content(file, name)))
+            .collect::<Vec<_>>();
+        (PathBuf::new(), files)
+    } else {
+        let files = get_file_paths(file)
+            .into_iter()
+            .map(|(name, content)| (name, content.to_string()))
+            .collect();
+        (confined_path(root, &file_name)?, files)
+    };
+
+    let mut paths: Vec<PathBuf> = files
+        .iter()
+        .filter(|(name, content)| {
+            !is_relocated(file, name) && !is_skipped(name, content)
+        })
+        .map(|(name, _)| dir_name.join(name))
+        .collect();
+    if !file.output_path.is_empty() {
+        paths.insert(0, confined_path(root, &file.output_path)?);
+    }
+    if !file.json.is_empty() {
+        paths.push(dir_name.join(PAGE_JSON_FILE));
+    }
+    for (template, _) in &file.alternates {
+        paths.push(
+            confined_path(&dir_name, template)?.join("index.html"),
+        );
+    }
+    Ok(paths)
+}
+
+/// Returns the root-relative URL of the directory `file_name` is written
+/// to: `/` for the home page, `/<name>/` otherwise.
+pub(crate) fn page_url_path(file_name: &str) -> String {
+    match get_processed_file_name(file_name).as_str() {
+        "index" => "/".to_string(),
+        name => format!("/{}/", name.trim_matches('/')),
+    }
+}
+
+/// Returns the path, relative to the build directory, that the HTML of
+/// the page loaded from `file_name` is written to under `urls`, given the
+/// page's front matter `permalink` (see [`page_output_path`]).
+pub(crate) fn page_html_path(
+    file_name: &str,
+    permalink: Option<&str>,
+    urls: &UrlOptions,
+) -> String {
+    let stem = match get_processed_file_name(file_name) {
+        name if name == "index" => String::new(),
+        name => name,
+    };
+    page_output_path(&stem, permalink, urls)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Joins `relative` onto `base_dir`, rejecting any path that could resolve
+/// outside of it.
+///
+/// # Arguments
+///
+/// * `base_dir` - The directory the output must stay within.
+/// * `relative` - The relative path, usually derived from a file name.
+///
+/// # Returns
+///
+/// The joined path, or an error if `relative` is empty, absolute, or
+/// contains a `..` component.
+fn confined_path(base_dir: &Path, relative: &str) -> Result<PathBuf> {
+    let path = sanitize_path(relative).with_context(|| {
+        format!(
+            "Refusing to write outside the build directory: '{}'",
+            relative
+        )
+    })?;
+    let is_relative = path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if relative.trim().is_empty() || !is_relative {
+        bail!(
+            "Refusing to write outside the build directory: '{}'",
+            relative
+        );
+    }
+    Ok(base_dir.join(path))
+}
+
 /// Gets the processed file name without extension for supported file types.
 ///
 /// This function checks if the file name has one of the known extensions
@@ -156,6 +399,7 @@ fn get_processed_file_name(original_name: &str) -> String {
 /// Writes content to a file with optional HTML minification.
 ///
 /// If `minify` is `true` and `file_name` is `"index.html"`, the file will be minified after writing.
+/// Text files listed in `TEXT_FILES` are normalised with `options` first.
 ///
 /// # Arguments
 ///
@@ -163,6 +407,7 @@ fn get_processed_file_name(original_name: &str) -> String {
 /// * `file_name` - Name of the file to write
 /// * `content` - Content to write to the file
 /// * `minify` - Whether to minify HTML content after writing
+/// * `options` - The options applied to text files
 ///
 /// # Returns
 ///
@@ -172,10 +417,17 @@ fn write_file(
     file_name: &str,
     content: &str,
     minify: bool,
+    options: &WriteOptions,
 ) -> Result<()> {
     let file_path = dir_path.join(file_name);
     debug!("Writing file: '{}'", file_path.display());
 
+    let content = if TEXT_FILES.contains(&file_name) {
+        options.apply(content)
+    } else {
+        content.to_string()
+    };
+
     fs::write(&file_path, content).with_context(|| {
         format!("Failed to write file at '{}'", file_path.display())
     })?;
@@ -282,6 +534,59 @@ fn get_file_paths(file: &FileData) -> Vec<(&'static str, &str)> {
     ]
 }
 
+/// Returns whether the `file_name` entry is the page's HTML, written to
+/// its [`FileData::output_path`] instead (see [`write_page_html`]).
+fn is_relocated(file: &FileData, file_name: &str) -> bool {
+    file_name == "index.html" && !file.output_path.is_empty()
+}
+
+/// Writes the page's HTML to its [`FileData::output_path`] under
+/// `build_dir_path`, creating its directory.
+///
+/// # Arguments
+///
+/// * `build_dir_path` - Path to the build directory
+/// * `file` - The `FileData` object whose `content` is written
+/// * `minify` - Whether to minify the HTML after writing
+///
+/// # Returns
+///
+/// `Ok(())` if the page is written, or an error if its path would write
+/// outside `build_dir_path` or writing fails.
+fn write_page_html(
+    build_dir_path: &Path,
+    file: &FileData,
+    minify: bool,
+) -> Result<()> {
+    let path = confined_path(build_dir_path, &file.output_path)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| {
+            format!("Failed to create directory '{}'", parent.display())
+        })?;
+    }
+    debug!("Writing page: '{}'", path.display());
+    fs::write(&path, &file.content).with_context(|| {
+        format!("Failed to write file at '{}'", path.display())
+    })?;
+    if minify {
+        minify_file(&path).context("Failed to minify HTML file")?;
+    }
+    Ok(())
+}
+
+/// Returns whether `file_name` is left unwritten because its generator
+/// produced nothing: it was disabled, or had nothing to emit for the
+/// page.
+///
+/// `index.html` is always written.
+fn is_skipped(file_name: &str, content: &str) -> bool {
+    let skipped = content.is_empty() && file_name != "index.html";
+    if skipped {
+        debug!("Skipping empty file: '{}'", file_name);
+    }
+    skipped
+}
+
 /// Retrieves content from a `FileData` object based on the provided file name.
 ///
 /// If the file name matches one of the known special files (like "CNAME", "index.html", etc.),
@@ -320,6 +625,7 @@ fn get_file_content(file: &FileData, file_name: &str) -> String {
 /// * `build_dir_path` - Path to the build directory
 /// * `file` - The `FileData` object containing content for these files
 /// * `index_html_minified` - Whether to minify `index.html` after writing
+/// * `options` - The options applied to text files
 ///
 /// # Returns
 ///
@@ -328,15 +634,23 @@ fn write_index_files(
     build_dir_path: &Path,
     file: &FileData,
     index_html_minified: bool,
+    options: &WriteOptions,
 ) -> Result<()> {
     debug!("Writing index files to '{}'", build_dir_path.display());
     for file_name in &INDEX_FILES {
+        let content = get_file_content(file, file_name);
+        if is_relocated(file, file_name)
+            || is_skipped(file_name, &content)
+        {
+            continue;
+        }
         debug!("Writing index file: '{}'", file_name);
         write_file(
             build_dir_path,
             file_name,
-            &get_file_content(file, file_name),
+            &content,
             index_html_minified,
+            options,
         )
         .with_context(|| {
             format!(
@@ -346,7 +660,8 @@ fn write_index_files(
             )
         })?;
     }
-    Ok(())
+    write_page_json(build_dir_path, file, options)?;
+    write_alternates(build_dir_path, file, index_html_minified, options)
 }
 
 /// Copies auxiliary files (e.g., JavaScript and service worker files) from the template directory
@@ -389,6 +704,7 @@ fn copy_auxiliary_files(
 /// * `dir_name` - The directory where the content files should be placed
 /// * `file` - The `FileData` object containing the file contents
 /// * `index_html_minified` - Whether to minify `index.html` after writing
+/// * `options` - The options applied to text files
 ///
 /// # Returns
 ///
@@ -397,6 +713,7 @@ fn write_content_files(
     dir_name: &Path,
     file: &FileData,
     index_html_minified: bool,
+    options: &WriteOptions,
 ) -> Result<()> {
     debug!("Creating directory '{}'", dir_name.display());
     fs::create_dir_all(dir_name).with_context(|| {
@@ -407,9 +724,20 @@ fn write_content_files(
     })?;
 
     for (file_name, content) in &get_file_paths(file) {
+        if is_relocated(file, file_name)
+            || is_skipped(file_name, content)
+        {
+            continue;
+        }
         debug!("Writing content file: '{}'", file_name);
-        write_file(dir_name, file_name, content, index_html_minified)
-            .with_context(|| {
+        write_file(
+            dir_name,
+            file_name,
+            content,
+            index_html_minified,
+            options,
+        )
+        .with_context(|| {
             format!(
                 "Failed to write content file '{}' in '{}'",
                 file_name,
@@ -417,6 +745,78 @@ fn write_content_files(
             )
         })?;
     }
+    write_page_json(dir_name, file, options)?;
+    write_alternates(dir_name, file, index_html_minified, options)
+}
+
+/// Writes the `page.json` sidecar of `file` into `dir_path`, if the page
+/// has one.
+///
+/// # Arguments
+///
+/// * `dir_path` - Directory holding the page's `index.html`
+/// * `file` - The `FileData` object whose `json` is written
+/// * `options` - The options applied to text files
+///
+/// # Returns
+///
+/// `Ok(())` if the sidecar is written or there is none, or an error if
+/// writing fails.
+fn write_page_json(
+    dir_path: &Path,
+    file: &FileData,
+    options: &WriteOptions,
+) -> Result<()> {
+    if file.json.is_empty() {
+        return Ok(());
+    }
+    write_file(dir_path, PAGE_JSON_FILE, &file.json, false, options)
+        .with_context(|| {
+            format!(
+                "Failed to write '{}' in '{}'",
+                PAGE_JSON_FILE,
+                dir_path.display()
+            )
+        })
+}
+
+/// Writes each alternate rendering of `file` to `<template>/index.html`
+/// under `dir_path`.
+///
+/// # Arguments
+///
+/// * `dir_path` - Directory holding the page's `index.html`
+/// * `file` - The `FileData` object whose `alternates` are written
+/// * `minify` - Whether to minify the alternates after writing
+/// * `options` - The options applied to text files
+///
+/// # Returns
+///
+/// `Ok(())` if every alternate is written, or an error if a template
+/// name would write outside `dir_path` or writing fails.
+fn write_alternates(
+    dir_path: &Path,
+    file: &FileData,
+    minify: bool,
+    options: &WriteOptions,
+) -> Result<()> {
+    for (template, html) in &file.alternates {
+        let alternate_dir = confined_path(dir_path, template)?;
+        fs::create_dir_all(&alternate_dir).with_context(|| {
+            format!(
+                "Failed to create alternate directory '{}'",
+                alternate_dir.display()
+            )
+        })?;
+        write_file(&alternate_dir, "index.html", html, minify, options)
+            .with_context(|| {
+                format!(
+                    "Failed to write the '{}' alternate in '{}'",
+                    template,
+                    dir_path.display()
+                )
+            })?;
+    }
     Ok(())
 }
 
@@ -476,3 +876,261 @@ fn print_section_headers(
     debug!("Section headers printed for '{}'", dir_path.display());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confined_path() {
+        let base = Path::new("build");
+
+        assert_eq!(
+            confined_path(base, "blog/post").unwrap(),
+            base.join("blog/post")
+        );
+        for name in ["../evil.html", "a/../../evil", "/etc/passwd", ""]
+        {
+            assert!(confined_path(base, name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_write_rejects_directory_traversal() {
+        let root =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        let build_dir = root.path().join("build");
+        fs::create_dir(&build_dir).unwrap();
+        let file = FileData {
+            name: "../evil.html".to_string(),
+            content: "<p>evil</p>".to_string(),
+            ..Default::default()
+        };
+
+        let result = write_files_to_build_directory(
+            &build_dir,
+            &file,
+            root.path(),
+        );
+
+        assert!(result.is_err());
+        assert!(!root.path().join("evil.html").exists());
+    }
+
+    #[test]
+    fn test_write_skips_empty_generated_files() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        let file = FileData {
+            name: "about.md".to_string(),
+            rss: "<rss/>".to_string(),
+            ..Default::default()
+        };
+
+        write_content_files(
+            dir.path(),
+            &file,
+            false,
+            &WriteOptions::default(),
+        )
+        .expect("Failed to write content files");
+
+        assert!(dir.path().join("index.html").exists());
+        assert!(dir.path().join("rss.xml").exists());
+        for skipped in ["manifest.json", "robots.txt", "sitemap.xml"] {
+            assert!(!dir.path().join(skipped).exists(), "{}", skipped);
+        }
+    }
+
+    #[test]
+    fn test_is_skipped() {
+        for file_name in INDEX_FILES {
+            assert_eq!(
+                is_skipped(file_name, ""),
+                file_name != "index.html",
+                "{}",
+                file_name
+            );
+            assert!(!is_skipped(file_name, "content"), "{}", file_name);
+        }
+    }
+
+    #[test]
+    fn test_output_paths() {
+        let page = FileData {
+            name: "blog.md".to_string(),
+            rss: "<rss/>".to_string(),
+            json: "{}".to_string(),
+            alternates: vec![("print".to_string(), "<p/>".to_string())],
+            ..Default::default()
+        };
+        assert_eq!(
+            output_paths(&page).unwrap(),
+            [
+                PathBuf::from("blog/index.html"),
+                PathBuf::from("blog/rss.xml"),
+                PathBuf::from("blog/page.json"),
+                PathBuf::from("blog/print/index.html"),
+            ]
+        );
+        assert_eq!(page_url_path("blog.md"), "/blog/");
+        assert_eq!(page_url_path("index.md"), "/");
+        let urls = UrlOptions::default();
+        assert_eq!(
+            page_html_path("blog.md", None, &urls),
+            "blog/index.html"
+        );
+        assert_eq!(
+            page_html_path("index.md", None, &urls),
+            "index.html"
+        );
+
+        let home = FileData {
+            name: "index.md".to_string(),
+            cname: "example.com".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            output_paths(&home).unwrap(),
+            [PathBuf::from("CNAME"), PathBuf::from("index.html")]
+        );
+
+        let flat = FileData {
+            name: "blog.md".to_string(),
+            rss: "<rss/>".to_string(),
+            output_path: "blog.html".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            output_paths(&flat).unwrap(),
+            [PathBuf::from("blog.html"), PathBuf::from("blog/rss.xml")]
+        );
+
+        let evil = FileData {
+            name: "../evil.md".to_string(),
+            ..Default::default()
+        };
+        assert!(output_paths(&evil).is_err());
+    }
+
+    #[test]
+    fn test_write_page_to_output_path() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        let templates =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        for (name, output_path) in [
+            ("about.md", "about.html"),
+            ("blog.md", "blog/default.htm"),
+        ] {
+            let file = FileData {
+                name: name.to_string(),
+                content: "<p>Page</p>".to_string(),
+                output_path: output_path.to_string(),
+                ..Default::default()
+            };
+            write_files_to_build_directory(
+                dir.path(),
+                &file,
+                templates.path(),
+            )
+            .expect("Failed to write files");
+        }
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("about.html")).unwrap(),
+            "<p>Page</p>"
+        );
+        assert!(!dir.path().join("about/index.html").exists());
+        assert!(dir.path().join("blog/default.htm").exists());
+        assert!(!dir.path().join("blog/index.html").exists());
+
+        let evil = FileData {
+            name: "about.md".to_string(),
+            output_path: "../evil.html".to_string(),
+            ..Default::default()
+        };
+        assert!(write_files_to_build_directory(
+            dir.path(),
+            &evil,
+            templates.path()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_default_write_options_keep_content() {
+        let options = WriteOptions::default();
+        assert_eq!(
+            options.apply("User-agent: *\nAllow: /"),
+            "User-agent: *\nAllow: /"
+        );
+    }
+
+    #[test]
+    fn test_write_options_line_endings() {
+        let crlf = WriteOptions {
+            line_ending: LineEnding::CrLf,
+            trailing_newline: false,
+        };
+        assert_eq!(crlf.apply("a\nb\r\nc"), "a\r\nb\r\nc");
+
+        let lf = WriteOptions::default();
+        assert_eq!(lf.apply("a\r\nb\r\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn test_write_options_trailing_newline() {
+        let options = WriteOptions {
+            line_ending: LineEnding::Lf,
+            trailing_newline: true,
+        };
+        assert_eq!(options.apply("a"), "a\n");
+        assert_eq!(options.apply("a\n"), "a\n");
+        assert_eq!(options.apply(""), "");
+    }
+
+    #[test]
+    fn test_write_file_applies_options_to_text_files() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        let options = WriteOptions {
+            line_ending: LineEnding::CrLf,
+            trailing_newline: true,
+        };
+
+        write_file(dir.path(), "robots.txt", "a\nb", false, &options)
+            .expect("Failed to write robots.txt");
+        write_file(dir.path(), "rss.xml", "a\nb", false, &options)
+            .expect("Failed to write rss.xml");
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("robots.txt")).unwrap(),
+            "a\r\nb\r\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("rss.xml")).unwrap(),
+            "a\nb"
+        );
+    }
+
+    #[test]
+    fn test_write_page_json_only_when_present() {
+        let dir =
+            tempfile::tempdir().expect("Failed to create temp dir");
+        let mut file = FileData::default();
+
+        write_page_json(dir.path(), &file, &WriteOptions::default())
+            .expect("Failed to skip page.json");
+        assert!(!dir.path().join(PAGE_JSON_FILE).exists());
+
+        file.json = "{}".to_string();
+        write_page_json(dir.path(), &file, &WriteOptions::default())
+            .expect("Failed to write page.json");
+        assert_eq!(
+            fs::read_to_string(dir.path().join(PAGE_JSON_FILE))
+                .unwrap(),
+            "{}"
+        );
+    }
+}